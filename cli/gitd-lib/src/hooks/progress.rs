@@ -107,7 +107,7 @@ impl<'a> From<Namespaced<'a, replication::Success>> for Progress {
             progress.push_str("updated references:\n");
             for updated in updates {
                 let update = match updated {
-                    Updated::Direct { name, target } => {
+                    Updated::Direct { name, target, .. } => {
                         let name = name.strip_prefix(&prefix).unwrap_or(name);
                         format!("+{name}->{target}\n")
                     },
@@ -115,7 +115,7 @@ impl<'a> From<Namespaced<'a, replication::Success>> for Progress {
                         let name = name.strip_prefix(&prefix).unwrap_or(name);
                         format!("+{name}->{target}\n")
                     },
-                    Updated::Prune { name } => {
+                    Updated::Prune { name, .. } => {
                         let name = name.strip_prefix(&prefix).unwrap_or(name);
                         format!("-{name}\n")
                     },
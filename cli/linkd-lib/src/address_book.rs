@@ -0,0 +1,56 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use futures::{FutureExt as _, StreamExt as _};
+use tokio::time;
+use tracing::{error, info, instrument, warn};
+
+use librad::{
+    net::{
+        discovery::AddressBook,
+        peer::{Peer, ProtocolEvent},
+        protocol::RequestPullGuard,
+    },
+    Signer,
+};
+
+/// Interval at which a modified address book is written to disk.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Record the membership transitions of `peer` in the [`AddressBook`], so the
+/// peers we were connected to can be discovered after a restart.
+#[instrument(name = "address book subroutine", skip(peer, book))]
+pub async fn routine<S, G>(peer: Peer<S, G>, mut book: AddressBook) -> anyhow::Result<()>
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    info!(peers = book.len(), "starting address book routine");
+
+    let events = peer.subscribe().fuse();
+    futures::pin_mut!(events);
+    let mut interval = time::interval(PERSIST_INTERVAL);
+
+    loop {
+        futures::select! {
+            event = events.next() => match event {
+                Some(Ok(ProtocolEvent::Membership(transition))) => book.transition(&transition),
+                Some(Ok(_)) => {},
+                Some(Err(err)) => error!(?err, "event error"),
+                None => break,
+            },
+            _ = interval.tick().fuse() => persist(&mut book),
+        }
+    }
+    persist(&mut book);
+
+    Ok(())
+}
+
+fn persist(book: &mut AddressBook) {
+    if let Err(err) = book.persist() {
+        warn!(?err, "error persisting address book")
+    }
+}
@@ -12,23 +12,30 @@ use librad::{
     net::{peer::Peer, protocol::RequestPullGuard},
 };
 use link_async::Spawner;
+use lnk_clib::seed::Seeds;
 
 pub use sockets::Sockets;
 
 pub mod announce;
 pub mod client;
+pub mod connected_peers;
+pub mod events;
 pub mod io;
+pub mod membership;
 pub mod messages;
+pub mod replicate;
 pub mod request_pull;
 mod rpc;
 pub mod sockets;
+pub mod stats;
 pub mod wire_types;
 
-#[instrument(name = "api subroutine", skip(spawner, peer, sockets))]
+#[instrument(name = "api subroutine", skip(spawner, peer, sockets, seeds))]
 pub async fn routine<'a, S, G>(
     spawner: Arc<Spawner>,
     peer: Peer<S, G>,
     sockets: &'a Sockets,
+    seeds: Seeds,
     linger_timeout: Option<Duration>,
     announce_wait_time: Duration,
 ) -> ()
@@ -36,7 +43,16 @@ where
     S: Signer + Clone,
     G: RequestPullGuard,
 {
-    let tasks = Box::pin(rpc::tasks(spawner, peer, sockets.rpc(), announce_wait_time));
+    let tasks = Box::pin(futures::stream::select(
+        rpc::tasks(
+            spawner.clone(),
+            peer.clone(),
+            sockets.rpc(),
+            seeds,
+            announce_wait_time,
+        ),
+        events::tasks(spawner, peer, sockets.events()),
+    ));
     if let Some(timeout) = linger_timeout {
         link_async::tasks::run_until_idle(tasks, timeout).await
     } else {
@@ -20,7 +20,7 @@ use tokio::net::UnixStream;
 
 use librad::{git::Urn, PeerId};
 
-use super::{announce, io, messages, request_pull};
+use super::{announce, connected_peers, io, membership, messages, replicate, request_pull, stats};
 
 pub struct Connection<T> {
     socket: T,
@@ -273,3 +273,44 @@ impl Command<request_pull::Request, request_pull::Response> {
         }
     }
 }
+
+impl Command<replicate::Request, replicate::Response> {
+    /// Replicate `urn` from `peers`, or from the seeds the node was configured
+    /// with if `peers` is empty.
+    pub fn replicate(urn: Urn, peers: Vec<(PeerId, Vec<SocketAddr>)>) -> Self {
+        Self {
+            payload: replicate::Request {
+                urn,
+                peers: peers.into_iter().map(replicate::Remote::from).collect(),
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Command<connected_peers::Request, connected_peers::Response> {
+    pub fn get_connected_peers() -> Self {
+        Self {
+            payload: connected_peers::Request,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Command<membership::Request, membership::Response> {
+    pub fn get_membership_info() -> Self {
+        Self {
+            payload: membership::Request,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Command<stats::Request, stats::Response> {
+    pub fn get_stats() -> Self {
+        Self {
+            payload: stats::Request,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use librad::PeerId;
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Request;
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+#[cbor(transparent)]
+pub struct Response(#[n(0)] pub Vec<PeerId>);
+
+impl From<Vec<PeerId>> for Response {
+    fn from(peers: Vec<PeerId>) -> Self {
+        Self(peers)
+    }
+}
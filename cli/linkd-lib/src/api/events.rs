@@ -0,0 +1,407 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The events API of the p2p node, as outlined in docs/rfc/0696-p2p-node.adoc
+//!
+//! Clients connect to the events socket and send a [`Subscribe`] message
+//! describing which events they are interested in. The node then streams
+//! [`Event`]s matching that subscription until the client hangs up. A client
+//! may send another [`Subscribe`] message at any time to replace its filter.
+//!
+//! Messages use the same length prefixed framing as the RPC API (see
+//! [`super::wire_types`]), except that the CBOR encoded [`Subscribe`] or
+//! [`Event`] takes the place of the message headers and there is no payload.
+
+use std::sync::Arc;
+
+use async_compat::{Compat, CompatExt as _};
+use futures::{
+    future::{self, FutureExt as _},
+    stream::StreamExt as _,
+};
+use git_ref_format::RefString;
+use tokio::net::{
+    unix::{OwnedReadHalf, OwnedWriteHalf},
+    UnixListener,
+    UnixStream,
+};
+
+use librad::{
+    git::Urn,
+    net::{
+        peer::{event::upstream, Peer, ProtocolEvent},
+        protocol::{gossip, membership, request_pull, RequestPullGuard},
+        replication,
+    },
+    PeerId,
+    Signer,
+};
+use link_async::{incoming::UnixListenerExt, Spawner};
+use radicle_git_ext::Oid;
+
+use super::{
+    io::{self, MessageReader, MessageWriter},
+    wire_types::Message,
+};
+
+/// Events the node reports to subscribers of the events socket.
+#[derive(Clone, Debug, PartialEq, minicbor::Encode, minicbor::Decode)]
+pub enum Event {
+    /// A `Have` was received via gossip.
+    #[n(0)]
+    #[cbor(array)]
+    Gossip {
+        #[n(0)]
+        provider: PeerId,
+        #[n(1)]
+        urn: Urn,
+        #[n(2)]
+        rev: Option<Oid>,
+        #[n(3)]
+        origin: Option<PeerId>,
+    },
+    /// Replication of `urn` from `peer` finished.
+    #[n(1)]
+    #[cbor(array)]
+    Replicated {
+        #[n(0)]
+        peer: PeerId,
+        #[n(1)]
+        urn: Urn,
+        #[n(2)]
+        updated: Vec<Updated>,
+    },
+    /// A request-pull from `peer` was served.
+    #[n(2)]
+    #[cbor(array)]
+    RequestPull {
+        #[n(0)]
+        peer: PeerId,
+        #[n(1)]
+        urn: Urn,
+        #[n(2)]
+        success: request_pull::Success,
+    },
+    /// `peer` was promoted to the active membership view.
+    #[n(3)]
+    #[cbor(array)]
+    PeerConnected {
+        #[n(0)]
+        peer: PeerId,
+    },
+    /// `peer` was demoted or evicted from the active membership view.
+    #[n(4)]
+    #[cbor(array)]
+    PeerDisconnected {
+        #[n(0)]
+        peer: PeerId,
+    },
+}
+
+/// A ref which was created, updated or pruned by replication, or an update
+/// which was rejected.
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Encode, minicbor::Decode)]
+pub enum Updated {
+    #[n(0)]
+    #[cbor(array)]
+    Direct {
+        #[n(0)]
+        name: RefString,
+        #[n(1)]
+        target: Oid,
+    },
+    #[n(1)]
+    #[cbor(array)]
+    Symbolic {
+        #[n(0)]
+        name: RefString,
+        #[n(1)]
+        target: RefString,
+    },
+    #[n(2)]
+    #[cbor(array)]
+    Prune {
+        #[n(0)]
+        name: RefString,
+    },
+}
+
+impl From<&replication::Updated> for Updated {
+    fn from(up: &replication::Updated) -> Self {
+        match up {
+            replication::Updated::Direct { name, target, .. } => Self::Direct {
+                name: name.clone(),
+                target: (*target).into(),
+            },
+            replication::Updated::Symbolic { name, target } => Self::Symbolic {
+                name: name.clone(),
+                target: target.clone(),
+            },
+            replication::Updated::Prune { name, .. } => Self::Prune { name: name.clone() },
+        }
+    }
+}
+
+impl From<&replication::Update<'_>> for Updated {
+    fn from(up: &replication::Update<'_>) -> Self {
+        match up {
+            replication::Update::Direct { name, target, .. } => Self::Direct {
+                name: name.clone().into(),
+                target: (*target).into(),
+            },
+            replication::Update::Symbolic { name, target, .. } => Self::Symbolic {
+                name: name.clone().into(),
+                target: target.name.strip_namespace().into(),
+            },
+            replication::Update::Prune { name, .. } => Self::Prune {
+                name: name.clone().into(),
+            },
+        }
+    }
+}
+
+impl From<upstream::Replicated> for Event {
+    fn from(upstream::Replicated { peer, urn, updated }: upstream::Replicated) -> Self {
+        Self::Replicated {
+            peer,
+            urn,
+            updated: updated.iter().map(Updated::from).collect(),
+        }
+    }
+}
+
+impl Event {
+    /// The URN this event is about, if any.
+    pub fn urn(&self) -> Option<&Urn> {
+        match self {
+            Self::Gossip { urn, .. }
+            | Self::Replicated { urn, .. }
+            | Self::RequestPull { urn, .. } => Some(urn),
+            Self::PeerConnected { .. } | Self::PeerDisconnected { .. } => None,
+        }
+    }
+
+    /// The remote peer this event is about.
+    pub fn peer(&self) -> &PeerId {
+        match self {
+            Self::Gossip { provider, .. } => provider,
+            Self::Replicated { peer, .. }
+            | Self::RequestPull { peer, .. }
+            | Self::PeerConnected { peer }
+            | Self::PeerDisconnected { peer } => peer,
+        }
+    }
+
+    fn from_upstream(event: ProtocolEvent) -> Option<Self> {
+        match event {
+            ProtocolEvent::Gossip(gossip) => match *gossip {
+                upstream::Gossip::Put {
+                    provider,
+                    payload: gossip::Payload { urn, rev, origin },
+                    ..
+                } => Some(Self::Gossip {
+                    provider: provider.peer_id,
+                    urn,
+                    rev: rev.map(|gossip::Rev::Git(oid)| oid.into()),
+                    origin,
+                }),
+            },
+            ProtocolEvent::Membership(membership::Transition::Promoted(info)) => {
+                Some(Self::PeerConnected { peer: info.peer_id })
+            },
+            ProtocolEvent::Membership(membership::Transition::Demoted(info)) => {
+                Some(Self::PeerDisconnected { peer: info.peer_id })
+            },
+            ProtocolEvent::Membership(membership::Transition::Evicted(info)) => {
+                Some(Self::PeerDisconnected { peer: info.peer_id })
+            },
+            ProtocolEvent::RequestPull(upstream::RequestPull { peer, urn, success }) => {
+                Some(Self::RequestPull { peer, urn, success })
+            },
+            ProtocolEvent::Replicated(replicated) => Some(replicated.into()),
+            _ => None,
+        }
+    }
+}
+
+/// The filter a client sends to subscribe to events.
+///
+/// An empty set of `urns` or `peers` matches any URN or peer respectively.
+/// Events which are not about a particular URN, such as
+/// [`Event::PeerConnected`], only match if `urns` is empty.
+#[derive(Clone, Debug, Default, PartialEq, minicbor::Encode, minicbor::Decode)]
+#[cbor(map)]
+pub struct Subscribe {
+    #[n(0)]
+    pub urns: Vec<Urn>,
+    #[n(1)]
+    pub peers: Vec<PeerId>,
+}
+
+impl Subscribe {
+    pub fn matches(&self, event: &Event) -> bool {
+        let urn_matches = self.urns.is_empty()
+            || event.urn().map_or(false, |urn| {
+                self.urns.iter().any(|wanted| wanted.id == urn.id)
+            });
+        let peer_matches = self.peers.is_empty() || self.peers.contains(event.peer());
+        urn_matches && peer_matches
+    }
+}
+
+pub fn tasks<S, G>(
+    spawner: Arc<Spawner>,
+    peer: Peer<S, G>,
+    socket: &UnixListener,
+) -> impl futures::stream::Stream<Item = link_async::Task<()>> + Send + '_
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    socket
+        .incoming()
+        .map(move |stream| match stream {
+            Ok(stream) => {
+                tracing::debug!("new events connection");
+                Some(spawner.spawn(subscription(peer.clone(), stream)))
+            },
+            Err(e) => {
+                tracing::error!(err=?e, "error accepting events connection");
+                None
+            },
+        })
+        .take_while(|e| future::ready(e.is_some()))
+        .filter_map(future::ready)
+}
+
+async fn subscription<S, G>(peer: Peer<S, G>, stream: UnixStream)
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    let (recv, send) = stream.into_split();
+    let mut reader = MessageReader::new(recv.compat());
+    let mut writer = MessageWriter::new(send.compat());
+
+    let mut filter = match reader.read_message::<Subscribe>().await {
+        Ok(Some(msg)) => msg.headers,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(err=?e, "failed to read subscription");
+            return;
+        },
+    };
+    tracing::debug!(?filter, "new subscription");
+
+    let events = peer
+        .subscribe()
+        .filter_map(|event| {
+            future::ready(match event {
+                Ok(event) => Event::from_upstream(event),
+                Err(e) => {
+                    tracing::warn!(err=?e, "protocol events error");
+                    None
+                },
+            })
+        })
+        .fuse();
+    futures::pin_mut!(events);
+
+    loop {
+        futures::select! {
+            event = events.next() => match event {
+                Some(event) => {
+                    if !filter.matches(&event) {
+                        continue;
+                    }
+                    let msg = Message { headers: event, payload: None };
+                    if let Err(e) = writer.write_message(&msg).await {
+                        tracing::debug!(err=?e, "error sending event, closing");
+                        break;
+                    }
+                },
+                None => break,
+            },
+            msg = reader.read_message::<Subscribe>().fuse() => match msg {
+                Ok(Some(msg)) => {
+                    tracing::debug!(filter=?msg.headers, "subscription updated");
+                    filter = msg.headers;
+                },
+                Ok(None) => {
+                    tracing::debug!("closing events connection");
+                    break;
+                },
+                Err(e) => {
+                    tracing::warn!(err=?e, "error receiving subscription, closing");
+                    break;
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("unable to decode event")]
+    DecodeFailed,
+}
+
+/// A client subscription to the events socket.
+pub struct Subscription {
+    reader: MessageReader<Compat<OwnedReadHalf>>,
+    writer: MessageWriter<Compat<OwnedWriteHalf>>,
+}
+
+impl Subscription {
+    /// Connect to the events socket at `socket_path` and subscribe to events
+    /// matching `filter`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no tokio runtime is available
+    pub async fn connect<P: AsRef<std::path::Path>>(
+        socket_path: P,
+        filter: Subscribe,
+    ) -> Result<Self, Error> {
+        let stream = UnixStream::connect(socket_path).await?;
+        let (recv, send) = stream.into_split();
+        let mut this = Self {
+            reader: MessageReader::new(recv.compat()),
+            writer: MessageWriter::new(send.compat()),
+        };
+        this.update(filter).await?;
+        Ok(this)
+    }
+
+    /// Replace the filter of this subscription.
+    ///
+    /// # Cancellation
+    ///
+    /// This method is not cancel safe
+    pub async fn update(&mut self, filter: Subscribe) -> Result<(), Error> {
+        let msg = Message {
+            headers: filter,
+            payload: None,
+        };
+        Ok(self.writer.write_message(&msg).await?)
+    }
+
+    /// Wait for the next event. A return value of `None` indicates that the
+    /// node closed the connection.
+    ///
+    /// # Cancellation
+    ///
+    /// This method is cancel safe
+    pub async fn next(&mut self) -> Result<Option<Event>, Error> {
+        match self.reader.read_message::<Event>().await {
+            Ok(msg) => Ok(msg.map(|msg| msg.headers)),
+            Err(io::Error::Io(e)) => Err(Error::Io(e)),
+            Err(io::Error::UnexpectedEof) => {
+                Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+            },
+            Err(io::Error::DecodeFailed) => Err(Error::DecodeFailed),
+        }
+    }
+}
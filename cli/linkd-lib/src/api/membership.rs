@@ -0,0 +1,58 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use librad::{net::peer::MembershipInfo, PeerId};
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Request;
+
+/// The success payload of a `get-membership-info` request.
+///
+/// Encoded as a CBOR map with the text keys `active` and `passive`, as
+/// specified in docs/rfc/0696-p2p-node.adoc.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub active: Vec<PeerId>,
+    pub passive: Vec<PeerId>,
+}
+
+impl From<MembershipInfo> for Response {
+    fn from(MembershipInfo { active, passive }: MembershipInfo) -> Self {
+        Self { active, passive }
+    }
+}
+
+impl minicbor::Encode for Response {
+    fn encode<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.map(2)?
+            .str("active")?
+            .encode(&self.active)?
+            .str("passive")?
+            .encode(&self.passive)?;
+        Ok(())
+    }
+}
+
+impl<'b> minicbor::Decode<'b> for Response {
+    fn decode(d: &mut minicbor::Decoder<'b>) -> Result<Self, minicbor::decode::Error> {
+        let len = d.map()?.ok_or(minicbor::decode::Error::Message(
+            "expected definite length map",
+        ))?;
+        let mut active = None;
+        let mut passive = None;
+        for _ in 0..len {
+            match d.str()? {
+                "active" => active = Some(d.decode()?),
+                "passive" => passive = Some(d.decode()?),
+                _ => d.skip()?,
+            }
+        }
+        Ok(Self {
+            active: active.ok_or(minicbor::decode::Error::Message("missing `active`"))?,
+            passive: passive.ok_or(minicbor::decode::Error::Message("missing `passive`"))?,
+        })
+    }
+}
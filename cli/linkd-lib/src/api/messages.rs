@@ -5,7 +5,7 @@
 
 use rand::Rng;
 
-use super::{announce, request_pull};
+use super::{announce, connected_peers, membership, replicate, request_pull, stats};
 
 #[derive(
     Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, minicbor::Decode, minicbor::Encode,
@@ -77,7 +77,11 @@ pub struct Request {
 #[derive(Clone, Debug, PartialEq)]
 pub enum RequestPayload {
     Announce(announce::Request),
+    GetConnectedPeers(connected_peers::Request),
+    GetMembershipInfo(membership::Request),
+    GetStats(stats::Request),
     RequestPull(request_pull::Request),
+    Replicate(replicate::Request),
 }
 
 impl From<announce::Request> for RequestPayload {
@@ -86,12 +90,36 @@ impl From<announce::Request> for RequestPayload {
     }
 }
 
+impl From<connected_peers::Request> for RequestPayload {
+    fn from(x: connected_peers::Request) -> Self {
+        Self::GetConnectedPeers(x)
+    }
+}
+
+impl From<membership::Request> for RequestPayload {
+    fn from(x: membership::Request) -> Self {
+        Self::GetMembershipInfo(x)
+    }
+}
+
+impl From<stats::Request> for RequestPayload {
+    fn from(x: stats::Request) -> Self {
+        Self::GetStats(x)
+    }
+}
+
 impl From<request_pull::Request> for RequestPayload {
     fn from(x: request_pull::Request) -> Self {
         Self::RequestPull(x)
     }
 }
 
+impl From<replicate::Request> for RequestPayload {
+    fn from(x: replicate::Request) -> Self {
+        Self::Replicate(x)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Response<P> {
     pub request_id: RequestId,
@@ -109,7 +137,11 @@ pub enum ResponsePayload<P> {
 #[derive(Clone, Debug, PartialEq)]
 pub enum SomeSuccess {
     Announce(announce::Response),
+    GetConnectedPeers(connected_peers::Response),
+    GetMembershipInfo(membership::Response),
+    GetStats(stats::Response),
     RequestPull(request_pull::Response),
+    Replicate(replicate::Response),
 }
 
 impl From<announce::Response> for SomeSuccess {
@@ -118,12 +150,36 @@ impl From<announce::Response> for SomeSuccess {
     }
 }
 
+impl From<connected_peers::Response> for SomeSuccess {
+    fn from(x: connected_peers::Response) -> Self {
+        Self::GetConnectedPeers(x)
+    }
+}
+
+impl From<membership::Response> for SomeSuccess {
+    fn from(x: membership::Response) -> Self {
+        Self::GetMembershipInfo(x)
+    }
+}
+
+impl From<stats::Response> for SomeSuccess {
+    fn from(x: stats::Response) -> Self {
+        Self::GetStats(x)
+    }
+}
+
 impl From<request_pull::Response> for SomeSuccess {
     fn from(x: request_pull::Response) -> Self {
         Self::RequestPull(x)
     }
 }
 
+impl From<replicate::Response> for SomeSuccess {
+    fn from(x: replicate::Response) -> Self {
+        Self::Replicate(x)
+    }
+}
+
 impl minicbor::Encode for SomeSuccess {
     fn encode<W: minicbor::encode::Write>(
         &self,
@@ -131,7 +187,11 @@ impl minicbor::Encode for SomeSuccess {
     ) -> Result<(), minicbor::encode::Error<W::Error>> {
         match self {
             SomeSuccess::Announce(x) => e.encode(x)?.ok(),
+            SomeSuccess::GetConnectedPeers(x) => e.encode(x)?.ok(),
+            SomeSuccess::GetMembershipInfo(x) => e.encode(x)?.ok(),
+            SomeSuccess::GetStats(x) => e.encode(x)?.ok(),
             SomeSuccess::RequestPull(x) => e.encode(x)?.ok(),
+            SomeSuccess::Replicate(x) => e.encode(x)?.ok(),
         }
     }
 }
@@ -0,0 +1,110 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::net::SocketAddr;
+
+use either::Either;
+use librad::{git::Urn, net::replication, PeerId};
+
+use super::events::Updated;
+
+/// Replicate `urn` from the given `peers`.
+///
+/// If `peers` is empty, the seeds the node was configured with are used.
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Request {
+    #[n(0)]
+    pub urn: Urn,
+    #[n(1)]
+    pub peers: Vec<Remote>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Remote {
+    #[n(0)]
+    pub peer: PeerId,
+    #[n(1)]
+    pub addrs: Vec<SocketAddr>,
+}
+
+impl From<(PeerId, Vec<SocketAddr>)> for Remote {
+    fn from((peer, addrs): (PeerId, Vec<SocketAddr>)) -> Self {
+        Self { peer, addrs }
+    }
+}
+
+impl From<Remote> for (PeerId, Vec<SocketAddr>) {
+    fn from(Remote { peer, addrs }: Remote) -> Self {
+        (peer, addrs)
+    }
+}
+
+/// The outcome of replicating from each of the requested peers.
+///
+/// The node replies with a [`Response`] even if replicating failed for all of
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+#[cbor(transparent)]
+pub struct Response(#[n(0)] pub Vec<Replicated>);
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Replicated {
+    #[n(0)]
+    pub peer: PeerId,
+    #[n(1)]
+    pub result: Outcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub enum Outcome {
+    #[n(0)]
+    Success(#[n(0)] Success),
+    #[n(1)]
+    Failure(#[n(0)] String),
+}
+
+/// A summary of [`replication::Success`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Success {
+    /// Refs which were created, updated or pruned.
+    #[n(0)]
+    pub updated: Vec<Updated>,
+    /// Ref updates which were rejected, eg. because they were not
+    /// fast-forwards.
+    #[n(1)]
+    pub rejected: Vec<Updated>,
+    /// Top-level URNs which were created.
+    #[n(2)]
+    pub created: Vec<Urn>,
+    /// Peers which are now tracked.
+    #[n(3)]
+    pub tracked_peers: Vec<PeerId>,
+    /// URNs which are now tracked.
+    #[n(4)]
+    pub tracked_urns: Vec<Urn>,
+    #[n(5)]
+    pub requires_confirmation: bool,
+}
+
+impl From<&replication::Success> for Success {
+    fn from(s: &replication::Success) -> Self {
+        let (tracked_peers, tracked_urns) =
+            s.tracked()
+                .iter()
+                .fold((Vec::new(), Vec::new()), |(mut peers, mut urns), t| {
+                    match t {
+                        Either::Left(peer) => peers.push(*peer),
+                        Either::Right(urn) => urns.push(Urn::from(urn.clone())),
+                    }
+                    (peers, urns)
+                });
+        Self {
+            updated: s.updated_refs().iter().map(Updated::from).collect(),
+            rejected: s.rejected_updates().iter().map(Updated::from).collect(),
+            created: s.urns_created().map(Urn::from).collect(),
+            tracked_peers,
+            tracked_urns,
+            requires_confirmation: s.requires_confirmation,
+        }
+    }
+}
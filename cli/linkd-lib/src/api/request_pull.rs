@@ -14,7 +14,7 @@ pub struct Request {
 
 #[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
 #[cbor(transparent)]
-pub struct Response(#[n(0)] request_pull::Success);
+pub struct Response(#[n(0)] pub request_pull::Success);
 
 impl From<request_pull::Success> for Response {
     fn from(x: request_pull::Success) -> Self {
@@ -4,7 +4,7 @@
 // Linking Exception. For full terms see the included LICENSE file.
 
 use futures::{future::FutureExt, stream::FuturesUnordered};
-use std::{marker::PhantomData, panic, sync::Arc, time::Duration};
+use std::{marker::PhantomData, net::SocketAddr, panic, sync::Arc, time::Duration};
 
 use futures::stream::StreamExt;
 use tokio::{
@@ -14,21 +14,28 @@ use tokio::{
 
 use librad::{
     net::{peer::Peer, protocol::RequestPullGuard},
+    PeerId,
     Signer,
 };
 use link_async::{incoming::UnixListenerExt, Spawner};
+use lnk_clib::seed::Seeds;
 
 use super::{
     announce,
+    connected_peers,
     io::{self, SocketTransportError, Transport},
+    membership,
     messages,
+    replicate,
     request_pull,
+    stats,
 };
 
 pub fn tasks<S, G>(
     spawner: Arc<Spawner>,
     peer: Peer<S, G>,
     socket: &UnixListener,
+    seeds: Seeds,
     announce_wait_time: Duration,
 ) -> impl futures::stream::Stream<Item = link_async::Task<()>> + Send + '_
 where
@@ -44,6 +51,7 @@ where
                     spawner.clone(),
                     peer.clone(),
                     stream,
+                    seeds.clone(),
                     announce_wait_time,
                 )))
             },
@@ -62,6 +70,7 @@ async fn rpc<S, G>(
     spawner: Arc<Spawner>,
     peer: Peer<S, G>,
     stream: UnixStream,
+    seeds: Seeds,
     announce_wait_time: Duration,
 ) where
     S: Signer + Clone,
@@ -93,12 +102,48 @@ async fn rpc<S, G>(
                                     listener.ack().await;
                                     listener.handle(peer, announce_wait_time, p).boxed()
                                 },
+                                messages::RequestPayload::GetConnectedPeers(p) => {
+                                    let mut listener = Listener::<connected_peers::Response>::new(
+                                        next.mode,
+                                        sx.clone(),
+                                    );
+                                    tracing::info!(?p, "dispatching request");
+                                    listener.ack().await;
+                                    listener.handle(peer).boxed()
+                                },
+                                messages::RequestPayload::GetMembershipInfo(p) => {
+                                    let mut listener = Listener::<membership::Response>::new(
+                                        next.mode,
+                                        sx.clone(),
+                                    );
+                                    tracing::info!(?p, "dispatching request");
+                                    listener.ack().await;
+                                    listener.handle(peer).boxed()
+                                },
+                                messages::RequestPayload::GetStats(p) => {
+                                    let mut listener = Listener::<stats::Response>::new(
+                                        next.mode,
+                                        sx.clone(),
+                                    );
+                                    tracing::info!(?p, "dispatching request");
+                                    listener.ack().await;
+                                    listener.handle(peer).boxed()
+                                },
                                 messages::RequestPayload::RequestPull(p) => {
                                     let mut listener = Listener::request_pull(next.mode, sx.clone());
                                     tracing::info!(?p, "dispatching request");
                                     listener.ack().await;
                                     listener.handle(peer, p).boxed()
-                                }
+                                },
+                                messages::RequestPayload::Replicate(p) => {
+                                    let mut listener = Listener::<replicate::Response>::new(
+                                        next.mode,
+                                        sx.clone(),
+                                    );
+                                    tracing::info!(?p, "dispatching request");
+                                    listener.ack().await;
+                                    listener.handle(peer, seeds.clone(), p).boxed()
+                                },
                             })
                         };
                         running_handlers.push(handler);
@@ -183,6 +228,18 @@ impl From<messages::RequestMode> for ListenerInterest {
 }
 
 impl<P> Listener<P> {
+    fn new(
+        mode: messages::RequestMode,
+        send: Sender<messages::Response<messages::SomeSuccess>>,
+    ) -> Self {
+        Self {
+            request_id: Default::default(),
+            send,
+            interest: mode.into(),
+            _marker: PhantomData,
+        }
+    }
+
     async fn ack(&mut self) {
         self.send(messages::ResponsePayload::Ack).await
     }
@@ -277,6 +334,46 @@ impl Listener<announce::Response> {
     }
 }
 
+impl Listener<connected_peers::Response> {
+    #[tracing::instrument(skip(self, peer))]
+    async fn handle<S, G>(mut self, peer: Peer<S, G>)
+    where
+        S: Signer + Clone,
+        G: RequestPullGuard,
+    {
+        tracing::info!("received get-connected-peers request");
+        let connected = peer.connected_peers().await;
+        self.success(connected_peers::Response::from(connected).into())
+            .await;
+    }
+}
+
+impl Listener<membership::Response> {
+    #[tracing::instrument(skip(self, peer))]
+    async fn handle<S, G>(mut self, peer: Peer<S, G>)
+    where
+        S: Signer + Clone,
+        G: RequestPullGuard,
+    {
+        tracing::info!("received get-membership-info request");
+        let info = peer.membership().await;
+        self.success(membership::Response::from(info).into()).await;
+    }
+}
+
+impl Listener<stats::Response> {
+    #[tracing::instrument(skip(self, peer))]
+    async fn handle<S, G>(mut self, peer: Peer<S, G>)
+    where
+        S: Signer + Clone,
+        G: RequestPullGuard,
+    {
+        tracing::info!("received get-stats request");
+        let stats = peer.stats().await;
+        self.success(stats::Response::from(stats).into()).await;
+    }
+}
+
 impl Listener<request_pull::Response> {
     fn request_pull(
         mode: messages::RequestMode,
@@ -346,3 +443,76 @@ impl Listener<request_pull::Response> {
         }
     }
 }
+
+impl Listener<replicate::Response> {
+    #[tracing::instrument(skip(self, peer, seeds))]
+    async fn handle<S, G>(
+        mut self,
+        peer: Peer<S, G>,
+        seeds: Seeds,
+        replicate::Request { urn, peers }: replicate::Request,
+    ) where
+        S: Signer + Clone,
+        G: RequestPullGuard,
+    {
+        tracing::info!(urn = %urn, "received replicate request");
+        let remotes = if peers.is_empty() {
+            seeds
+                .0
+                .into_iter()
+                .map(<(PeerId, Vec<SocketAddr>)>::from)
+                .collect::<Vec<_>>()
+        } else {
+            peers
+                .into_iter()
+                .map(<(PeerId, Vec<SocketAddr>)>::from)
+                .collect()
+        };
+        if remotes.is_empty() {
+            self.error("no peers given and no seeds configured".to_string())
+                .await;
+            return;
+        }
+
+        let client = match peer.client() {
+            Err(err) => {
+                tracing::error!(err = %err, "failed to initialise client");
+                self.error("replication failed due to internal client error".to_string())
+                    .await;
+                return;
+            },
+            Ok(client) => client,
+        };
+
+        let mut replicated = Vec::with_capacity(remotes.len());
+        for (remote, addrs) in remotes {
+            self.progress(format!("replicating `{urn}` from `{remote}`"))
+                .await;
+            let result = match client.replicate((remote, addrs), urn.clone(), None).await {
+                Ok(success) => {
+                    let success = replicate::Success::from(&success);
+                    self.progress(format!(
+                        "replicated `{urn}` from `{remote}`, {} refs updated",
+                        success.updated.len()
+                    ))
+                    .await;
+                    replicate::Outcome::Success(success)
+                },
+                Err(err) => {
+                    tracing::warn!(peer = %remote, err = %err, "replication failed");
+                    self.progress(format!("failed to replicate from `{remote}`: {err}"))
+                        .await;
+                    replicate::Outcome::Failure(err.to_string())
+                },
+            };
+            replicated.push(replicate::Replicated {
+                peer: remote,
+                result,
+            });
+        }
+
+        // Failures are reported per peer, even if replication failed for all
+        // of them.
+        self.success(replicate::Response(replicated).into()).await;
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{collections::BTreeMap, net::SocketAddr};
+
+use librad::{net::peer::Stats, PeerId};
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct Request;
+
+/// The success payload of a `get-stats` request.
+///
+/// Encoded as a CBOR map with text keys, as specified in
+/// docs/rfc/0696-p2p-node.adoc. Unknown keys are ignored when decoding, so
+/// that the node may report additional statistics in the future.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub connections_total: u64,
+    pub membership_active: u64,
+    pub membership_passive: u64,
+    pub connected_peers: BTreeMap<PeerId, Vec<SocketAddr>>,
+    pub caches: CacheStats,
+    /// Not reported by nodes predating bandwidth accounting, in which case it
+    /// decodes as zero.
+    pub bandwidth: BandwidthStats,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct CacheStats {
+    #[n(0)]
+    pub urns: UrnsCacheStats,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct UrnsCacheStats {
+    #[n(0)]
+    pub elements: u64,
+    #[n(1)]
+    pub fingerprints: u64,
+}
+
+/// The total number of bytes transferred via `git` streams.
+#[derive(Clone, Debug, Default, PartialEq, Eq, minicbor::Decode, minicbor::Encode)]
+pub struct BandwidthStats {
+    #[n(0)]
+    pub upload: u64,
+    #[n(1)]
+    pub download: u64,
+}
+
+impl From<Stats> for Response {
+    fn from(stats: Stats) -> Self {
+        Self {
+            connections_total: stats.connections_total as u64,
+            membership_active: stats.membership_active as u64,
+            membership_passive: stats.membership_passive as u64,
+            connected_peers: stats.connected_peers.into_iter().collect(),
+            caches: CacheStats {
+                urns: UrnsCacheStats {
+                    elements: stats.caches.urns.elements as u64,
+                    fingerprints: stats.caches.urns.fingerprints as u64,
+                },
+            },
+            bandwidth: BandwidthStats {
+                upload: stats.bandwidth.total.upload,
+                download: stats.bandwidth.total.download,
+            },
+        }
+    }
+}
+
+impl minicbor::Encode for Response {
+    fn encode<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.map(6)?
+            .str("connections-total")?
+            .u64(self.connections_total)?
+            .str("membership-active")?
+            .u64(self.membership_active)?
+            .str("membership-passive")?
+            .u64(self.membership_passive)?
+            .str("connected-peers")?
+            .encode(&self.connected_peers)?
+            .str("caches")?
+            .encode(&self.caches)?
+            .str("bandwidth")?
+            .encode(&self.bandwidth)?;
+        Ok(())
+    }
+}
+
+impl<'b> minicbor::Decode<'b> for Response {
+    fn decode(d: &mut minicbor::Decoder<'b>) -> Result<Self, minicbor::decode::Error> {
+        use minicbor::decode::Error;
+
+        let len = d
+            .map()?
+            .ok_or(Error::Message("expected definite length map"))?;
+        let mut connections_total = None;
+        let mut membership_active = None;
+        let mut membership_passive = None;
+        let mut connected_peers = None;
+        let mut caches = None;
+        let mut bandwidth = None;
+        for _ in 0..len {
+            match d.str()? {
+                "connections-total" => connections_total = Some(d.u64()?),
+                "membership-active" => membership_active = Some(d.u64()?),
+                "membership-passive" => membership_passive = Some(d.u64()?),
+                "connected-peers" => connected_peers = Some(d.decode()?),
+                "caches" => caches = Some(d.decode()?),
+                "bandwidth" => bandwidth = Some(d.decode()?),
+                _ => d.skip()?,
+            }
+        }
+        Ok(Self {
+            connections_total: connections_total
+                .ok_or(Error::Message("missing `connections-total`"))?,
+            membership_active: membership_active
+                .ok_or(Error::Message("missing `membership-active`"))?,
+            membership_passive: membership_passive
+                .ok_or(Error::Message("missing `membership-passive`"))?,
+            connected_peers: connected_peers.ok_or(Error::Message("missing `connected-peers`"))?,
+            caches: caches.ok_or(Error::Message("missing `caches`"))?,
+            bandwidth: bandwidth.unwrap_or_default(),
+        })
+    }
+}
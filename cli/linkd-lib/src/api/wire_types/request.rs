@@ -41,9 +41,23 @@ impl From<messages::Request> for Request {
             messages::RequestPayload::Announce(announce) => {
                 (minicbor::to_vec(announce).unwrap(), Kind::Announce)
             },
+            messages::RequestPayload::GetConnectedPeers(connected_peers) => (
+                minicbor::to_vec(connected_peers).unwrap(),
+                Kind::GetConnectedPeers,
+            ),
+            messages::RequestPayload::GetMembershipInfo(membership) => (
+                minicbor::to_vec(membership).unwrap(),
+                Kind::GetMembershipInfo,
+            ),
+            messages::RequestPayload::GetStats(stats) => {
+                (minicbor::to_vec(stats).unwrap(), Kind::GetStats)
+            },
             messages::RequestPayload::RequestPull(request_pull) => {
                 (minicbor::to_vec(request_pull).unwrap(), Kind::RequestPull)
             },
+            messages::RequestPayload::Replicate(replicate) => {
+                (minicbor::to_vec(replicate).unwrap(), Kind::Replicate)
+            },
         };
         Request {
             headers: Headers {
@@ -75,9 +89,19 @@ impl TryFrom<Request> for messages::Request {
         let payload_bytes = value.payload.ok_or(DecodeError::MissingPayload)?;
         let payload = match value.headers.kind {
             Kind::Announce => messages::RequestPayload::Announce(minicbor::decode(&payload_bytes)?),
+            Kind::GetConnectedPeers => {
+                messages::RequestPayload::GetConnectedPeers(minicbor::decode(&payload_bytes)?)
+            },
+            Kind::GetMembershipInfo => {
+                messages::RequestPayload::GetMembershipInfo(minicbor::decode(&payload_bytes)?)
+            },
+            Kind::GetStats => messages::RequestPayload::GetStats(minicbor::decode(&payload_bytes)?),
             Kind::RequestPull => {
                 messages::RequestPayload::RequestPull(minicbor::decode(&payload_bytes)?)
             },
+            Kind::Replicate => {
+                messages::RequestPayload::Replicate(minicbor::decode(&payload_bytes)?)
+            },
             Kind::Unknown(other) => return Err(DecodeError::UnknownRequestKind(other)),
         };
         Ok(messages::Request {
@@ -88,14 +112,20 @@ impl TryFrom<Request> for messages::Request {
     }
 }
 
-// TODO: Introduce get-connected-peers, get-membership-info, and get-stats -- 2,
-// 3, and 4 respectively.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Kind {
     // CBOR encode and decode maps to 1
     Announce,
+    // CBOR encode and decode maps to 2
+    GetConnectedPeers,
+    // CBOR encode and decode maps to 3
+    GetMembershipInfo,
+    // CBOR encode and decode maps to 4
+    GetStats,
     // CBOR encode and decode maps to 5
     RequestPull,
+    // CBOR encode and decode maps to 6
+    Replicate,
     Unknown(u8),
 }
 
@@ -117,7 +147,11 @@ impl minicbor::Encode for Kind {
     ) -> Result<(), minicbor::encode::Error<W::Error>> {
         let val = match self {
             Self::Announce => 1,
+            Self::GetConnectedPeers => 2,
+            Self::GetMembershipInfo => 3,
+            Self::GetStats => 4,
             Self::RequestPull => 5,
+            Self::Replicate => 6,
             Self::Unknown(other) => *other,
         };
         e.u8(val)?;
@@ -129,7 +163,11 @@ impl<'b> minicbor::Decode<'b> for Kind {
     fn decode(d: &mut minicbor::Decoder<'b>) -> Result<Self, minicbor::decode::Error> {
         Ok(match d.u8()? {
             1 => Self::Announce,
+            2 => Self::GetConnectedPeers,
+            3 => Self::GetMembershipInfo,
+            4 => Self::GetStats,
             5 => Self::RequestPull,
+            6 => Self::Replicate,
             other => Self::Unknown(other),
         })
     }
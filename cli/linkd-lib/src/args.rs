@@ -3,11 +3,10 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-// TODO(xla): Expose discovery args.
 // TODO(xla): Expose storage args.
 // TODO(xla): Expose logging args.
 
-use std::{fmt, net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
+use std::{fmt, net::SocketAddr, num::NonZeroU64, path::PathBuf, str::FromStr, time::Duration};
 
 use clap::Parser;
 
@@ -54,9 +53,21 @@ pub struct Args {
     #[clap(long, default_value_t)]
     pub signer: Signer,
 
+    #[clap(flatten)]
+    pub bandwidth: BandwidthArgs,
+
+    #[clap(flatten)]
+    pub discovery: DiscoveryArgs,
+
+    #[clap(flatten)]
+    pub hooks: HooksArgs,
+
     #[clap(flatten)]
     pub key: KeyArgs,
 
+    #[clap(flatten)]
+    pub maintenance: MaintenanceArgs,
+
     #[clap(flatten)]
     pub metrics: MetricsArgs,
 
@@ -224,6 +235,91 @@ fn parse_lnk_home(src: &str) -> LnkHome {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Parser)]
+pub struct BandwidthArgs {
+    /// The number of bytes the node serves to a single peer within the
+    /// bandwidth window. If not specified, uploads to a peer are unlimited.
+    #[clap(long = "upload-limit-peer", name = "upload-limit-peer")]
+    pub upload_per_peer: Option<NonZeroU64>,
+
+    /// The number of bytes the node serves of a single URN within the
+    /// bandwidth window. If not specified, uploads of a URN are unlimited.
+    #[clap(long = "upload-limit-urn", name = "upload-limit-urn")]
+    pub upload_per_urn: Option<NonZeroU64>,
+
+    /// The number of bytes the node fetches from a single peer within the
+    /// bandwidth window. If not specified, downloads from a peer are
+    /// unlimited.
+    #[clap(long = "download-limit-peer", name = "download-limit-peer")]
+    pub download_per_peer: Option<NonZeroU64>,
+
+    /// The number of bytes the node fetches of a single URN within the
+    /// bandwidth window. If not specified, downloads of a URN are unlimited.
+    #[clap(long = "download-limit-urn", name = "download-limit-urn")]
+    pub download_per_urn: Option<NonZeroU64>,
+
+    /// The number of seconds over which the bandwidth limits replenish. Must
+    /// not be zero.
+    #[clap(
+        long = "bandwidth-window",
+        name = "bandwidth-window",
+        default_value = "3600"
+    )]
+    pub window: NonZeroU64,
+}
+
+impl Default for BandwidthArgs {
+    fn default() -> Self {
+        Self {
+            upload_per_peer: None,
+            upload_per_urn: None,
+            download_per_peer: None,
+            download_per_urn: None,
+            window: NonZeroU64::new(3600).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Parser)]
+pub struct DiscoveryArgs {
+    /// Announce the node on the local network via mDNS, and connect to other
+    /// nodes of the same network announced this way.
+    #[clap(long)]
+    pub mdns: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Parser)]
+pub struct HooksArgs {
+    /// The number of notifications buffered for each hook, before further
+    /// notifications are dropped.
+    #[clap(long = "hook-buffer", name = "hook-buffer", default_value = "10")]
+    pub buffer: usize,
+
+    /// The number of milliseconds to wait for a hook to exit after it was sent
+    /// the end-of-transmission message, before it is killed.
+    #[clap(long = "hook-timeout", name = "hook-timeout", default_value = "2000")]
+    pub timeout: u64,
+}
+
+impl Default for HooksArgs {
+    fn default() -> Self {
+        Self {
+            buffer: 10,
+            timeout: 2000,
+        }
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Parser)]
+pub struct MaintenanceArgs {
+    /// The number of seconds between runs of the git maintenance tasks on the
+    /// monorepo (repacking, commit-graph and multi-pack-index updates). If not
+    /// specified, no maintenance is performed by the node, and `lnk
+    /// maintenance` should be run regularly instead.
+    #[clap(long = "maintenance-interval", name = "maintenance-interval")]
+    pub interval: Option<u64>,
+}
+
 #[derive(Debug, Eq, PartialEq, Parser)]
 pub struct MetricsArgs {
     /// Provider for metrics collection.
@@ -7,6 +7,7 @@ use std::{
     convert::TryFrom,
     io,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs as _},
+    num::NonZeroU64,
     time::Duration,
 };
 
@@ -20,16 +21,22 @@ use tracing::warn;
 
 use librad::{
     crypto::{BoxedSigner, IntoSecretKeyError},
-    git::storage,
+    git::{hooks::hook, storage},
     keystore::SecretKeyExt as _,
     net,
-    net::{discovery, peer::Config as PeerConfig, protocol::membership},
+    net::{
+        bandwidth,
+        discovery::{self, book, AddressBook, Mdns},
+        peer::Config as PeerConfig,
+        protocol::membership,
+    },
     profile::{LnkHome, Profile},
+    PeerId,
     SecretKey,
 };
 use lnk_clib::keys;
 
-use crate::{args, request_pull, tracking::Tracker};
+use crate::{args, hooks, request_pull, tracking::Tracker};
 
 use lnk_clib::seed::{self, store::FileStore, Seeds};
 
@@ -63,6 +70,9 @@ pub enum Error {
     #[error("no seed nodes could be resolved")]
     NoSeeds,
 
+    #[error(transparent)]
+    Mdns(#[from] discovery::mdns::Error),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 
@@ -91,12 +101,23 @@ impl Default for RunMode {
 }
 
 pub struct Cfg<Disco, Signer, Auth> {
+    /// The peers seen in previous runs, which are also part of `disco`.
+    pub address_book: AddressBook,
     pub disco: Disco,
+    /// Discovery on the local network, in addition to `disco`.
+    pub mdns: Option<Mdns>,
+    pub hooks: hooks::Config,
+    /// The interval at which to run git maintenance on the monorepo, if at
+    /// all.
+    pub maintenance: Option<Duration>,
     pub metrics: Option<Metrics>,
     pub peer: PeerConfig<Signer, Auth>,
     pub tracker: Option<Tracker>,
     pub run_mode: RunMode,
     pub profile: Profile,
+    /// The seeds used for bootstrapping, which are also the default peers to
+    /// replicate from.
+    pub seeds: Seeds,
 }
 
 impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
@@ -104,13 +125,21 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
         let membership = membership::Params::default();
         let profile = Profile::try_from(args)?;
 
+        let address_book = {
+            let path = profile.paths().address_book_file();
+            AddressBook::load(path, book::Config::default()).unwrap_or_else(|err| {
+                warn!(err = %err, "failed to load address book, starting with an empty one");
+                AddressBook::new(path, book::Config::default())
+            })
+        };
+
         let seeds = if !args.bootstraps.is_empty() {
             let (seeds, failures) = Seeds::resolve(args.bootstraps.iter()).await;
             for fail in failures {
                 tracing::warn!("failed to load bootstrap seed: {}", fail);
             }
 
-            if seeds.is_empty() {
+            if seeds.is_empty() && address_book.is_empty() {
                 return Err(Error::NoBootstrap);
             }
 
@@ -123,18 +152,32 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
                 tracing::warn!("failed to load configured seed: {}", fail)
             }
 
-            if seeds.is_empty() && !failures.is_empty() {
+            if seeds.is_empty() && !failures.is_empty() && address_book.is_empty() {
                 return Err(Error::NoSeeds);
             }
 
             seeds
         };
-        let disco = discovery::Static::try_from(seeds)?;
+        let mut disco = discovery::Static::try_from(seeds.clone())?;
+        // Enough candidates to fill the active view, even if some are no
+        // longer reachable.
+        disco.extend(
+            address_book
+                .best(membership.max_active * 2)
+                .into_iter()
+                .map(<(PeerId, Vec<SocketAddr>)>::from),
+        );
         let signer = construct_signer(args, &profile).await?;
 
         // Ensure the storage is accessible for the created profile and signer.
         storage::Storage::init(profile.paths(), signer.clone())?;
 
+        let mdns = if args.discovery.mdns {
+            Some(Mdns::new(signer.peer_id(), args.protocol.network.clone())?)
+        } else {
+            None
+        };
+
         let listen_addr = match args.protocol.listen {
             args::ProtocolListen::Any => *ANY,
             args::ProtocolListen::Localhost => *LOCALHOST,
@@ -153,6 +196,24 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
             None => None,
         };
 
+        let maintenance = args.maintenance.interval.map(Duration::from_secs);
+
+        let bandwidth = {
+            let per = Duration::from_secs(args.bandwidth.window.get());
+            let limit =
+                |bytes: Option<NonZeroU64>| bytes.map(|bytes| bandwidth::Limit { bytes, per });
+            bandwidth::Quota {
+                upload: bandwidth::Limits {
+                    per_peer: limit(args.bandwidth.upload_per_peer),
+                    per_urn: limit(args.bandwidth.upload_per_urn),
+                },
+                download: bandwidth::Limits {
+                    per_peer: limit(args.bandwidth.download_per_peer),
+                    per_urn: limit(args.bandwidth.download_per_urn),
+                },
+            }
+        };
+
         let run_mode = match &args.linger_timeout {
             Some(t) => RunMode::Mortal(t.into()),
             None => RunMode::Immortal,
@@ -167,6 +228,13 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
             ),
         });
 
+        let hooks = hooks::Config::new(hook::Config {
+            hook: hook::Hook {
+                buffer: args.hooks.buffer,
+                timeout: Duration::from_millis(args.hooks.timeout),
+            },
+        });
+
         let storage_lock = storage::pool::Initialised::no();
         let request_pull = request_pull::State::new(
             storage::Pool::new(
@@ -178,10 +246,15 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
                 args.request_pull.pool_size,
             ),
             tracker.clone(),
+            hooks.notifier.clone(),
         );
 
         Ok(Self {
+            address_book,
             disco,
+            mdns,
+            hooks,
+            maintenance,
             metrics,
             peer: PeerConfig {
                 signer,
@@ -192,7 +265,10 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
                     membership,
                     network: args.protocol.network.clone(),
                     replication: Default::default(),
-                    rate_limits: Default::default(),
+                    rate_limits: net::protocol::Quota {
+                        bandwidth,
+                        ..Default::default()
+                    },
                     request_pull,
                 },
                 storage: Default::default(),
@@ -200,6 +276,7 @@ impl Cfg<discovery::Static, BoxedSigner, request_pull::State> {
             tracker,
             profile,
             run_mode,
+            seeds,
         })
     }
 }
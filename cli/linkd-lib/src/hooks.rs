@@ -0,0 +1,113 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use futures::{pin_mut, stream, StreamExt as _};
+use tokio::{process::Child, sync::mpsc};
+use tracing::{error, info, instrument, warn};
+
+use librad::{
+    git::{
+        hooks::{self, hook, Hooks, Notification, Track},
+        tracking,
+        Urn,
+    },
+    net::{
+        peer::{Peer, ProtocolEvent},
+        protocol::RequestPullGuard,
+    },
+    PeerId,
+    Signer,
+};
+use radicle_git_ext::Oid;
+
+/// Configuration of the storage hooks run by the node.
+pub struct Config {
+    pub hook: hook::Config,
+    /// Handle for sending [`Notification::Track`]s to the hooks.
+    pub notifier: Notifier,
+    pub notifications: mpsc::Receiver<Notification<Oid>>,
+}
+
+impl Config {
+    pub fn new(hook: hook::Config) -> Self {
+        let (tx, notifications) = mpsc::channel(hook.hook.buffer);
+        Self {
+            hook,
+            notifier: Notifier { tx },
+            notifications,
+        }
+    }
+}
+
+/// Sends [`Notification`]s for changes made by the node itself, ie. which are
+/// not observable as protocol events.
+#[derive(Clone)]
+pub struct Notifier {
+    tx: mpsc::Sender<Notification<Oid>>,
+}
+
+impl Notifier {
+    /// Notify the `tracking_changed` hooks that the tracking entry
+    /// `reference` for `peer` in `urn` was created.
+    pub fn tracked(&self, urn: &Urn, peer: PeerId, reference: &tracking::Ref) {
+        self.notify(Notification::Track(Track {
+            urn: urn.clone().with_path(None),
+            peer: Some(peer),
+            old: Oid::from(git2::Oid::zero()),
+            new: reference.target,
+        }))
+    }
+
+    fn notify(&self, notification: Notification<Oid>) {
+        match self.tx.try_send(notification) {
+            Ok(()) => {},
+            Err(mpsc::error::TrySendError::Full(n)) => {
+                warn!(notification = %n, "hooks are lagging behind, dropping notification")
+            },
+            // The hooks are not running
+            Err(mpsc::error::TrySendError::Closed(_)) => {},
+        }
+    }
+}
+
+/// Run the `hooks` for every ref updated by a replication, as well as every
+/// [`Notification`] received via the [`Notifier`].
+#[instrument(name = "hooks subroutine", skip(peer, hooks, notifications))]
+pub async fn routine<S, G>(
+    peer: Peer<S, G>,
+    hooks: Hooks<Child>,
+    notifications: mpsc::Receiver<Notification<Oid>>,
+) -> anyhow::Result<()>
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    info!("starting hooks routine");
+
+    let data = peer
+        .subscribe()
+        .filter_map(|res| async move {
+            match res {
+                Ok(ProtocolEvent::Replicated(replicated)) => Some(stream::iter(
+                    hooks::data(&replicated.updated)
+                        .map(Notification::Data)
+                        .collect::<Vec<_>>(),
+                )),
+                Ok(_) => None,
+                Err(err) => {
+                    error!(?err, "event error");
+                    None
+                },
+            }
+        })
+        .flatten();
+    let notifications = stream::unfold(notifications, |mut rx| async move {
+        rx.recv().await.map(|n| (n, rx))
+    });
+    let incoming = stream::select(data, notifications);
+    pin_mut!(incoming);
+
+    hooks.run(incoming).await;
+
+    Ok(())
+}
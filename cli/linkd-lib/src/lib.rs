@@ -7,8 +7,12 @@ pub mod args;
 
 mod cfg;
 
+mod address_book;
 pub mod api;
+pub mod hooks;
 mod logging;
+mod maintenance;
+mod mdns;
 mod metrics;
 pub mod node;
 mod protocol;
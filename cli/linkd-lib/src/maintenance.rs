@@ -0,0 +1,38 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use tokio::time;
+use tracing::{error, info, instrument};
+
+use librad::{
+    git::storage::maintenance,
+    net::{peer::Peer, protocol::RequestPullGuard},
+    Signer,
+};
+
+/// Run [`maintenance`] on the monorepo every `interval`.
+///
+/// Failures are logged, and retried at the next interval.
+#[instrument(name = "maintenance subroutine", skip(peer))]
+pub async fn routine<S, G>(peer: Peer<S, G>, interval: Duration) -> anyhow::Result<()>
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    info!("starting maintenance routine");
+
+    loop {
+        time::sleep(interval).await;
+
+        match peer.maintenance(maintenance::Options::default()).await {
+            Ok(stats) => info!(
+                packs = stats.after.packs,
+                loose = stats.after.loose,
+                "maintenance complete"
+            ),
+            Err(e) => error!(err = %e, "maintenance failed"),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use futures::{pin_mut, Future, Stream, StreamExt as _};
+use tracing::{error, info, instrument, warn};
+
+use librad::{
+    net::{
+        discovery::Mdns,
+        peer::{event::upstream::Endpoint, Peer, ProtocolEvent},
+        protocol::{RecvError, RequestPullGuard},
+    },
+    Signer,
+};
+
+/// Announce the listen addresses of `peer` via [`Mdns`] whenever its endpoint
+/// is (re-)bound.
+///
+/// Nb. the protocol events are subscribed to right away, so as to not miss
+/// the endpoint coming up before the returned future is first polled.
+pub fn routine<S, G>(peer: Peer<S, G>, mdns: Mdns) -> impl Future<Output = anyhow::Result<()>>
+where
+    S: Signer + Clone,
+    G: RequestPullGuard,
+{
+    let upstream = peer.subscribe();
+    announce(upstream, mdns)
+}
+
+#[instrument(name = "mdns subroutine", skip(upstream, mdns))]
+async fn announce(
+    upstream: impl Stream<Item = Result<ProtocolEvent, RecvError>>,
+    mdns: Mdns,
+) -> anyhow::Result<()> {
+    pin_mut!(upstream);
+
+    while let Some(res) = upstream.next().await {
+        match res {
+            Ok(ProtocolEvent::Endpoint(Endpoint::Up { listen_addrs })) => {
+                match mdns.announce(&listen_addrs) {
+                    Ok(()) => info!(?listen_addrs, "announced via mDNS"),
+                    Err(err) => warn!(err = %err, "failed to announce via mDNS"),
+                }
+            },
+            Ok(_) => {},
+            Err(err) => {
+                error!(?err, "event error");
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -8,18 +8,23 @@ use std::{panic, sync::Arc, time::Duration};
 use clap::Parser as _;
 use futures::{future::FutureExt as _, stream::FuturesUnordered, StreamExt};
 use tokio::sync::mpsc;
-use tracing::info;
+use tracing::{info, warn};
 
 use librad::{
     crypto::BoxedSigner,
+    git::hooks::hooks as load_hooks,
     net::{discovery, peer::Peer},
 };
 
 use crate::{
+    address_book,
     api,
     args::Args,
     cfg::{self, Cfg, RunMode},
+    hooks,
     logging,
+    maintenance,
+    mdns,
     metrics::graphite,
     protocol,
     request_pull,
@@ -43,19 +48,55 @@ pub async fn run() -> anyhow::Result<()> {
 
     let mut coalesced = FuturesUnordered::new();
     let peer = Peer::new(cfg.peer)?;
+    if let Some(mdns) = cfg.mdns.clone() {
+        let mdns_task = spawner.spawn(mdns::routine(peer.clone(), mdns)).fuse();
+        coalesced.push(mdns_task);
+    }
+
     let peer_task = spawner
-        .spawn(protocol::routine(peer.clone(), cfg.disco, shutdown_rx))
+        .spawn(protocol::routine(
+            peer.clone(),
+            (cfg.disco, cfg.mdns),
+            shutdown_rx,
+        ))
         .fuse();
     coalesced.push(peer_task);
 
+    let address_book_task = spawner
+        .spawn(address_book::routine(peer.clone(), cfg.address_book))
+        .fuse();
+    coalesced.push(address_book_task);
+
     if let Some(cfg::Metrics::Graphite(addr)) = cfg.metrics {
         let graphite_task = spawner.spawn(graphite::routine(peer.clone(), addr)).fuse();
         coalesced.push(graphite_task);
     }
 
+    if let Some(interval) = cfg.maintenance {
+        let maintenance_task = spawner
+            .spawn(maintenance::routine(peer.clone(), interval))
+            .fuse();
+        coalesced.push(maintenance_task);
+    }
+
+    let hooks::Config {
+        hook,
+        notifier,
+        notifications,
+    } = cfg.hooks;
+    match load_hooks(cfg.profile.paths(), hook).await {
+        Ok(loaded) => {
+            let hooks_task = spawner
+                .spawn(hooks::routine(peer.clone(), loaded, notifications))
+                .fuse();
+            coalesced.push(hooks_task);
+        },
+        Err(err) => warn!(err = %err, "failed to load hooks, hooks will not be run"),
+    }
+
     if let Some(tracker) = cfg.tracker {
         let tracking_task = spawner
-            .spawn(tracking::routine(peer.clone(), tracker))
+            .spawn(tracking::routine(peer.clone(), tracker, notifier))
             .fuse();
         coalesced.push(tracking_task);
     }
@@ -69,6 +110,7 @@ pub async fn run() -> anyhow::Result<()> {
         spawner.clone(),
         peer.clone(),
         &sockets,
+        cfg.seeds,
         timeout,
         ANNOUNCE_WAIT_TIME,
     )
@@ -11,22 +11,25 @@ use librad::{
     PeerId,
 };
 
-use crate::tracking::Tracker;
+use crate::{hooks::Notifier, tracking::Tracker};
 
 #[derive(Clone)]
 pub struct State {
     storage: storage::Pool<storage::Storage>,
     tracker: Option<Tracker>,
+    notifier: Notifier,
 }
 
 impl State {
     pub fn new(
         storage: storage::Pool<storage::Storage>,
         tracker: impl Into<Option<Tracker>>,
+        notifier: Notifier,
     ) -> Self {
         State {
             storage,
             tracker: tracker.into(),
+            notifier,
         }
     }
 }
@@ -76,6 +79,9 @@ impl Guard for State {
                             tracking::Config::default(),
                             tracking::policy::Track::MustNotExist,
                         )?;
+                        if let Ok(reference) = &tracked {
+                            self.notifier.tracked(urn, *peer, reference);
+                        }
                         Ok(Tracked {
                             tracked: Some(tracked),
                             urn: urn.clone(),
@@ -25,6 +25,8 @@ use librad::{
     Signer,
 };
 
+use crate::hooks::Notifier;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Tracker {
     /// Track any `Urn` or `PeerId`, regardless of a tracking entry being
@@ -215,16 +217,20 @@ impl Tracker {
     }
 }
 
-#[instrument(name = "tracking subroutine", skip(peer, tracker))]
-pub async fn routine<S, G>(peer: Peer<S, G>, tracker: Tracker) -> anyhow::Result<()>
+#[instrument(name = "tracking subroutine", skip(peer, tracker, notifier))]
+pub async fn routine<S, G>(
+    peer: Peer<S, G>,
+    tracker: Tracker,
+    notifier: Notifier,
+) -> anyhow::Result<()>
 where
     S: Signer + Clone,
     G: RequestPullGuard,
 {
-    let events = peer.subscribe();
-    pin_mut!(events);
+    let upstream = peer.subscribe();
+    pin_mut!(upstream);
 
-    while let Some(res) = events.next().await {
+    while let Some(res) = upstream.next().await {
         match res {
             Ok(ProtocolEvent::Gossip(gossip)) => {
                 let Gossip::Put {
@@ -246,6 +252,7 @@ where
                     let updated = peer
                     .using_storage({
                         let urn = urn.clone();
+                        let notifier = notifier.clone();
                         move |storage| -> anyhow::Result<bool> {
                             match tracking::track(
                                 storage,
@@ -256,6 +263,7 @@ where
                             )? {
                                 Ok(reference) => {
                                     trace!(name=%reference.name, target=%reference.target, "created tracking entry");
+                                    notifier.tracked(&urn, peer_id, &reference);
                                     Ok(true)
                                 },
                                 Err(err) => {
@@ -8,7 +8,15 @@ use std::{fmt::Debug, net::SocketAddr};
 use librad_test::gen::protocol::gen_request_pull_success;
 use link_crypto_test::gen::gen_peer_id;
 use link_identities_test::gen::urn::{gen_oid, gen_urn};
-use linkd_lib::api::{announce, messages, request_pull};
+use linkd_lib::api::{
+    announce,
+    connected_peers,
+    membership,
+    messages,
+    replicate,
+    request_pull,
+    stats,
+};
 use proptest::{collection, prelude::*};
 use test_helpers::gen::std_net::gen_socket_addr;
 
@@ -42,9 +50,23 @@ pub fn request_pull(addrs: Vec<SocketAddr>) -> impl Strategy<Value = request_pul
     })
 }
 
+pub fn replicate_remote() -> impl Strategy<Value = replicate::Remote> {
+    (gen_peer_id(), collection::vec(gen_socket_addr(), 0..3))
+        .prop_map(|(peer, addrs)| replicate::Remote { peer, addrs })
+}
+
+pub fn replicate() -> impl Strategy<Value = replicate::Request> {
+    (gen_urn(), collection::vec(replicate_remote(), 0..3))
+        .prop_map(|(urn, peers)| replicate::Request { urn, peers })
+}
+
 pub fn request_payload() -> impl Strategy<Value = messages::RequestPayload> {
     prop_oneof![
         announce().prop_map(messages::RequestPayload::from),
+        Just(connected_peers::Request).prop_map(messages::RequestPayload::from),
+        Just(membership::Request).prop_map(messages::RequestPayload::from),
+        Just(stats::Request).prop_map(messages::RequestPayload::from),
+        replicate().prop_map(messages::RequestPayload::from),
         collection::vec(gen_socket_addr(), 1..3)
             .prop_flat_map(request_pull)
             .prop_map(messages::RequestPayload::from)
@@ -102,3 +124,124 @@ pub fn request_pull_response() -> impl Strategy<Value = messages::Response<reque
             })
     })
 }
+
+pub fn connected_peers_response(
+) -> impl Strategy<Value = messages::Response<connected_peers::Response>> {
+    request_id().prop_flat_map(move |id| {
+        (
+            Just(id),
+            collection::vec(gen_peer_id(), 0..3)
+                .prop_flat_map(move |peers| response_payload(connected_peers::Response(peers))),
+        )
+            .prop_map(move |(request_id, payload)| messages::Response {
+                payload,
+                request_id,
+            })
+    })
+}
+
+pub fn membership_response() -> impl Strategy<Value = messages::Response<membership::Response>> {
+    request_id().prop_flat_map(move |id| {
+        (
+            Just(id),
+            (
+                collection::vec(gen_peer_id(), 0..3),
+                collection::vec(gen_peer_id(), 0..3),
+            )
+                .prop_flat_map(move |(active, passive)| {
+                    response_payload(membership::Response { active, passive })
+                }),
+        )
+            .prop_map(move |(request_id, payload)| messages::Response {
+                payload,
+                request_id,
+            })
+    })
+}
+
+pub fn stats() -> impl Strategy<Value = stats::Response> {
+    (
+        any::<u64>(),
+        collection::btree_map(
+            gen_peer_id(),
+            collection::vec(gen_socket_addr(), 1..3),
+            0..3,
+        ),
+        any::<u64>(),
+        any::<u64>(),
+        any::<u64>(),
+        any::<u64>(),
+        any::<(u64, u64)>(),
+    )
+        .prop_map(
+            |(
+                connections_total,
+                connected_peers,
+                membership_active,
+                membership_passive,
+                elements,
+                fingerprints,
+                (upload, download),
+            )| stats::Response {
+                connections_total,
+                connected_peers,
+                membership_active,
+                membership_passive,
+                caches: stats::CacheStats {
+                    urns: stats::UrnsCacheStats {
+                        elements,
+                        fingerprints,
+                    },
+                },
+                bandwidth: stats::BandwidthStats { upload, download },
+            },
+        )
+}
+
+pub fn stats_response() -> impl Strategy<Value = messages::Response<stats::Response>> {
+    request_id().prop_flat_map(move |id| {
+        (Just(id), stats().prop_flat_map(response_payload)).prop_map(
+            move |(request_id, payload)| messages::Response {
+                payload,
+                request_id,
+            },
+        )
+    })
+}
+
+pub fn replicated() -> impl Strategy<Value = replicate::Replicated> {
+    (
+        gen_peer_id(),
+        prop_oneof![
+            (
+                collection::vec(gen_urn(), 0..3),
+                collection::vec(gen_peer_id(), 0..3),
+                any::<bool>()
+            )
+                .prop_map(|(created, tracked_peers, requires_confirmation)| {
+                    replicate::Outcome::Success(replicate::Success {
+                        created,
+                        tracked_peers,
+                        requires_confirmation,
+                        ..Default::default()
+                    })
+                }),
+            any::<String>().prop_map(replicate::Outcome::Failure),
+        ],
+    )
+        .prop_map(|(peer, result)| replicate::Replicated { peer, result })
+}
+
+pub fn replicate_response() -> impl Strategy<Value = messages::Response<replicate::Response>> {
+    request_id().prop_flat_map(move |id| {
+        (
+            Just(id),
+            collection::vec(replicated(), 0..3)
+                .prop_flat_map(|replicated| response_payload(replicate::Response(replicated))),
+        )
+            .prop_map(move |(request_id, payload)| messages::Response {
+                payload,
+                request_id,
+            })
+    })
+}
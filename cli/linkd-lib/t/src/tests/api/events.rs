@@ -0,0 +1,67 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use librad::{git::Urn, PeerId};
+use link_crypto_test::gen::gen_peer_id;
+use link_identities_test::gen::urn::gen_urn;
+use linkd_lib::api::events::{Event, Subscribe};
+use proptest::prelude::*;
+
+fn gossip(provider: PeerId, urn: Urn) -> Event {
+    Event::Gossip {
+        provider,
+        urn,
+        rev: None,
+        origin: None,
+    }
+}
+
+proptest! {
+    #[test]
+    fn event_roundtrip(provider in gen_peer_id(), urn in gen_urn()) {
+        let event = gossip(provider, urn);
+        let bytes = minicbor::to_vec(&event).unwrap();
+        assert_eq!(event, minicbor::decode::<Event>(&bytes).unwrap());
+    }
+
+    #[test]
+    fn subscribe_roundtrip(peer in gen_peer_id(), urn in gen_urn()) {
+        let sub = Subscribe { urns: vec![urn], peers: vec![peer] };
+        let bytes = minicbor::to_vec(&sub).unwrap();
+        assert_eq!(sub, minicbor::decode::<Subscribe>(&bytes).unwrap());
+    }
+
+    #[test]
+    fn empty_subscription_matches_everything(peer in gen_peer_id(), urn in gen_urn()) {
+        let sub = Subscribe::default();
+        assert!(sub.matches(&gossip(peer, urn)));
+        assert!(sub.matches(&Event::PeerConnected { peer }));
+    }
+
+    #[test]
+    fn urn_subscription(
+        peer in gen_peer_id(),
+        urn in gen_urn(),
+        other in gen_urn(),
+    ) {
+        prop_assume!(urn.id != other.id);
+        let sub = Subscribe { urns: vec![urn.clone()], peers: vec![] };
+        assert!(sub.matches(&gossip(peer, urn)));
+        assert!(!sub.matches(&gossip(peer, other)));
+        assert!(!sub.matches(&Event::PeerDisconnected { peer }));
+    }
+
+    #[test]
+    fn peer_subscription(
+        peer in gen_peer_id(),
+        other in gen_peer_id(),
+        urn in gen_urn(),
+    ) {
+        prop_assume!(peer != other);
+        let sub = Subscribe { urns: vec![], peers: vec![peer] };
+        assert!(sub.matches(&gossip(peer, urn.clone())));
+        assert!(!sub.matches(&gossip(other, urn)));
+        assert!(sub.matches(&Event::PeerConnected { peer }));
+        assert!(!sub.matches(&Event::PeerConnected { peer: other }));
+    }
+}
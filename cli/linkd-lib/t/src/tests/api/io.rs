@@ -6,7 +6,15 @@
 use linkd_lib::api::{io, io::Transport as _, messages};
 use proptest::{array::uniform3, prelude::*};
 
-use crate::gen::{announce_response, request, request_pull_response};
+use crate::gen::{
+    announce_response,
+    connected_peers_response,
+    membership_response,
+    replicate_response,
+    request,
+    request_pull_response,
+    stats_response,
+};
 
 proptest! {
     #[test]
@@ -36,6 +44,26 @@ proptest! {
     fn test_response_round_trip_request_pull(responses in uniform3(request_pull_response())) {
         test_response_round_trip(&responses)
     }
+
+    #[test]
+    fn test_response_round_trip_connected_peers(responses in uniform3(connected_peers_response())) {
+        test_response_round_trip(&responses)
+    }
+
+    #[test]
+    fn test_response_round_trip_membership(responses in uniform3(membership_response())) {
+        test_response_round_trip(&responses)
+    }
+
+    #[test]
+    fn test_response_round_trip_stats(responses in uniform3(stats_response())) {
+        test_response_round_trip(&responses)
+    }
+
+    #[test]
+    fn test_response_round_trip_replicate(responses in uniform3(replicate_response())) {
+        test_response_round_trip(&responses)
+    }
 }
 
 fn with_async_transport<
@@ -5,6 +5,7 @@
 
 use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    num::NonZeroU64,
     path::PathBuf,
     str::FromStr,
 };
@@ -20,7 +21,11 @@ use librad::{
 use linkd_lib::args::{
     self,
     Args,
+    BandwidthArgs,
+    DiscoveryArgs,
+    HooksArgs,
     KeyArgs,
+    MaintenanceArgs,
     MetricsArgs,
     MetricsProvider,
     ProtocolArgs,
@@ -92,6 +97,114 @@ fn bootstraps() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn mdns() -> Result<()> {
+    #[rustfmt::skip]
+    let iter = vec![
+        "linkd",
+            "--protocol-listen", "localhost",
+            "--mdns",
+    ];
+    let parsed = Args::try_parse_from(iter)?;
+
+    assert_eq!(
+        parsed,
+        Args {
+            discovery: DiscoveryArgs { mdns: true },
+            ..Default::default()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bandwidth() -> Result<()> {
+    #[rustfmt::skip]
+    let iter = vec![
+        "linkd",
+            "--protocol-listen", "localhost",
+            "--upload-limit-peer", "1048576",
+            "--download-limit-urn", "4096",
+            "--bandwidth-window", "60",
+    ];
+    let parsed = Args::try_parse_from(iter)?;
+
+    assert_eq!(
+        parsed,
+        Args {
+            bandwidth: BandwidthArgs {
+                upload_per_peer: NonZeroU64::new(1048576),
+                download_per_urn: NonZeroU64::new(4096),
+                window: NonZeroU64::new(60).unwrap(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bandwidth_window_zero() {
+    #[rustfmt::skip]
+    let iter = vec![
+        "linkd",
+            "--protocol-listen", "localhost",
+            "--bandwidth-window", "0",
+    ];
+    assert!(Args::try_parse_from(iter).is_err());
+}
+
+#[test]
+fn hooks() -> Result<()> {
+    #[rustfmt::skip]
+    let iter = vec![
+        "linkd",
+            "--protocol-listen", "localhost",
+            "--hook-buffer", "100",
+            "--hook-timeout", "500",
+    ];
+    let parsed = Args::try_parse_from(iter)?;
+
+    assert_eq!(
+        parsed,
+        Args {
+            hooks: HooksArgs {
+                buffer: 100,
+                timeout: 500,
+            },
+            ..Default::default()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn maintenance() -> Result<()> {
+    #[rustfmt::skip]
+    let iter = vec![
+        "linkd",
+            "--protocol-listen", "localhost",
+            "--maintenance-interval", "3600",
+    ];
+    let parsed = Args::try_parse_from(iter)?;
+
+    assert_eq!(
+        parsed,
+        Args {
+            maintenance: MaintenanceArgs {
+                interval: Some(3600),
+            },
+            ..Default::default()
+        }
+    );
+
+    Ok(())
+}
+
 #[test]
 fn metrics_graphite() -> Result<()> {
     #[rustfmt::skip]
@@ -0,0 +1,6 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+pub mod args;
+mod main;
+pub use main::main;
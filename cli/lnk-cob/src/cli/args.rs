@@ -0,0 +1,140 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use librad::{
+    collaborative_objects::{ObjectId, TypeName},
+    git::Urn,
+};
+
+/// Management of collaborative objects of any type, e.g. for prototyping new
+/// types.
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Parser)]
+pub enum Command {
+    Create(Create),
+    List(List),
+    Show(Show),
+    Update(Update),
+    Graph(Graph),
+}
+
+/// create a new collaborative object
+#[derive(Debug, Parser)]
+pub struct Create {
+    /// the URN of the identity the object is stored under
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the type of the object, e.g. `xyz.radicle.issue`
+    #[clap(long)]
+    pub typename: TypeName,
+
+    /// the Radicle URN of the local identity to author the object as. If no
+    /// URN is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the path to the JSON schema of the object
+    #[clap(long)]
+    pub schema: PathBuf,
+
+    /// the path to the encoded Automerge change initialising the object
+    #[clap(long)]
+    pub changes: PathBuf,
+
+    /// create an operation log rather than an Automerge object, in which case
+    /// `--changes` is the path to a JSON array of operations
+    #[clap(long)]
+    pub op_log: bool,
+
+    /// a message to add to the commit creating the object
+    #[clap(long)]
+    pub message: Option<String>,
+}
+
+/// list the collaborative objects of a type
+#[derive(Debug, Parser)]
+pub struct List {
+    /// the URN of the identity the objects are stored under
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the type of the objects, e.g. `xyz.radicle.issue`
+    #[clap(long)]
+    pub typename: TypeName,
+}
+
+/// show the materialised state of a collaborative object
+#[derive(Debug, Parser)]
+pub struct Show {
+    /// the URN of the identity the object is stored under
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the type of the object, e.g. `xyz.radicle.issue`
+    #[clap(long)]
+    pub typename: TypeName,
+
+    /// the identifier of the object
+    #[clap(long)]
+    pub id: ObjectId,
+}
+
+/// apply an Automerge change to a collaborative object
+#[derive(Debug, Parser)]
+pub struct Update {
+    /// the URN of the identity the object is stored under
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the type of the object, e.g. `xyz.radicle.issue`
+    #[clap(long)]
+    pub typename: TypeName,
+
+    /// the identifier of the object
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to author the change as. If no
+    /// URN is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the path to the encoded Automerge change
+    #[clap(long)]
+    pub changes: PathBuf,
+
+    /// the object is an operation log, in which case `--changes` is the path
+    /// to a JSON array of operations
+    #[clap(long)]
+    pub op_log: bool,
+
+    /// a message to add to the commit of the change
+    #[clap(long)]
+    pub message: Option<String>,
+}
+
+/// print the change graph of a collaborative object in graphviz format
+#[derive(Debug, Parser)]
+pub struct Graph {
+    /// the URN of the identity the object is stored under
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the type of the object, e.g. `xyz.radicle.issue`
+    #[clap(long)]
+    pub typename: TypeName,
+
+    /// the identifier of the object
+    #[clap(long)]
+    pub id: ObjectId,
+}
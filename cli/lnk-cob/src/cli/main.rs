@@ -0,0 +1,156 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{fs, path::Path};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use librad::{
+    collaborative_objects::{
+        materialise,
+        CollaborativeObject,
+        EntryContents,
+        NewObjectSpec,
+        ObjectId,
+        TypeName,
+        UpdateObjectSpec,
+    },
+    git::{
+        identities::{self, local::LocalIdentity},
+        storage::Storage,
+        Urn,
+    },
+    profile::{LnkHome, Profile, ProfileId},
+};
+use lnk_clib::{keys::ssh::SshAuthSock, storage::ssh};
+
+use super::args::*;
+
+/// A [`CollaborativeObject`] along with its materialised state, for display
+/// purposes.
+#[derive(Serialize)]
+struct Display {
+    id: ObjectId,
+    typename: TypeName,
+    state: serde_json::Value,
+}
+
+impl Display {
+    fn new(object: &CollaborativeObject) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: *object.id(),
+            typename: object.typename().clone(),
+            state: materialise(object.history())?,
+        })
+    }
+}
+
+pub fn main(
+    Args { command }: Args,
+    profile: Option<ProfileId>,
+    sock: SshAuthSock,
+) -> anyhow::Result<()> {
+    let home = LnkHome::default();
+    let profile = Profile::from_home(&home, profile)?;
+    let (_, storage) = ssh::storage(&profile, sock)?;
+    let cobs = storage.collaborative_objects(Some(profile.paths().cob_cache_dir().to_path_buf()));
+
+    match command {
+        Command::Create(Create {
+            urn,
+            typename,
+            whoami,
+            schema,
+            changes,
+            op_log,
+            message,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let schema_json = serde_json::from_slice(&fs::read(&schema)?)?;
+            let history = read_changes(&changes, op_log)?;
+            let object = cobs.create(
+                &whoami,
+                &urn,
+                NewObjectSpec {
+                    schema_json,
+                    history,
+                    typename,
+                    message,
+                },
+            )?;
+            print(&Display::new(&object)?)?
+        },
+        Command::List(List { urn, typename }) => {
+            let objects = cobs
+                .list(&urn, &typename)?
+                .iter()
+                .map(Display::new)
+                .collect::<Result<Vec<_>, _>>()?;
+            print(&objects)?
+        },
+        Command::Show(Show { urn, typename, id }) => {
+            let object = cobs.retrieve(&urn, &typename, &id)?.ok_or_else(|| {
+                anyhow!("object {} of type {} not found in {}", id, typename, urn)
+            })?;
+            print(&Display::new(&object)?)?
+        },
+        Command::Update(Update {
+            urn,
+            typename,
+            id,
+            whoami,
+            changes,
+            op_log,
+            message,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let changes = read_changes(&changes, op_log)?;
+            let object = cobs.update(
+                &whoami,
+                &urn,
+                UpdateObjectSpec {
+                    object_id: id,
+                    typename,
+                    message,
+                    changes,
+                },
+            )?;
+            print(&Display::new(&object)?)?
+        },
+        Command::Graph(Graph { urn, typename, id }) => {
+            let info = cobs
+                .changegraph_info_for_object(&urn, &typename, &id)?
+                .ok_or_else(|| {
+                    anyhow!("object {} of type {} not found in {}", id, typename, urn)
+                })?;
+            println!("{}", info.dotviz)
+        },
+    }
+
+    Ok(())
+}
+
+fn resolve_whoami(storage: &Storage, whoami: Option<Urn>) -> anyhow::Result<LocalIdentity> {
+    match whoami {
+        None => identities::local::default(storage)?
+            .ok_or_else(|| anyhow!("no default identity was found, perhaps you need to set one")),
+        Some(urn) => identities::local::load(storage, urn.clone())?
+            .ok_or_else(|| anyhow!("the local identity `{}` does not exist", urn)),
+    }
+}
+
+fn read_changes(path: &Path, op_log: bool) -> anyhow::Result<EntryContents> {
+    let bytes = fs::read(path)?;
+    if op_log {
+        let ops = serde_json::from_slice::<Vec<serde_json::Value>>(&bytes)?;
+        Ok(EntryContents::op_log(&ops))
+    } else {
+        Ok(EntryContents::Automerge(bytes))
+    }
+}
+
+fn print<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
@@ -51,10 +51,21 @@ pub struct Global {
 
 #[derive(Debug, Parser)]
 pub enum Command {
+    /// Manage collaborative objects of any type
+    Cob(lnk_cob::cli::args::Args),
     /// Manage Radicle Identities
     Identities(lnk_identities::cli::args::Args),
+    /// Track tasks and bug reports
+    Issue(lnk_issue::cli::args::Args),
+    /// Maintain the monorepo of your profile
+    #[clap(flatten)]
+    Maintenance(lnk_maintenance::cli::args::Args),
+    /// Propose, review, and merge patches
+    Patch(lnk_patch::cli::args::Args),
     /// Manage your Radicle profiles
     Profile(lnk_profile::cli::args::Args),
+    /// Show, import, or export tracking entries
+    Tracking(lnk_identities::cli::args::Tracking),
     /// Sync with your configured seeds
     #[clap(flatten)]
     Sync(lnk_sync::cli::args::Args),
@@ -20,10 +20,29 @@ pub fn main() -> anyhow::Result<()> {
         .unwrap();
 
     match command {
+        args::Command::Cob(args) => {
+            lnk_cob::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock)
+        },
         args::Command::Identities(args) => {
             lnk_identities::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock)
         },
+        args::Command::Issue(args) => {
+            lnk_issue::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock)
+        },
+        args::Command::Maintenance(args) => {
+            lnk_maintenance::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock)
+        },
+        args::Command::Patch(args) => {
+            lnk_patch::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock)
+        },
         args::Command::Profile(args) => lnk_profile::cli::main(args, global.lnk_ssh_auth_sock),
+        args::Command::Tracking(args) => lnk_identities::cli::main(
+            lnk_identities::cli::args::Args {
+                command: lnk_identities::cli::args::Command::Tracking(args),
+            },
+            global.lnk_profile,
+            global.lnk_ssh_auth_sock,
+        ),
         args::Command::Sync(args) => {
             lnk_sync::cli::main(args, global.lnk_profile, global.lnk_ssh_auth_sock, runtime)
         },
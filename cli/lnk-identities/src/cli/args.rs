@@ -38,6 +38,7 @@ pub enum Command {
     Refs(Refs),
     Track(tracking::Track),
     Untrack(tracking::Untrack),
+    Tracking(Tracking),
 }
 
 /// create, get, or modify a Radicle project
@@ -83,6 +84,13 @@ pub struct Refs {
     pub refs: refs::Options,
 }
 
+/// show, import, or export tracking entries
+#[derive(Debug, Parser)]
+pub struct Tracking {
+    #[clap(subcommand)]
+    pub tracking: tracking::Options,
+}
+
 pub mod project {
     use super::*;
 
@@ -112,6 +120,10 @@ pub mod project {
         Checkout(Checkout),
         Diff(Diff),
         Accept(Accept),
+        Review(Review),
+        Propose(Propose),
+        Sign(Sign),
+        Status(Status),
         Tracked(Tracked),
     }
 
@@ -293,6 +305,68 @@ pub mod project {
         pub force: bool,
     }
 
+    /// check whether the local Radicle project is ahead of, behind or forked
+    /// from a peer's, requiring both histories to be linear
+    #[derive(Debug, Parser)]
+    pub struct Review {
+        /// the Radicle URN of the project
+        #[clap(long)]
+        pub urn: Urn,
+        /// the peer to compare to
+        #[clap(long)]
+        pub peer: PeerId,
+    }
+
+    /// propose a new revision of a Radicle project, to be signed by the other
+    /// delegates until it reaches quorum
+    #[derive(Debug, Parser)]
+    pub struct Propose {
+        /// the Radicle URN of the project
+        #[clap(long)]
+        pub urn: Urn,
+
+        /// the Radicle URN pointing to a local identity that will be used for
+        /// setting `rad/self` on this project.
+        #[clap(long)]
+        pub whoami: Option<Urn>,
+
+        /// the payload to propose for the project. The `name` field is
+        /// expected, while `default_branch` and `description` are optional.
+        #[clap(long, parse(try_from_str = project_payload))]
+        pub payload: Option<payload::Project>,
+
+        /// provide a list of extensions to extend the payload. The extension
+        /// must be a JSON object consisting of a namespace URL and the extended
+        /// JSON payload
+        #[clap(long, parse(try_from_str = ext_payload))]
+        pub ext: Vec<payload::Ext<serde_json::Value>>,
+
+        /// the set of delegates to propose for the project. This set is
+        /// required to be absolute, see `update`.
+        #[clap(long, parse(try_from_str = indirect_delegation))]
+        pub delegations: Vec<KeyOrUrn<Revision>>,
+    }
+
+    /// sign a proposed revision of a Radicle project. Once the revision has
+    /// reached quorum, the project is updated to it
+    #[derive(Debug, Parser)]
+    pub struct Sign {
+        /// the Radicle URN of the project
+        #[clap(long)]
+        pub urn: Urn,
+        /// the proposed revision to sign
+        #[clap(long)]
+        pub revision: Revision,
+    }
+
+    /// list the proposed revisions of a Radicle project and their signers
+    #[derive(Debug, Parser)]
+    pub struct Status {
+        /// the Radicle URN of the project
+        #[clap(long)]
+        pub urn: Urn,
+    }
+
     #[derive(Debug, Parser)]
     pub struct Tracked {
         /// the Radicle URN of the project
@@ -324,6 +398,7 @@ pub mod person {
         Checkout(Checkout),
         Diff(Diff),
         Accept(Accept),
+        Revoke(Revoke),
         Tracked(Tracked),
     }
 
@@ -481,6 +556,28 @@ pub mod person {
         pub force: bool,
     }
 
+    /// revoke a device key of a Radicle person, e.g. of a lost device,
+    /// optionally replacing it with the key of a new device
+    #[derive(Debug, Parser)]
+    pub struct Revoke {
+        /// the Radicle URN of the person
+        #[clap(long)]
+        pub urn: Urn,
+        /// the key to revoke, in Peer ID form
+        #[clap(long, parse(try_from_str = direct_delegation))]
+        pub key: PublicKey,
+        /// the key replacing the revoked one, in Peer ID form
+        #[clap(long, parse(try_from_str = direct_delegation))]
+        pub replacement: Option<PublicKey>,
+        /// why the key is revoked, e.g. "lost laptop"
+        #[clap(long)]
+        pub reason: String,
+        /// the time, in seconds since the Unix epoch, from which on data signed
+        /// by the key is no longer trusted. Defaults to the current time.
+        #[clap(long)]
+        pub effective: Option<u64>,
+    }
+
     #[derive(Debug, Parser)]
     pub struct Tracked {
         /// the Radicle URN of the person
@@ -674,7 +771,65 @@ pub mod refs {
 pub mod tracking {
     use super::*;
 
+    use librad::{
+        collaborative_objects,
+        git::tracking::{
+            config::{Pattern, TypeName},
+            git::config,
+            policy,
+        },
+    };
+
+    /// A filter on collaborative objects, of the form `<typename>` or
+    /// `<typename>=<object id>[,<object id>...]`. The typename `*` matches all
+    /// types.
+    #[derive(Debug)]
+    pub struct CobFilter {
+        pub typename: TypeName<config::TypeName>,
+        pub pattern: Pattern<config::ObjectId>,
+    }
+
+    fn cob_filter(value: &str) -> Result<CobFilter, String> {
+        let (typename, objects) = match value.split_once('=') {
+            Some((typename, objects)) => (typename, Some(objects)),
+            None => (value, None),
+        };
+        let typename = match typename {
+            "*" => TypeName::Wildcard,
+            ty => TypeName::Type(config::TypeName(
+                ty.parse::<collaborative_objects::TypeName>()
+                    .map_err(|err| err.to_string())?,
+            )),
+        };
+        let pattern = match objects {
+            None => Pattern::Wildcard,
+            Some(objects) => Pattern::Objects(
+                objects
+                    .split(',')
+                    .map(|id| {
+                        id.parse::<collaborative_objects::ObjectId>()
+                            .map(config::ObjectId)
+                            .map_err(|err| err.to_string())
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(CobFilter { typename, pattern })
+    }
+
+    fn track_policy(value: &str) -> Result<policy::Track, String> {
+        match value {
+            "any" => Ok(policy::Track::Any),
+            "must-exist" => Ok(policy::Track::MustExist),
+            "must-not-exist" => Ok(policy::Track::MustNotExist),
+            _ => Err(format!("unsupported tracking policy `{}`", value)),
+        }
+    }
+
     /// track a peer's gossip for a Radicle URN
+    ///
+    /// Without any further options, all data and collaborative objects of the
+    /// peer are replicated.
     #[derive(Debug, Parser)]
     pub struct Track {
         /// the Radicle URN to track
@@ -684,6 +839,36 @@ pub mod tracking {
         /// the peer to track
         #[clap(long)]
         pub peer: PeerId,
+
+        /// path to a JSON file containing the full tracking configuration, as
+        /// per RFC 0699
+        #[clap(long, conflicts_with_all = &["no_data", "allow_cob", "deny_cob"])]
+        pub config: Option<PathBuf>,
+
+        /// do not replicate the peer's branches, tags, and notes
+        #[clap(long)]
+        pub no_data: bool,
+
+        /// replicate the collaborative objects of a type, e.g.
+        /// `xyz.radicle.issue`, or only the given objects of a type, e.g.
+        /// `xyz.radicle.issue=<object id>,<object id>`. May be given multiple
+        /// times. If neither this nor `--deny-cob` is given, all collaborative
+        /// objects are replicated.
+        #[clap(long, multiple_occurrences = true, parse(try_from_str = cob_filter))]
+        pub allow_cob: Vec<CobFilter>,
+
+        /// do not replicate the collaborative objects of a type, or only the
+        /// given objects of a type, in the same form as `--allow-cob`. Unless
+        /// `--allow-cob` is given as well, objects which are not denied are
+        /// replicated. May be given multiple times.
+        #[clap(long, multiple_occurrences = true, parse(try_from_str = cob_filter))]
+        pub deny_cob: Vec<CobFilter>,
+
+        /// whether the tracking entry may already exist, one of `any`,
+        /// `must-exist`, or `must-not-exist`. Use `must-exist` to only update
+        /// the configuration of an existing entry.
+        #[clap(long, default_value = "any", parse(try_from_str = track_policy))]
+        pub policy: policy::Track,
     }
 
     /// untrack a peer's gossip for a Radicle URN
@@ -697,6 +882,58 @@ pub mod tracking {
         #[clap(long)]
         pub peer: PeerId,
     }
+
+    #[derive(Debug, Parser)]
+    pub enum Options {
+        Show(Show),
+        List(List),
+        Import(Import),
+        Export(Export),
+    }
+
+    /// show the tracking entry of a peer for a Radicle URN
+    #[derive(Debug, Parser)]
+    pub struct Show {
+        /// the Radicle URN of the tracking entry
+        #[clap(long)]
+        pub urn: Urn,
+
+        /// the tracked peer. If not given, the default entry of the URN is
+        /// shown
+        #[clap(long)]
+        pub peer: Option<PeerId>,
+    }
+
+    /// list the tracking entries, optionally limited to a Radicle URN
+    #[derive(Debug, Parser)]
+    pub struct List {
+        /// the Radicle URN to list the tracking entries of
+        #[clap(long)]
+        pub urn: Option<Urn>,
+    }
+
+    /// apply all tracking entries of a manifest, as produced by `export`, in a
+    /// single batch
+    #[derive(Debug, Parser)]
+    pub struct Import {
+        /// path to the manifest file
+        pub manifest: PathBuf,
+
+        /// whether the tracking entries may already exist, one of `any`,
+        /// `must-exist`, or `must-not-exist`. Entries which violate the policy
+        /// are reported and skipped.
+        #[clap(long, default_value = "any", parse(try_from_str = track_policy))]
+        pub policy: policy::Track,
+    }
+
+    /// print a manifest of the tracking entries, optionally limited to a
+    /// Radicle URN, as canonical JSON
+    #[derive(Debug, Parser)]
+    pub struct Export {
+        /// the Radicle URN to export the tracking entries of
+        #[clap(long)]
+        pub urn: Option<Urn>,
+    }
 }
 
 fn ext_payload(value: &str) -> Result<payload::Ext<serde_json::Value>, String> {
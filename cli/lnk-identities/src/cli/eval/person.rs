@@ -3,7 +3,12 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{convert::TryFrom as _, io, path::PathBuf};
+use std::{
+    convert::TryFrom as _,
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::anyhow;
 
@@ -15,7 +20,7 @@ use librad::{
         types::{Namespace, Reference},
         Urn,
     },
-    identities::payload,
+    identities::{git::Revocation, payload},
     profile::Profile,
     PeerId,
 };
@@ -45,6 +50,13 @@ pub fn eval(profile: &Profile, sock: SshAuthSock, opts: Options) -> anyhow::Resu
         Options::Accept(Accept { urn, peer, force }) => {
             eval_accept(profile, sock, urn, peer, force)?
         },
+        Options::Revoke(Revoke {
+            urn,
+            key,
+            replacement,
+            reason,
+            effective,
+        }) => eval_revoke(profile, sock, urn, key, replacement, reason, effective)?,
         Options::Tracked(Tracked { urn }) => eval_tracked(profile, urn)?,
     }
 
@@ -165,6 +177,31 @@ fn eval_diff(profile: &Profile, urn: Urn, peer: PeerId) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn eval_revoke(
+    profile: &Profile,
+    sock: SshAuthSock,
+    urn: Urn,
+    key: PublicKey,
+    replacement: Option<PublicKey>,
+    reason: String,
+    effective: Option<u64>,
+) -> anyhow::Result<()> {
+    let (_, storage) = ssh::storage(profile, sock)?;
+    let effective = match effective {
+        Some(effective) => effective,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
+    let person = identities::person::revoke(
+        &storage,
+        &urn,
+        key,
+        replacement,
+        Revocation { reason, effective },
+    )?;
+    println!("{}", serde_json::to_string(&person::Display::from(person))?);
+    Ok(())
+}
+
 fn eval_accept(
     profile: &Profile,
     sock: SshAuthSock,
@@ -15,7 +15,7 @@ use librad::{
         Urn,
     },
     identities::{
-        git::Revision,
+        git::{error::Tip, Revision},
         payload::{self, KeyOrUrn},
     },
     profile::Profile,
@@ -48,6 +48,16 @@ pub fn eval(profile: &Profile, sock: SshAuthSock, opts: Options) -> anyhow::Resu
             eval_accept(profile, sock, urn, peer, force)?
         },
         Options::Tracked(Tracked { urn }) => eval_tracked(profile, urn)?,
+        Options::Review(Review { urn, peer }) => eval_review(profile, urn, peer)?,
+        Options::Propose(Propose {
+            urn,
+            whoami,
+            payload,
+            ext,
+            delegations,
+        }) => eval_propose(profile, sock, urn, whoami, payload, ext, delegations)?,
+        Options::Sign(Sign { urn, revision }) => eval_sign(profile, sock, urn, revision)?,
+        Options::Status(Status { urn }) => eval_status(profile, urn)?,
     }
 
     Ok(())
@@ -139,6 +149,60 @@ fn eval_update(
     Ok(())
 }
 
+fn eval_propose(
+    profile: &Profile,
+    sock: SshAuthSock,
+    urn: Urn,
+    whoami: Option<Urn>,
+    payload: Option<payload::Project>,
+    ext: Vec<payload::Ext<serde_json::Value>>,
+    delegations: Vec<KeyOrUrn<Revision>>,
+) -> anyhow::Result<()> {
+    let (_, storage) = ssh::storage(profile, sock)?;
+    let delegations = delegations.into_iter().collect();
+    let project = project::propose(&storage, &urn, whoami, payload, ext, delegations)?;
+    println!(
+        "{}",
+        serde_json::to_string(&project::Display::from(project))?
+    );
+    Ok(())
+}
+
+fn eval_sign(
+    profile: &Profile,
+    sock: SshAuthSock,
+    urn: Urn,
+    revision: Revision,
+) -> anyhow::Result<()> {
+    let (_, storage) = ssh::storage(profile, sock)?;
+    let project = identities::project::proposals::sign(&storage, &urn, revision)?;
+    println!(
+        "{}",
+        serde_json::to_string(&project::Display::from(project))?
+    );
+    Ok(())
+}
+
+fn eval_status(profile: &Profile, urn: Urn) -> anyhow::Result<()> {
+    let storage = storage::read_only(profile)?;
+    let proposals = identities::project::proposals::status(&storage, &urn)?
+        .into_iter()
+        .map(|proposal| {
+            serde_json::json!({
+                "revision": proposal.revision.to_string(),
+                "signers": proposal
+                    .signers
+                    .iter()
+                    .map(|key| PeerId::from(*key).to_string())
+                    .collect::<Vec<_>>(),
+                "adopted": proposal.adopted,
+            })
+        })
+        .collect::<Vec<_>>();
+    println!("{}", serde_json::to_string(&proposals)?);
+    Ok(())
+}
+
 fn eval_checkout(
     profile: &Profile,
     sock: SshAuthSock,
@@ -170,6 +234,34 @@ fn eval_diff(profile: &Profile, urn: Urn, peer: PeerId) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn eval_review(profile: &Profile, urn: Urn, peer: PeerId) -> anyhow::Result<()> {
+    let storage = storage::read_only(profile)?;
+    let review = match identities::project::review(&storage, &urn, peer)? {
+        identities::project::Review::UpToDate => serde_json::json!({ "status": "up-to-date" }),
+        identities::project::Review::Ahead => serde_json::json!({ "status": "ahead" }),
+        identities::project::Review::Behind => serde_json::json!({ "status": "behind" }),
+        identities::project::Review::Forked { ours, theirs } => serde_json::json!({
+            "status": "forked",
+            "ours": tip(&ours),
+            "theirs": tip(&theirs),
+        }),
+    };
+    println!("{}", serde_json::to_string(&review)?);
+    Ok(())
+}
+
+fn tip(tip: &Tip) -> serde_json::Value {
+    serde_json::json!({
+        "content_id": tip.content_id.to_string(),
+        "revision": tip.revision.to_string(),
+        "signers": tip
+            .signers
+            .iter()
+            .map(|key| PeerId::from(*key).to_string())
+            .collect::<Vec<_>>(),
+    })
+}
+
 fn eval_accept(
     profile: &Profile,
     sock: SshAuthSock,
@@ -3,19 +3,79 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use librad::profile::Profile;
-use lnk_clib::{keys::ssh::SshAuthSock, storage::ssh};
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fs,
+    path::PathBuf,
+};
+
+use anyhow::anyhow;
+
+use librad::{
+    canonical::Canonical as _,
+    git::{
+        tracking::{
+            config::{
+                cobs::{Filter, Policy},
+                Cobs,
+                Pattern,
+                TypeName,
+            },
+            git::config::{ObjectId, TypeName as CobType},
+            manifest::Manifest,
+            policy,
+            Applied,
+            Config,
+            Tracked,
+        },
+        Urn,
+    },
+    profile::Profile,
+    PeerId,
+};
+use lnk_clib::{
+    keys::ssh::SshAuthSock,
+    storage::{self, ssh},
+};
 
 use crate::{cli::args::tracking::*, tracking};
 
+pub fn eval(profile: &Profile, sock: SshAuthSock, opts: Options) -> anyhow::Result<()> {
+    match opts {
+        Options::Show(Show { urn, peer }) => eval_show(profile, urn, peer)?,
+        Options::List(List { urn }) => eval_list(profile, urn)?,
+        Options::Import(Import { manifest, policy }) => {
+            eval_import(profile, sock, manifest, policy)?
+        },
+        Options::Export(Export { urn }) => eval_export(profile, urn)?,
+    }
+
+    Ok(())
+}
+
 pub fn eval_track(
     profile: &Profile,
     sock: SshAuthSock,
-    Track { urn, peer }: Track,
+    Track {
+        urn,
+        peer,
+        config,
+        no_data,
+        allow_cob,
+        deny_cob,
+        policy,
+    }: Track,
 ) -> anyhow::Result<()> {
+    let config = match config {
+        Some(path) => read_config(path)?,
+        None => Config {
+            data: !no_data,
+            cobs: cobs(allow_cob, deny_cob)?,
+        },
+    };
     let (_, storage) = ssh::storage(profile, sock)?;
     let paths = profile.paths();
-    tracking::track(&storage, paths, &urn, peer)?;
+    tracking::track(&storage, paths, &urn, peer, config, policy)?;
     Ok(())
 }
 
@@ -29,3 +89,127 @@ pub fn eval_untrack(
     tracking::untrack(&storage, paths, &urn, peer)?;
     Ok(())
 }
+
+fn eval_show(profile: &Profile, urn: Urn, peer: Option<PeerId>) -> anyhow::Result<()> {
+    let storage = storage::read_only(profile)?;
+    let tracked = tracking::get(&storage, &urn, peer)?.ok_or_else(|| match peer {
+        Some(peer) => anyhow!("`{}` is not tracked for the URN `{}`", peer, urn),
+        None => anyhow!("the URN `{}` has no default tracking entry", urn),
+    })?;
+    println!("{}", serde_json::to_string(&display(tracked)?)?);
+    Ok(())
+}
+
+fn eval_list(profile: &Profile, urn: Option<Urn>) -> anyhow::Result<()> {
+    let storage = storage::read_only(profile)?;
+    let tracked = tracking::list(&storage, urn.as_ref())?
+        .into_iter()
+        .map(display)
+        .collect::<Result<Vec<_>, _>>()?;
+    println!("{}", serde_json::to_string(&tracked)?);
+    Ok(())
+}
+
+fn eval_import(
+    profile: &Profile,
+    sock: SshAuthSock,
+    path: PathBuf,
+    policy: policy::Track,
+) -> anyhow::Result<()> {
+    let manifest = fs::read_to_string(&path)?
+        .parse::<Manifest>()
+        .map_err(|err| anyhow!("invalid tracking manifest `{}`: {}", path.display(), err))?;
+    let (_, storage) = ssh::storage(profile, sock)?;
+    let paths = profile.paths();
+    let Applied {
+        updates,
+        rejections,
+    } = tracking::import(&storage, paths, &manifest, policy)?;
+    println!(
+        "{}",
+        serde_json::json!({
+            "tracked": updates.len(),
+            "rejected": rejections.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        })
+    );
+    Ok(())
+}
+
+fn eval_export(profile: &Profile, urn: Option<Urn>) -> anyhow::Result<()> {
+    let storage = storage::read_only(profile)?;
+    let manifest = tracking::export(&storage, urn.as_ref())?.canonical_form()?;
+    println!("{}", String::from_utf8(manifest)?);
+    Ok(())
+}
+
+fn display(tracked: Tracked) -> anyhow::Result<serde_json::Value> {
+    let config = tracked.config().canonical_form()?;
+    Ok(serde_json::json!({
+        "urn": tracked.urn().to_string(),
+        "peer": tracked.peer_id().map(|peer| peer.to_string()),
+        "config": serde_json::from_slice::<serde_json::Value>(&config)?,
+    }))
+}
+
+fn read_config(path: PathBuf) -> anyhow::Result<Config> {
+    let contents = fs::read_to_string(&path)?;
+    contents.parse().map_err(|err| {
+        anyhow!(
+            "invalid tracking configuration `{}`: {}",
+            path.display(),
+            err
+        )
+    })
+}
+
+/// Build the cob filters from the `--allow-cob` and `--deny-cob` options.
+/// Filters for the same type name are merged, as long as their policies agree.
+///
+/// If only `--deny-cob` is given, all other collaborative objects are
+/// allowed.
+pub fn cobs(
+    allow: Vec<CobFilter>,
+    deny: Vec<CobFilter>,
+) -> anyhow::Result<Cobs<CobType, ObjectId>> {
+    if allow.is_empty() && deny.is_empty() {
+        return Ok(Cobs::allow_all());
+    }
+
+    let only_deny = allow.is_empty();
+    let mut filters = BTreeMap::new();
+    let allow = allow.into_iter().map(|filter| (Policy::Allow, filter));
+    let deny = deny.into_iter().map(|filter| (Policy::Deny, filter));
+    for (policy, CobFilter { typename, pattern }) in allow.chain(deny) {
+        match filters.entry(typename) {
+            Entry::Vacant(entry) => {
+                entry.insert(Filter { policy, pattern });
+            },
+            Entry::Occupied(mut entry) => {
+                if entry.get().policy != policy {
+                    let typename = match entry.key() {
+                        TypeName::Wildcard => "*".to_owned(),
+                        TypeName::Type(ty) => ty.0.to_string(),
+                    };
+                    return Err(anyhow!(
+                        "cob type `{}` is both allowed and denied",
+                        typename
+                    ));
+                }
+                let filter = entry.get_mut();
+                match (&mut filter.pattern, pattern) {
+                    (Pattern::Objects(objs), Pattern::Objects(mut more)) => objs.append(&mut more),
+                    (current, _) => *current = Pattern::Wildcard,
+                }
+            },
+        }
+    }
+
+    if only_deny {
+        filters.entry(TypeName::Wildcard).or_insert(Filter {
+            policy: Policy::Allow,
+            pattern: Pattern::Wildcard,
+        });
+    }
+
+    Ok(filters.into_iter().collect())
+}
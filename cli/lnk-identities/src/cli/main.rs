@@ -28,6 +28,7 @@ pub fn main(
         Command::Refs(opts) => refs::eval(&profile, opts.refs)?,
         Command::Track(track) => tracking::eval_track(&profile, sock, track)?,
         Command::Untrack(untrack) => tracking::eval_untrack(&profile, sock, untrack)?,
+        Command::Tracking(opts) => tracking::eval(&profile, sock, opts.tracking)?,
     }
 
     Ok(())
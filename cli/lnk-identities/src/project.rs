@@ -182,9 +182,50 @@ pub fn update(
     urn: &Urn,
     whoami: Option<Urn>,
     payload: Option<payload::Project>,
-    mut ext: Vec<payload::Ext<serde_json::Value>>,
+    ext: Vec<payload::Ext<serde_json::Value>>,
+    delegations: BTreeSet<KeyOrUrn<Revision>>,
+) -> Result<Project, Error> {
+    let (whoami, payload, delegations) =
+        next_revision(storage, urn, whoami, payload, ext, delegations)?;
+    Ok(project::update(storage, urn, whoami, payload, delegations)?)
+}
+
+pub fn propose(
+    storage: &Storage,
+    urn: &Urn,
+    whoami: Option<Urn>,
+    payload: Option<payload::Project>,
+    ext: Vec<payload::Ext<serde_json::Value>>,
     delegations: BTreeSet<KeyOrUrn<Revision>>,
 ) -> Result<Project, Error> {
+    let (whoami, payload, delegations) =
+        next_revision(storage, urn, whoami, payload, ext, delegations)?;
+    Ok(project::proposals::propose(
+        storage,
+        urn,
+        whoami,
+        payload,
+        delegations,
+    )?)
+}
+
+/// Resolve the arguments of [`update`] and [`propose`] against the current
+/// state of the project.
+fn next_revision(
+    storage: &Storage,
+    urn: &Urn,
+    whoami: Option<Urn>,
+    payload: Option<payload::Project>,
+    mut ext: Vec<payload::Ext<serde_json::Value>>,
+    delegations: BTreeSet<KeyOrUrn<Revision>>,
+) -> Result<
+    (
+        Option<LocalIdentity>,
+        ProjectPayload,
+        Option<IndirectDelegation>,
+    ),
+    Error,
+> {
     let old =
         project::verify(storage, urn)?.ok_or_else(|| identities::Error::NotFound(urn.clone()))?;
     let mut old_payload = old.payload().clone();
@@ -216,7 +257,7 @@ pub fn update(
     } else {
         Some(resolve_indirect(storage, delegations)?)
     };
-    Ok(project::update(storage, urn, whoami, payload, delegations)?)
+    Ok((whoami, payload, delegations))
 }
 
 fn resolve_indirect(
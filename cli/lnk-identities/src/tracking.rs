@@ -3,10 +3,19 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
+use std::collections::BTreeSet;
+
 use thiserror::Error;
 
 use librad::{
-    git::{storage::Storage, tracking, Urn},
+    git::{
+        storage::{ReadOnly, Storage},
+        tracking::{
+            self,
+            manifest::{self, Manifest},
+        },
+        Urn,
+    },
     paths::Paths,
     PeerId,
 };
@@ -19,23 +28,43 @@ pub enum Error {
     #[error(transparent)]
     Include(#[from] include::Error),
 
+    #[error(transparent)]
+    Batch(#[from] tracking::error::Batch),
+
+    #[error(transparent)]
+    Get(#[from] tracking::error::Get),
+
+    #[error(transparent)]
+    IsTracked(#[from] tracking::error::IsTracked),
+
+    #[error(transparent)]
+    Previous(#[from] tracking::PreviousError),
+
     #[error(transparent)]
     Track(#[from] tracking::error::Track),
 
+    #[error(transparent)]
+    Tracked(#[from] tracking::error::Tracked),
+
     #[error(transparent)]
     Untrack(#[from] tracking::error::Untrack),
+
+    #[error("{} manifest entries violate the {policy:?} policy, nothing was imported", .rejected.len())]
+    Rejected {
+        policy: tracking::policy::Track,
+        rejected: Vec<manifest::Entry>,
+    },
 }
 
-// TODO(finto): allow specification of Config
-// TODO(finto): perhaps we want a flag to force track?
-pub fn track(storage: &Storage, paths: &Paths, urn: &Urn, peer: PeerId) -> Result<(), Error> {
-    let _tracked = tracking::track(
-        storage,
-        urn,
-        Some(peer),
-        tracking::Config::default(),
-        tracking::policy::Track::Any,
-    )?;
+pub fn track(
+    storage: &Storage,
+    paths: &Paths,
+    urn: &Urn,
+    peer: PeerId,
+    config: tracking::Config,
+    policy: tracking::policy::Track,
+) -> Result<(), Error> {
+    let _tracked = tracking::track(storage, urn, Some(peer), config, policy)??;
     include::update(storage, paths, urn)?;
     Ok(())
 }
@@ -50,3 +79,63 @@ pub fn untrack(storage: &Storage, paths: &Paths, urn: &Urn, peer: PeerId) -> Res
     include::update(storage, paths, urn)?;
     Ok(())
 }
+
+/// Get the tracking entry of `peer` for `urn`, or the default entry of `urn`
+/// if `peer` is `None`.
+pub fn get(
+    storage: &ReadOnly,
+    urn: &Urn,
+    peer: Option<PeerId>,
+) -> Result<Option<tracking::Tracked>, Error> {
+    Ok(tracking::get(storage, urn, peer)?)
+}
+
+/// List all tracking entries, or only the ones for `urn`.
+pub fn list(storage: &ReadOnly, urn: Option<&Urn>) -> Result<Vec<tracking::Tracked>, Error> {
+    Ok(tracking::tracked(storage, urn)?.collect::<Result<_, _>>()?)
+}
+
+/// Export all tracking entries, or only the ones for `urn`.
+pub fn export(storage: &ReadOnly, urn: Option<&Urn>) -> Result<Manifest, Error> {
+    Ok(tracking::tracked(storage, urn)?.collect::<Result<_, _>>()?)
+}
+
+/// Apply all entries of the `manifest` in a single batch, each subject to
+/// `policy`.
+///
+/// The entries are checked against `policy` before anything is written: if any
+/// of them would be rejected, [`Error::Rejected`] is returned and the tracking
+/// entries are left untouched.
+pub fn import(
+    storage: &Storage,
+    paths: &Paths,
+    manifest: &Manifest,
+    policy: tracking::policy::Track,
+) -> Result<tracking::Applied, Error> {
+    let mut rejected = Vec::new();
+    for entry in &manifest.entries {
+        let exists = tracking::is_tracked(storage, &entry.urn, entry.peer)?;
+        let accepted = match policy {
+            tracking::policy::Track::Any => true,
+            tracking::policy::Track::MustExist => exists,
+            tracking::policy::Track::MustNotExist => !exists,
+        };
+        if !accepted {
+            rejected.push(entry.clone());
+        }
+    }
+    if !rejected.is_empty() {
+        return Err(Error::Rejected { policy, rejected });
+    }
+
+    let applied = tracking::batch(storage, manifest.actions(policy))?;
+    let urns = manifest
+        .entries
+        .iter()
+        .map(|entry| &entry.urn)
+        .collect::<BTreeSet<_>>();
+    for urn in urns {
+        include::update(storage, paths, urn)?;
+    }
+    Ok(applied)
+}
@@ -0,0 +1,48 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeSet;
+
+use either::Either;
+
+use it_helpers::{
+    fixed::{TestPerson, TestProject},
+    tmp,
+};
+use librad::{
+    crypto::SecretKey,
+    git::{identities, storage::Storage},
+    identities::payload::KeyOrUrn,
+};
+use lnk_identities::project::{self, Creation, WhoAmI};
+
+#[test]
+fn create_with_delegates() -> anyhow::Result<()> {
+    let paths = tmp::paths();
+    let signer = SecretKey::new();
+    let storage = Storage::open(&*paths, signer.clone())?;
+    let alice = TestPerson::create(&storage)?;
+    let bob = SecretKey::new().public();
+
+    let proj = project::create::<String>(
+        &storage,
+        (*paths).clone(),
+        signer.into(),
+        WhoAmI::Urn(alice.owner.urn()),
+        BTreeSet::from([KeyOrUrn::from(Either::Left(bob))]),
+        TestProject::default_payload(),
+        vec![],
+        Creation::New { path: None },
+    )?;
+
+    let verified = identities::project::verify(&storage, &proj.urn())?
+        .expect("project must verify after creation");
+    assert_eq!(verified.urn(), proj.urn());
+    let delegations = verified.delegations();
+    assert!(delegations.iter().any(|d| d.left() == Some(&bob)));
+    assert!(delegations
+        .iter()
+        .any(|d| d.right().map(|person| person.urn()) == Some(alice.owner.urn())));
+
+    Ok(())
+}
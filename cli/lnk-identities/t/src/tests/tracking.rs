@@ -0,0 +1,126 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use it_helpers::{fixed::TestProject, tmp};
+use librad::{
+    crypto::SecretKey,
+    git::{
+        storage::Storage,
+        tracking::{
+            self,
+            config::{
+                cobs::{Filter, Policy},
+                Cobs,
+                Pattern,
+                TypeName,
+            },
+            git::config,
+            manifest::{Entry, Manifest},
+            policy,
+        },
+    },
+};
+use lnk_identities::{
+    cli::{args::tracking::CobFilter, eval::tracking::cobs},
+    tracking::{import, Error},
+};
+
+fn issues() -> TypeName<config::TypeName> {
+    TypeName::Type(config::TypeName("xyz.radicle.issue".parse().unwrap()))
+}
+
+#[test]
+fn deny_only_allows_the_rest() {
+    let cobs = cobs(
+        vec![],
+        vec![CobFilter {
+            typename: issues(),
+            pattern: Pattern::Wildcard,
+        }],
+    )
+    .unwrap();
+    assert_eq!(
+        cobs,
+        Cobs::from([
+            (
+                TypeName::Wildcard,
+                Filter {
+                    policy: Policy::Allow,
+                    pattern: Pattern::Wildcard,
+                }
+            ),
+            (
+                issues(),
+                Filter {
+                    policy: Policy::Deny,
+                    pattern: Pattern::Wildcard,
+                }
+            ),
+        ])
+    );
+}
+
+#[test]
+fn deny_wildcard() {
+    let cobs = cobs(
+        vec![],
+        vec![CobFilter {
+            typename: TypeName::Wildcard,
+            pattern: Pattern::Wildcard,
+        }],
+    )
+    .unwrap();
+    assert_eq!(cobs, Cobs::deny_all());
+}
+
+#[test]
+fn allow_denies_the_rest() {
+    let cobs = cobs(
+        vec![CobFilter {
+            typename: issues(),
+            pattern: Pattern::Wildcard,
+        }],
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(cobs.wildcard(), None);
+}
+
+#[test]
+fn import_is_all_or_nothing() -> anyhow::Result<()> {
+    let paths = tmp::paths();
+    let storage = Storage::open(&*paths, SecretKey::new())?;
+    let proj = TestProject::create(&storage)?;
+    let urn = proj.project.urn();
+    let tracked = SecretKey::new().public().into();
+    let untracked = SecretKey::new().public().into();
+    tracking::track(
+        &storage,
+        &urn,
+        Some(tracked),
+        tracking::Config::default(),
+        policy::Track::Any,
+    )??;
+
+    let manifest = Manifest {
+        entries: vec![
+            Entry {
+                urn: urn.clone(),
+                peer: Some(untracked),
+                config: tracking::Config::default(),
+            },
+            Entry {
+                urn: urn.clone(),
+                peer: Some(tracked),
+                config: tracking::Config::default(),
+            },
+        ],
+    };
+    assert_matches!(
+        import(&storage, &paths, &manifest, policy::Track::MustNotExist),
+        Err(Error::Rejected { rejected, .. }) if rejected == manifest.entries[1..]
+    );
+    assert!(!tracking::is_tracked(&storage, &urn, Some(untracked))?);
+
+    Ok(())
+}
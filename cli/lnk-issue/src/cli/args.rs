@@ -0,0 +1,224 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use clap::Parser;
+
+use librad::{
+    collaborative_objects::{issue::CommentId, ObjectId},
+    git::Urn,
+};
+
+/// Management of issues, i.e. tasks and bug reports tracked alongside a
+/// project.
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Parser)]
+pub enum Command {
+    Open(Open),
+    List(List),
+    Show(Show),
+    Comment(Comment),
+    Label(Label),
+    Unlabel(Unlabel),
+    Assign(Assign),
+    Unassign(Unassign),
+    Close(Close),
+    Reopen(Reopen),
+}
+
+/// open a new issue
+#[derive(Debug, Parser)]
+pub struct Open {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the Radicle URN of the local identity to author the issue as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the title of the issue
+    #[clap(long)]
+    pub title: String,
+
+    /// the description of the issue
+    #[clap(long, default_value = "")]
+    pub description: String,
+
+    /// a label to attach to the issue, may be given multiple times
+    #[clap(long = "label")]
+    pub labels: Vec<String>,
+
+    /// the Radicle URN of a person to assign to the issue, may be given
+    /// multiple times
+    #[clap(long = "assignee")]
+    pub assignees: Vec<Urn>,
+}
+
+/// list the issues of a project
+#[derive(Debug, Parser)]
+pub struct List {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+}
+
+/// show an issue
+#[derive(Debug, Parser)]
+pub struct Show {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+}
+
+/// comment on an issue, optionally in reply to another comment
+#[derive(Debug, Parser)]
+pub struct Comment {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to comment as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the text of the comment
+    #[clap(long)]
+    pub body: String,
+
+    /// the id of the comment to reply to
+    #[clap(long)]
+    pub reply_to: Option<CommentId>,
+}
+
+/// attach labels to an issue
+#[derive(Debug, Parser)]
+pub struct Label {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to label the issue as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the label to attach, may be given multiple times
+    #[clap(long = "label", required = true)]
+    pub labels: Vec<String>,
+}
+
+/// detach labels from an issue
+#[derive(Debug, Parser)]
+pub struct Unlabel {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to unlabel the issue as. If no
+    /// URN is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the label to detach, may be given multiple times
+    #[clap(long = "label", required = true)]
+    pub labels: Vec<String>,
+}
+
+/// assign persons to an issue
+#[derive(Debug, Parser)]
+pub struct Assign {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to assign as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the Radicle URN of the person to assign, may be given multiple times
+    #[clap(long = "assignee", required = true)]
+    pub assignees: Vec<Urn>,
+}
+
+/// unassign persons from an issue
+#[derive(Debug, Parser)]
+pub struct Unassign {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to unassign as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the Radicle URN of the person to unassign, may be given multiple times
+    #[clap(long = "assignee", required = true)]
+    pub assignees: Vec<Urn>,
+}
+
+/// close an issue
+#[derive(Debug, Parser)]
+pub struct Close {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to close the issue as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+}
+
+/// reopen a closed issue
+#[derive(Debug, Parser)]
+pub struct Reopen {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the issue
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to reopen the issue as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+}
@@ -0,0 +1,157 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use librad::{
+    collaborative_objects::{
+        issue::{Issue, Issues, NewIssue},
+        ObjectId,
+    },
+    git::{
+        identities::{self, local::LocalIdentity},
+        storage::Storage,
+        Urn,
+    },
+    profile::{LnkHome, Profile, ProfileId},
+};
+use lnk_clib::{keys::ssh::SshAuthSock, storage::ssh};
+
+use super::args::*;
+
+/// An [`Issue`] along with its identifier, for display purposes.
+#[derive(Serialize)]
+struct Display {
+    id: ObjectId,
+    #[serde(flatten)]
+    issue: Issue,
+}
+
+pub fn main(
+    Args { command }: Args,
+    profile: Option<ProfileId>,
+    sock: SshAuthSock,
+) -> anyhow::Result<()> {
+    let home = LnkHome::default();
+    let profile = Profile::from_home(&home, profile)?;
+    let (_, storage) = ssh::storage(&profile, sock)?;
+    let issues = Issues::new(
+        storage.collaborative_objects(Some(profile.paths().cob_cache_dir().to_path_buf())),
+    );
+
+    match command {
+        Command::Open(Open {
+            urn,
+            whoami,
+            title,
+            description,
+            labels,
+            assignees,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let (id, issue) = issues.create(
+                &whoami,
+                &urn,
+                NewIssue {
+                    title,
+                    description,
+                    labels,
+                    assignees,
+                },
+            )?;
+            print(&Display { id, issue })?
+        },
+        Command::List(List { urn }) => {
+            let issues = issues
+                .list(&urn)?
+                .into_iter()
+                .map(|(id, issue)| Display { id, issue })
+                .collect::<Vec<_>>();
+            print(&issues)?
+        },
+        Command::Show(Show { urn, id }) => {
+            let issue = issues
+                .get(&urn, &id)?
+                .ok_or_else(|| anyhow!("issue {} not found in {}", id, urn))?;
+            print(&Display { id, issue })?
+        },
+        Command::Comment(Comment {
+            urn,
+            id,
+            whoami,
+            body,
+            reply_to,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.comment(&whoami, &urn, &id, body, reply_to)?;
+            print(&Display { id, issue })?
+        },
+        Command::Label(Label {
+            urn,
+            id,
+            whoami,
+            labels,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.label(&whoami, &urn, &id, labels)?;
+            print(&Display { id, issue })?
+        },
+        Command::Unlabel(Unlabel {
+            urn,
+            id,
+            whoami,
+            labels,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.unlabel(&whoami, &urn, &id, labels)?;
+            print(&Display { id, issue })?
+        },
+        Command::Assign(Assign {
+            urn,
+            id,
+            whoami,
+            assignees,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.assign(&whoami, &urn, &id, assignees)?;
+            print(&Display { id, issue })?
+        },
+        Command::Unassign(Unassign {
+            urn,
+            id,
+            whoami,
+            assignees,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.unassign(&whoami, &urn, &id, assignees)?;
+            print(&Display { id, issue })?
+        },
+        Command::Close(Close { urn, id, whoami }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.close(&whoami, &urn, &id)?;
+            print(&Display { id, issue })?
+        },
+        Command::Reopen(Reopen { urn, id, whoami }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let issue = issues.reopen(&whoami, &urn, &id)?;
+            print(&Display { id, issue })?
+        },
+    }
+
+    Ok(())
+}
+
+fn resolve_whoami(storage: &Storage, whoami: Option<Urn>) -> anyhow::Result<LocalIdentity> {
+    match whoami {
+        None => identities::local::default(storage)?
+            .ok_or_else(|| anyhow!("no default identity was found, perhaps you need to set one")),
+        Some(urn) => identities::local::load(storage, urn.clone())?
+            .ok_or_else(|| anyhow!("the local identity `{}` does not exist", urn)),
+    }
+}
+
+fn print<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use librad::git::storage::maintenance::Task;
+
+#[derive(Clone, Debug, clap::Subcommand)]
+pub enum Args {
+    /// Remove objects which are no longer referenced from the monorepo
+    ///
+    /// Untracking peers or projects removes their references, but not the
+    /// objects they pointed to. This determines which objects are still
+    /// reachable from any reference and removes the others, provided they are
+    /// older than the expiry period. Objects written by a concurrently running
+    /// `linkd` or `lnk` are thus left alone.
+    Gc {
+        /// Only remove unreferenced objects older than this many seconds.
+        /// Defaults to two weeks.
+        #[clap(long)]
+        expire: Option<u64>,
+        /// Recompute all deltas while repacking. This is considerably slower,
+        /// but may result in a smaller repository.
+        #[clap(long)]
+        aggressive: bool,
+    },
+    /// Optimise the monorepo for faster fetching and serving
+    ///
+    /// This packs loose objects, consolidates packs, and updates the
+    /// multi-pack-index and commit-graph. Objects are packed such that they
+    /// only form deltas with objects of the same URN. No objects are removed,
+    /// but `multi-pack-index expire` deletes packs whose objects have been
+    /// consolidated into other packs. `linkd` rescans the packs when one it
+    /// knows of has gone missing, so it is safe to run this while `linkd` is
+    /// running, which can also be configured to run the maintenance
+    /// periodically.
+    Maintenance {
+        /// The task to run, one of `loose-objects`, `incremental-repack`, or
+        /// `commit-graph`. May be given multiple times. If not given, all
+        /// tasks are run.
+        #[clap(long, parse(try_from_str = task))]
+        task: Vec<Task>,
+        /// Consolidate all packs into one if there are more than this many.
+        #[clap(long)]
+        pack_limit: Option<u64>,
+    },
+}
+
+fn task(value: &str) -> Result<Task, String> {
+    match value {
+        "loose-objects" => Ok(Task::LooseObjects),
+        "incremental-repack" => Ok(Task::IncrementalRepack),
+        "commit-graph" => Ok(Task::CommitGraph),
+        _ => Err(format!("unknown maintenance task `{}`", value)),
+    }
+}
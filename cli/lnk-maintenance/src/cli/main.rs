@@ -0,0 +1,59 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use librad::{
+    git::storage::{gc, maintenance},
+    profile::{LnkHome, Profile, ProfileId},
+};
+use lnk_clib::{keys::ssh::SshAuthSock, storage::ssh};
+
+use super::args::Args;
+
+pub fn main(args: Args, profile: Option<ProfileId>, sock: SshAuthSock) -> anyhow::Result<()> {
+    let home = LnkHome::default();
+    let profile = Profile::from_home(&home, profile)?;
+
+    match args {
+        Args::Gc { expire, aggressive } => {
+            let (_, storage) = ssh::storage(&profile, sock)?;
+            let opts = gc::Options {
+                expire: expire
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| gc::Options::default().expire),
+                aggressive,
+            };
+            let gc::Stats { before, after } = gc::collect(&storage, &opts)?;
+            println!(
+                "{}",
+                serde_json::json!({
+                    "before": { "objects": before.loose + before.packed, "size": before.size() },
+                    "after": { "objects": after.loose + after.packed, "size": after.size() },
+                })
+            );
+        },
+        Args::Maintenance { task, pack_limit } => {
+            let (_, storage) = ssh::storage(&profile, sock)?;
+            let defaults = maintenance::Options::default();
+            let opts = maintenance::Options {
+                tasks: if task.is_empty() {
+                    defaults.tasks
+                } else {
+                    task
+                },
+                pack_limit: pack_limit.unwrap_or(defaults.pack_limit),
+            };
+            let gc::Stats { before, after } = maintenance::run(&storage, &opts)?;
+            println!(
+                "{}",
+                serde_json::json!({
+                    "before": { "packs": before.packs, "loose": before.loose },
+                    "after": { "packs": after.packs, "loose": after.loose },
+                })
+            );
+        },
+    }
+
+    Ok(())
+}
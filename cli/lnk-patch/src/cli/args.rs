@@ -0,0 +1,228 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use librad::{
+    collaborative_objects::{patch::Verdict, ObjectId},
+    git::Urn,
+};
+use radicle_git_ext::Oid;
+
+/// Management of patches, i.e. proposals to merge commits into a project.
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Parser)]
+pub enum Command {
+    Open(Open),
+    List(List),
+    Show(Show),
+    Revise(Revise),
+    Review(Review),
+    Comment(Comment),
+    Merge(Merge),
+    Close(Close),
+}
+
+/// open a new patch
+#[derive(Debug, Parser)]
+pub struct Open {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the Radicle URN of the local identity to author the patch as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the title of the patch
+    #[clap(long)]
+    pub title: String,
+
+    /// the description of the patch
+    #[clap(long, default_value = "")]
+    pub description: String,
+
+    /// the name of the branch the patch is to be merged into
+    #[clap(long)]
+    pub target: String,
+
+    /// the commit proposed to be merged
+    #[clap(long)]
+    pub commit: Oid,
+
+    /// the commit the proposed commit is based on
+    #[clap(long)]
+    pub base: Option<Oid>,
+}
+
+/// list the patches of a project
+#[derive(Debug, Parser)]
+pub struct List {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+}
+
+/// show a patch
+#[derive(Debug, Parser)]
+pub struct Show {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+}
+
+/// add a new revision to a patch
+#[derive(Debug, Parser)]
+pub struct Revise {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to author the revision as. If no
+    /// URN is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the commit proposed to be merged
+    #[clap(long)]
+    pub commit: Oid,
+
+    /// the commit the proposed commit is based on
+    #[clap(long)]
+    pub base: Option<Oid>,
+
+    /// what changed compared to the previous revision
+    #[clap(long, default_value = "")]
+    pub description: String,
+}
+
+/// review a revision of a patch
+#[derive(Debug, Parser)]
+pub struct Review {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to review as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the commit of the revision under review. If not provided, the latest
+    /// revision is reviewed.
+    #[clap(long)]
+    pub revision: Option<Oid>,
+
+    /// the verdict of the review: `accept`, `reject`, or `comment`
+    #[clap(long, parse(try_from_str = verdict))]
+    pub verdict: Verdict,
+
+    /// a comment summarising the review
+    #[clap(long)]
+    pub comment: Option<String>,
+}
+
+/// comment on a patch, optionally on a line of a file
+#[derive(Debug, Parser)]
+pub struct Comment {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to comment as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the text of the comment
+    #[clap(long)]
+    pub body: String,
+
+    /// the path of the file to comment on, relative to the repository root
+    #[clap(long, requires = "line")]
+    pub path: Option<PathBuf>,
+
+    /// the line of the file to comment on, starting at 1
+    #[clap(long, requires = "path")]
+    pub line: Option<u64>,
+
+    /// the commit of the revision the comment refers to. If not provided, the
+    /// latest revision is used.
+    #[clap(long, requires = "path")]
+    pub revision: Option<Oid>,
+}
+
+/// mark a patch as merged
+#[derive(Debug, Parser)]
+pub struct Merge {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to merge as. If no URN is
+    /// provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+
+    /// the commit of the revision which got merged. If not provided, the
+    /// latest revision is used.
+    #[clap(long)]
+    pub revision: Option<Oid>,
+}
+
+/// close a patch without merging it
+#[derive(Debug, Parser)]
+pub struct Close {
+    /// the URN of the project
+    #[clap(long)]
+    pub urn: Urn,
+
+    /// the identifier of the patch
+    #[clap(long)]
+    pub id: ObjectId,
+
+    /// the Radicle URN of the local identity to close the patch as. If no URN
+    /// is provided the default identity will be used instead.
+    #[clap(long)]
+    pub whoami: Option<Urn>,
+}
+
+fn verdict(value: &str) -> Result<Verdict, String> {
+    match value {
+        "accept" => Ok(Verdict::Accept),
+        "reject" => Ok(Verdict::Reject),
+        "comment" => Ok(Verdict::Comment),
+        other => Err(format!(
+            "invalid verdict `{}`, expected one of `accept`, `reject`, `comment`",
+            other
+        )),
+    }
+}
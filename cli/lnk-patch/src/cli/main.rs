@@ -0,0 +1,171 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use librad::{
+    collaborative_objects::{
+        patch::{Anchor, NewPatch, Patch, Patches},
+        ObjectId,
+    },
+    git::{
+        identities::{self, local::LocalIdentity},
+        storage::Storage,
+        Urn,
+    },
+    profile::{LnkHome, Profile, ProfileId},
+};
+use lnk_clib::{keys::ssh::SshAuthSock, storage::ssh};
+
+use super::args::*;
+
+/// A [`Patch`] along with its identifier, for display purposes.
+#[derive(Serialize)]
+struct Display {
+    id: ObjectId,
+    #[serde(flatten)]
+    patch: Patch,
+}
+
+pub fn main(
+    Args { command }: Args,
+    profile: Option<ProfileId>,
+    sock: SshAuthSock,
+) -> anyhow::Result<()> {
+    let home = LnkHome::default();
+    let profile = Profile::from_home(&home, profile)?;
+    let (_, storage) = ssh::storage(&profile, sock)?;
+    let patches = Patches::new(
+        storage.collaborative_objects(Some(profile.paths().cob_cache_dir().to_path_buf())),
+    );
+
+    match command {
+        Command::Open(Open {
+            urn,
+            whoami,
+            title,
+            description,
+            target,
+            commit,
+            base,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let (id, patch) = patches.create(
+                &whoami,
+                &urn,
+                NewPatch {
+                    title,
+                    description,
+                    target,
+                    commit,
+                    base,
+                },
+            )?;
+            print(&Display { id, patch })?
+        },
+        Command::List(List { urn }) => {
+            let patches = patches
+                .list(&urn)?
+                .into_iter()
+                .map(|(id, patch)| Display { id, patch })
+                .collect::<Vec<_>>();
+            print(&patches)?
+        },
+        Command::Show(Show { urn, id }) => {
+            let patch = patches
+                .get(&urn, &id)?
+                .ok_or_else(|| anyhow!("patch {} not found in {}", id, urn))?;
+            print(&Display { id, patch })?
+        },
+        Command::Revise(Revise {
+            urn,
+            id,
+            whoami,
+            commit,
+            base,
+            description,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let patch = patches.revise(&whoami, &urn, &id, commit, base, description)?;
+            print(&Display { id, patch })?
+        },
+        Command::Review(Review {
+            urn,
+            id,
+            whoami,
+            revision,
+            verdict,
+            comment,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let patch = patches.review(&whoami, &urn, &id, revision, verdict, comment)?;
+            print(&Display { id, patch })?
+        },
+        Command::Comment(Comment {
+            urn,
+            id,
+            whoami,
+            body,
+            path,
+            line,
+            revision,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let anchor = match (path, line) {
+                (Some(path), Some(line)) => {
+                    let revision = match revision {
+                        Some(revision) => revision,
+                        None => patches
+                            .get(&urn, &id)?
+                            .and_then(|patch| patch.latest().map(|rev| rev.commit))
+                            .ok_or_else(|| anyhow!("patch {} has no revisions", id))?,
+                    };
+                    let path = path
+                        .to_str()
+                        .ok_or_else(|| anyhow!("path is not valid UTF-8: {}", path.display()))?
+                        .to_owned();
+                    Some(Anchor {
+                        revision,
+                        path,
+                        line,
+                    })
+                },
+                _ => None,
+            };
+            let patch = patches.comment(&whoami, &urn, &id, body, anchor)?;
+            print(&Display { id, patch })?
+        },
+        Command::Merge(Merge {
+            urn,
+            id,
+            whoami,
+            revision,
+        }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let patch = patches.merge(&whoami, &urn, &id, revision)?;
+            print(&Display { id, patch })?
+        },
+        Command::Close(Close { urn, id, whoami }) => {
+            let whoami = resolve_whoami(&storage, whoami)?;
+            let patch = patches.close(&whoami, &urn, &id)?;
+            print(&Display { id, patch })?
+        },
+    }
+
+    Ok(())
+}
+
+fn resolve_whoami(storage: &Storage, whoami: Option<Urn>) -> anyhow::Result<LocalIdentity> {
+    match whoami {
+        None => identities::local::default(storage)?
+            .ok_or_else(|| anyhow!("no default identity was found, perhaps you need to set one")),
+        Some(urn) => identities::local::load(storage, urn.clone())?
+            .ok_or_else(|| anyhow!("the local identity `{}` does not exist", urn)),
+    }
+}
+
+fn print<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
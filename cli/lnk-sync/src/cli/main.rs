@@ -15,6 +15,7 @@ use librad::{
         Network,
     },
     profile::{LnkHome, Profile, ProfileId},
+    PeerId,
 };
 use link_async::Spawner;
 use lnk_clib::{
@@ -22,7 +23,7 @@ use lnk_clib::{
     seed::{self, Seeds},
 };
 
-use crate::{cli::args::Args, forked, sync};
+use crate::{cli::args::Args, forked, sync_via};
 
 pub fn main(
     args: Args,
@@ -52,6 +53,7 @@ pub fn main(
         };
         let endpoint = quic::SendOnly::new(signer.clone(), Network::default()).await?;
         let client = Client::new(config, spawner, endpoint)?;
+        let rpc_socket = paths.rpc_socket(&PeerId::from_signer(&signer));
         let seeds = {
             let seeds_file = profile.paths().seeds_file();
             let store = seed::store::FileStore::<String>::new(seeds_file)?;
@@ -74,7 +76,7 @@ pub fn main(
         };
         match args {
             Args::Sync { urn, mode } => {
-                let synced = sync(&client, urn, seeds, mode).await;
+                let synced = sync_via(&rpc_socket, &client, urn, seeds, mode).await;
                 println!("{}", serde_json::to_string(&synced)?);
             },
             Args::Clone { urn, path, peer } => {
@@ -84,7 +86,7 @@ pub fn main(
                 let path = WorkingCopyDir::at_or_current_dir(path)?;
                 println!("cloning urn {} into {}", urn, path);
                 println!("syncing monorepo with seeds");
-                sync(&client, urn.clone(), seeds, crate::Mode::Fetch).await;
+                sync_via(&rpc_socket, &client, urn.clone(), seeds, crate::Mode::Fetch).await;
 
                 if !already_had_urn {
                     // This is the first time we've seen this project, so we set the default head
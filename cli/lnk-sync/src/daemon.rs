@@ -0,0 +1,154 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Synchronisation by way of a running `linkd` node, rather than spinning up
+//! an ephemeral [`librad::net::peer::Client`].
+
+use std::path::Path;
+
+use librad::git::Urn;
+use linkd_lib::api::{
+    client::{Command, Connection, Reply},
+    io::SocketTransport,
+    messages,
+    replicate,
+};
+use lnk_clib::seed::Seeds;
+
+use crate::{replication, request_pull, Mode, Synced};
+
+const USER_AGENT: &str = "lnk-sync";
+
+/// Connect to the `linkd` node listening on `rpc_socket`.
+///
+/// Returns `None` if no node is reachable, in which case the caller should
+/// fall back to [`crate::sync`].
+pub async fn connect<P: AsRef<Path>>(rpc_socket: P) -> Option<Connection<SocketTransport>> {
+    match Connection::connect(USER_AGENT, rpc_socket).await {
+        Ok(conn) => Some(conn),
+        Err(err) => {
+            tracing::debug!(err = %err, "linkd node not reachable");
+            None
+        },
+    }
+}
+
+/// Synchronise with the provided list of `seeds` for the given `urn`, asking
+/// the `linkd` node on the other end of `conn` to do the work.
+///
+/// For each seed the [`Mode`] is checked to see if it should replicate and
+/// request-pull. A failure to do either is reported, and the seed is recorded
+/// without the corresponding result.
+pub async fn sync(
+    conn: Connection<SocketTransport>,
+    urn: Urn,
+    seeds: Seeds,
+    mode: Mode,
+) -> Vec<Synced> {
+    let mut conn = Some(conn);
+    let mut syncs = Vec::with_capacity(seeds.len());
+    for seed in seeds.0.into_iter() {
+        let replication = if mode.is_fetch() {
+            let cmd = Command::replicate(urn.clone(), vec![seed.clone().into()]);
+            let res = run(&mut conn, cmd)
+                .await
+                .and_then(|replicate::Response(replicated)| {
+                    replicated
+                        .into_iter()
+                        .find(|r| r.peer == seed.peer)
+                        .ok_or_else(|| "no result for the seed".to_owned())
+                        .and_then(|r| match r.result {
+                            replicate::Outcome::Success(s) => Ok(replication::Success::from(s)),
+                            replicate::Outcome::Failure(err) => Err(err),
+                        })
+                });
+            match res {
+                Ok(success) => Some(success),
+                Err(err) => {
+                    eprintln!(
+                        "failed to replicate from the seed: {}, reason: {}",
+                        seed.peer, err
+                    );
+                    tracing::error!(seed = %seed.peer, err = %err, "replication error");
+                    None
+                },
+            }
+        } else {
+            None
+        };
+
+        let request_pull = if mode.is_push() {
+            let cmd = Command::request_pull(urn.clone(), seed.peer, seed.addrs.clone());
+            match run(&mut conn, cmd).await {
+                Ok(resp) => Some(request_pull::Success::from(resp.0)),
+                Err(err) => {
+                    eprintln!(
+                        "failed to request-pull to the seed: {}, reason: {}",
+                        seed.peer, err
+                    );
+                    tracing::error!(seed = %seed.peer, err = %err, "request-pull error");
+                    None
+                },
+            }
+        } else {
+            None
+        };
+
+        syncs.push(Synced {
+            seed,
+            replication,
+            request_pull,
+        })
+    }
+    syncs
+}
+
+/// Run `cmd`, yielding either the response or the reason for the failure, be
+/// it reported by the node or a transport error.
+///
+/// The connection is put back into `conn` once the node replied. If sending
+/// the request fails, the connection is gone and so subsequent commands fail
+/// too.
+async fn run<Rq, Rs>(
+    conn: &mut Option<Connection<SocketTransport>>,
+    cmd: Command<Rq, Rs>,
+) -> Result<Rs, String>
+where
+    Rq: Into<messages::RequestPayload>,
+    Rs: messages::RecvPayload,
+{
+    let next = conn
+        .take()
+        .ok_or_else(|| "lost the connection to the linkd node".to_owned())?;
+    let mut replies = cmd
+        .execute_with_reply(next)
+        .await
+        .map_err(|err| err.to_string())?;
+    loop {
+        match replies.next().await {
+            Ok(Reply::Progress {
+                replies: next_replies,
+                msg,
+            }) => {
+                eprintln!("{}", msg);
+                tracing::info!("linkd progress {}", msg);
+                replies = next_replies;
+            },
+            Ok(Reply::Success {
+                conn: next,
+                payload,
+            }) => {
+                *conn = Some(next);
+                return Ok(payload);
+            },
+            Ok(Reply::Error { conn: next, msg }) => {
+                *conn = Some(next);
+                return Err(msg);
+            },
+            Err((next, err)) => {
+                *conn = Some(next);
+                return Err(err.to_string());
+            },
+        }
+    }
+}
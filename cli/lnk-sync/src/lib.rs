@@ -17,6 +17,7 @@ use librad::{
 use lnk_clib::seed::{Seed, Seeds};
 
 pub mod cli;
+pub mod daemon;
 mod forked;
 pub mod replication;
 pub mod request_pull;
@@ -94,6 +95,29 @@ impl fmt::Display for Mode {
     }
 }
 
+/// Synchronise via the `linkd` node listening on `rpc_socket` if one is
+/// reachable, otherwise fall back to [`sync`] using `client`.
+pub async fn sync_via<S, E, P>(
+    rpc_socket: P,
+    client: &Client<S, E>,
+    urn: Urn,
+    seeds: Seeds,
+    mode: Mode,
+) -> Vec<Synced>
+where
+    S: Signer + Clone,
+    E: ConnectPeer + Clone + Send + Sync + 'static,
+    P: AsRef<std::path::Path>,
+{
+    match daemon::connect(rpc_socket).await {
+        Some(conn) => {
+            tracing::info!("synchronising via linkd node");
+            daemon::sync(conn, urn, seeds, mode).await
+        },
+        None => sync(client, urn, seeds, mode).await,
+    }
+}
+
 /// Synchronise with the provided list of `seeds` for the given `urn`.
 ///
 /// For each seed the [`Mode`] is checked to see if it should replicate and
@@ -18,6 +18,7 @@ use librad::{
     PeerId,
     Signer,
 };
+use linkd_lib::api::{events, replicate};
 use lnk_clib::seed::Seed;
 
 pub(super) async fn replicate<S, E>(
@@ -71,6 +72,21 @@ impl From<replication::Success> for Success {
     }
 }
 
+impl From<replicate::Success> for Success {
+    fn from(s: replicate::Success) -> Self {
+        Self {
+            references: s.updated.into_iter().collect(),
+            rejected: s.rejected.into_iter().collect(),
+            tracked: Tracked {
+                indirect: s.tracked_urns,
+                direct: s.tracked_peers,
+            },
+            created: s.created.into_iter().collect(),
+            requires_confirmation: s.requires_confirmation,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Created {
     urns: Vec<Urn>,
@@ -118,6 +134,23 @@ impl<'a> FromIterator<link_replication::Update<'a>> for Rejected {
     }
 }
 
+impl FromIterator<events::Updated> for Rejected {
+    fn from_iter<T: IntoIterator<Item = events::Updated>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::default(), |mut rej, update| {
+            match update {
+                events::Updated::Direct { name, target } => {
+                    rej.direct.push(Direct { name, target })
+                },
+                events::Updated::Symbolic { name, target } => {
+                    rej.symbolic.push(Symbolic { name, target })
+                },
+                events::Updated::Prune { name } => rej.pruned.push(name),
+            }
+            rej
+        })
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct References {
     updated: Updates,
@@ -128,7 +161,7 @@ impl FromIterator<link_replication::Updated> for References {
     fn from_iter<T: IntoIterator<Item = link_replication::Updated>>(iter: T) -> Self {
         iter.into_iter().fold(Self::default(), |mut refs, update| {
             match update {
-                link_replication::Updated::Direct { name, target } => {
+                link_replication::Updated::Direct { name, target, .. } => {
                     refs.updated.direct.push(Direct {
                         name,
                         target: target.into(),
@@ -137,7 +170,24 @@ impl FromIterator<link_replication::Updated> for References {
                 link_replication::Updated::Symbolic { name, target } => {
                     refs.updated.symbolic.push(Symbolic { name, target })
                 },
-                link_replication::Updated::Prune { name } => refs.pruned.push(name),
+                link_replication::Updated::Prune { name, .. } => refs.pruned.push(name),
+            }
+            refs
+        })
+    }
+}
+
+impl FromIterator<events::Updated> for References {
+    fn from_iter<T: IntoIterator<Item = events::Updated>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::default(), |mut refs, update| {
+            match update {
+                events::Updated::Direct { name, target } => {
+                    refs.updated.direct.push(Direct { name, target })
+                },
+                events::Updated::Symbolic { name, target } => {
+                    refs.updated.symbolic.push(Symbolic { name, target })
+                },
+                events::Updated::Prune { name } => refs.pruned.push(name),
             }
             refs
         })
@@ -3,6 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
+use either::Either;
 use link_identities::{git::Urn, Person, Project, VerifiedPerson, VerifiedProject};
 
 pub enum AuthDecision {
@@ -16,6 +17,9 @@ pub trait AuthorizingIdentity: std::fmt::Debug {
     /// Check whether `principal` is allowed to make changes to COBs in this
     /// `AuthorizingIdentity`
     fn check_authorization(&self, principal: &VerifiedPerson) -> AuthDecision;
+    /// Check whether `principal` is a delegate of this `AuthorizingIdentity`,
+    /// for the purpose of enforcing the [`crate::policy::Policy`] of a COB
+    fn is_delegate(&self, principal: &VerifiedPerson) -> bool;
     /// The OID of the tip of this identity
     fn content_id(&self) -> git2::Oid;
 }
@@ -37,6 +41,10 @@ impl AuthorizingIdentity for VerifiedPerson {
         }
     }
 
+    fn is_delegate(&self, principal: &VerifiedPerson) -> bool {
+        self.urn() == principal.urn()
+    }
+
     fn content_id(&self) -> git2::Oid {
         self.content_id.into()
     }
@@ -52,6 +60,14 @@ impl AuthorizingIdentity for VerifiedProject {
         AuthDecision::Authorized
     }
 
+    fn is_delegate(&self, principal: &VerifiedPerson) -> bool {
+        let urn = principal.urn();
+        self.delegations().iter().any(|delegate| match delegate {
+            Either::Left(key) => principal.delegations().contains(key),
+            Either::Right(person) => person.urn() == urn,
+        })
+    }
+
     fn content_id(&self) -> git2::Oid {
         self.content_id.into()
     }
@@ -4,13 +4,14 @@
 // Linking Exception. For full terms see the included LICENSE file.
 
 use crate::{
+    policy::Principal,
     validated_automerge::error::ProposalError,
     EntryContents,
     History,
     ObjectId,
     Schema,
     TypeName,
-    ValidatedAutomerge,
+    ValidatedHistory,
 };
 
 use link_identities::git::Urn;
@@ -68,22 +69,21 @@ impl CachedChangeGraph {
         &self.history
     }
 
-    pub(crate) fn propose_change(&mut self, change: &EntryContents) -> Result<(), ProposalError> {
-        match change {
-            EntryContents::Automerge(change_bytes) => {
-                let mut validated = self.history.traverse(
-                    ValidatedAutomerge::new(self.schema.clone()),
-                    |mut doc, entry| {
-                        // This unwrap should be safe as we only save things in the cache when we've
-                        // validated them
-                        doc.propose_change(entry.contents().as_ref()).unwrap();
-                        ControlFlow::Continue(doc)
-                    },
-                );
-                validated.propose_change(change_bytes)?;
+    pub(crate) fn propose_change(
+        &mut self,
+        change: &EntryContents,
+        principal: &Principal,
+    ) -> Result<(), ProposalError> {
+        let mut validated = self.history.traverse(
+            ValidatedHistory::new(self.history.history_type(), self.schema.clone()),
+            |mut doc, entry| {
+                // This unwrap should be safe as we only save things in the cache when we've
+                // validated them
+                doc.replay_change(entry.contents()).unwrap();
+                ControlFlow::Continue(doc)
             },
-        }
-        Ok(())
+        );
+        validated.propose_change(change, principal)
     }
 
     pub fn schema(&self) -> &Schema {
@@ -157,17 +157,18 @@ impl Change {
         let manifest: Manifest =
             toml::de::from_slice(manifest_blob.content()).map_err(error::Load::InvalidManifest)?;
 
-        let contents = match manifest.history_type {
-            HistoryType::Automerge => {
-                let contents_tree_entry = tree
-                    .get_name(CHANGE_BLOB_NAME)
-                    .ok_or(error::Load::NoChange)?;
-                let contents_object = contents_tree_entry.to_object(repo)?;
-                let contents_blob = contents_object
-                    .as_blob()
-                    .ok_or(error::Load::ChangeNotBlob)?;
-                EntryContents::Automerge(contents_blob.content().into())
-            },
+        let contents = {
+            let contents_tree_entry = tree
+                .get_name(CHANGE_BLOB_NAME)
+                .ok_or(error::Load::NoChange)?;
+            let contents_object = contents_tree_entry.to_object(repo)?;
+            let contents_blob = contents_object
+                .as_blob()
+                .ok_or(error::Load::ChangeNotBlob)?;
+            match manifest.history_type {
+                HistoryType::Automerge => EntryContents::Automerge(contents_blob.content().into()),
+                HistoryType::OpLog => EntryContents::OpLog(contents_blob.content().into()),
+            }
         };
 
         let schema_commit_trailer =
@@ -115,15 +115,16 @@ impl<'a> ChangeGraph<'a> {
         // This is okay because we check that the graph has a root node in
         // GraphBuilder::build
         let root = roots.first().unwrap();
-        let typename = {
+        let (typename, history_type) = {
             let first_node = &self.graph[*root];
-            first_node.typename().clone()
+            (first_node.typename().clone(), first_node.contents().into())
         };
         let evaluating = evaluation::Evaluating::new(
             identities,
             self.authorizing_identity,
             self.repo,
             self.schema().clone(),
+            history_type,
         );
         let topo = Topo::new(&self.graph);
         let items = topo.iter(&self.graph).map(|idx| {
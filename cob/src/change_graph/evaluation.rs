@@ -9,19 +9,22 @@ use crate::{
     change::Change,
     history,
     identity_storage::{lookup_authorizing_identity, lookup_person},
+    policy::{Principal, Violation},
     pruning_fold,
-    validated_automerge::{error::ProposalError, ValidatedAutomerge},
+    validated_automerge::error::ProposalError,
     AuthDecision,
     AuthorizingIdentity,
+    HistoryType,
     IdentityStorage,
     Schema,
+    ValidatedHistory,
 };
 
 pub struct Evaluating<'a, I: IdentityStorage> {
     identities: &'a I,
     authorizing_identity: &'a dyn AuthorizingIdentity,
     repo: &'a git2::Repository,
-    in_progress_history: ValidatedAutomerge,
+    in_progress_history: ValidatedHistory,
 }
 
 impl<'a, I: IdentityStorage> Evaluating<'a, I> {
@@ -30,12 +33,13 @@ impl<'a, I: IdentityStorage> Evaluating<'a, I> {
         authorizer: &'a dyn AuthorizingIdentity,
         repo: &'a git2::Repository,
         schema: Schema,
+        history_type: HistoryType,
     ) -> Evaluating<'a, I> {
         Evaluating {
             identities,
             authorizing_identity: authorizer,
             repo,
-            in_progress_history: ValidatedAutomerge::new(schema),
+            in_progress_history: ValidatedHistory::new(history_type, schema),
         }
     }
 
@@ -112,15 +116,18 @@ impl<'a, I: IdentityStorage> Evaluating<'a, I> {
         };
 
         // Check that the history the change carries is well formed and does not violate
-        // the schema
-        match &change.contents() {
-            history::EntryContents::Automerge(bytes) => {
-                match self.in_progress_history.propose_change(bytes) {
-                    Ok(()) => {},
-                    Err(e) => {
-                        return Err(RejectionReason::InvalidChange(e));
-                    },
-                }
+        // the schema nor the authorization policy of the object
+        let principal = Principal::new(referenced_auth_identity.as_ref(), &author);
+        match self
+            .in_progress_history
+            .propose_change(change.contents(), &principal)
+        {
+            Ok(()) => {},
+            Err(ProposalError::Unauthorized(violation)) => {
+                return Err(RejectionReason::PolicyViolation(violation));
+            },
+            Err(e) => {
+                return Err(RejectionReason::InvalidChange(e));
             },
         };
 
@@ -164,6 +171,7 @@ enum RejectionReason {
     Unauthorized {
         reason: &'static str,
     },
+    PolicyViolation(Violation),
     InvalidChange(ProposalError),
 }
 
@@ -214,6 +222,13 @@ impl RejectionReason {
                     "rejecting change as it was not authorized"
                 );
             },
+            RejectionReason::PolicyViolation(violation) => {
+                tracing::warn!(
+                    commit=?change.commit(),
+                    %violation,
+                    "rejecting change as it violates the authorization policy of the object"
+                );
+            },
             RejectionReason::InvalidChange(error) => {
                 tracing::warn!(
                     err=?error,
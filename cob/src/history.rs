@@ -124,6 +124,13 @@ impl History {
         }
     }
 
+    /// The kind of history this is, as determined by the change which created
+    /// the object.
+    pub fn history_type(&self) -> HistoryType {
+        // SAFETY: the constructors ensure the root is in `entries`
+        self.entries[&self.root].contents().into()
+    }
+
     pub(crate) fn tips(&self) -> BTreeSet<EntryId> {
         self.graph
             .externals(petgraph::Direction::Outgoing)
@@ -162,24 +169,59 @@ fn create_petgraph<'a>(root: &'a EntryId, entries: &'a HashMap<EntryId, HistoryE
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq, minicbor::Encode, minicbor::Decode)]
 pub enum EntryContents {
+    /// An encoded Automerge change.
     #[n(0)]
     Automerge(
         #[cbor(with = "minicbor::bytes")]
         #[n(0)]
         Vec<u8>,
     ),
+    /// A JSON array of operations, see [`crate::op_log`].
+    #[n(1)]
+    OpLog(
+        #[cbor(with = "minicbor::bytes")]
+        #[n(0)]
+        Vec<u8>,
+    ),
 }
 
-#[derive(serde::Serialize, serde::Deserialize, minicbor::Encode, minicbor::Decode)]
+impl EntryContents {
+    /// The contents of an [`HistoryType::OpLog`] change carrying `ops`.
+    pub fn op_log(ops: &[serde_json::Value]) -> Self {
+        // SAFETY: serialising JSON values to an in-memory buffer cannot fail
+        Self::OpLog(serde_json::to_vec(ops).unwrap())
+    }
+}
+
+/// The kind of history a collaborative object is made of. All changes to an
+/// object must be of the same kind as the change which created it.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    minicbor::Encode,
+    minicbor::Decode,
+)]
 pub enum HistoryType {
+    /// The changes are Automerge changes, and the state of the object is the
+    /// Automerge document they make up.
     #[n(0)]
     Automerge,
+    /// The changes are lists of operations, which applications fold into the
+    /// state of the object themselves. See [`crate::op_log`].
+    #[n(1)]
+    OpLog,
 }
 
 impl From<&EntryContents> for HistoryType {
     fn from(c: &EntryContents) -> Self {
         match c {
             EntryContents::Automerge(..) => HistoryType::Automerge,
+            EntryContents::OpLog(..) => HistoryType::OpLog,
         }
     }
 }
@@ -188,6 +230,7 @@ impl AsRef<[u8]> for EntryContents {
     fn as_ref(&self) -> &[u8] {
         match self {
             Self::Automerge(bytes) => bytes,
+            Self::OpLog(bytes) => bytes,
         }
     }
 }
@@ -84,6 +84,9 @@ use change_graph::ChangeGraph;
 pub mod schema;
 pub use schema::Schema;
 
+pub mod policy;
+use policy::Principal;
+
 mod change;
 use change::Change;
 
@@ -99,6 +102,9 @@ use cache::{Cache, CachedChangeGraph};
 mod validated_automerge;
 use validated_automerge::ValidatedAutomerge;
 
+pub mod op_log;
+use op_log::ValidatedHistory;
+
 mod identity_storage;
 pub use identity_storage::IdentityStorage;
 
@@ -231,7 +237,6 @@ pub struct CollaborativeObject {
     /// The id of the object
     id: ObjectId,
     /// The schema any changes to this object must respect
-    #[allow(unused)]
     schema: Schema,
 }
 
@@ -260,6 +265,10 @@ impl CollaborativeObject {
     pub fn typename(&self) -> &TypeName {
         &self.typename
     }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
 }
 
 /// Additional information about the change graph of an object
@@ -364,7 +373,8 @@ pub mod error {
 pub struct CreateObjectArgs<'a, R: RefsStorage, P: AsRef<std::path::Path>> {
     /// A valid JSON schema which uses the vocabulary at <https://alexjg.github.io/automerge-jsonschema/spec>
     pub schema: Schema,
-    /// The CRDT history to initialize this object with
+    /// The CRDT history to initialize this object with. The kind of contents
+    /// determines the [`HistoryType`] of the object.
     pub contents: EntryContents,
     /// The typename for this object
     pub typename: TypeName,
@@ -423,8 +433,8 @@ pub fn create_object<R: RefsStorage, P: AsRef<std::path::Path>>(
         schema.clone(),
     )?;
 
-    let mut valid_history = ValidatedAutomerge::new(schema.clone());
-    valid_history.propose_change(contents.as_ref())?;
+    let mut valid_history = ValidatedHistory::new(contents.into(), schema.clone());
+    valid_history.propose_change(contents, &Principal::new(authorizing_identity, author))?;
 
     let init_change = change::Change::create(
         authorizing_identity.content_id(),
@@ -603,7 +613,9 @@ pub fn update<R: RefsStorage, I: IdentityStorage, P: AsRef<std::path::Path>>(
     .load_or_materialize::<error::Update<R::Error>, _>(identity_storage, cache.as_mut(), repo)?
     .ok_or(error::Update::NoSuchObject)?;
 
-    cached.borrow_mut().propose_change(&changes)?;
+    cached
+        .borrow_mut()
+        .propose_change(&changes, &Principal::new(authorizing_identity, author))?;
 
     let change = change::Change::create(
         authorizing_identity.content_id(),
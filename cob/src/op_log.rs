@@ -0,0 +1,153 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Operation logs: a lightweight alternative to Automerge histories.
+//!
+//! Each change of an [`HistoryType::OpLog`] object carries a JSON array of
+//! operations. Operations are opaque to this crate, except that each of them
+//! must be valid with respect to the schema of the object -- i.e. the schema
+//! describes a single operation, not the state of the object. Changes are
+//! signed and causally ordered just like Automerge changes, and are stored
+//! using the same change commit layout (see
+//! `docs/rfc/0662-collaborative-objects.adoc`).
+//!
+//! There is no notion of a materialised state: applications fold the
+//! operations into whatever state they need using [`fold`]. This makes
+//! operation logs well suited for objects which don't need the merge semantics
+//! of a full CRDT, e.g. reactions or CI statuses, as they are much cheaper to
+//! evaluate.
+//!
+//! If the schema carries an authorization [`crate::policy::Policy`], every
+//! operation is checked as if it was setting the values it contains on an
+//! empty document: an operation containing a value at a `delegates` pointer
+//! may only be made by a delegate, and entries at an `authors` pointer must be
+//! authored by the principal making the change.
+
+use std::ops::ControlFlow;
+
+use crate::{
+    policy::Principal,
+    validated_automerge::error::ProposalError,
+    EntryContents,
+    History,
+    HistoryEntry,
+    HistoryType,
+    Schema,
+};
+
+/// Decode the operations carried by `contents`.
+///
+/// Returns `None` if `contents` are not of type [`HistoryType::OpLog`].
+pub fn ops(contents: &EntryContents) -> Option<Result<Vec<serde_json::Value>, serde_json::Error>> {
+    match contents {
+        EntryContents::OpLog(bytes) => Some(serde_json::from_slice(bytes)),
+        EntryContents::Automerge(_) => None,
+    }
+}
+
+/// Fold the operations of `history` in causal order, i.e. the operations of a
+/// change are visited after the operations of all the changes it depends on.
+/// Operations within a change are visited in order.
+///
+/// If `history` is not of type [`HistoryType::OpLog`], `init` is returned.
+pub fn fold<A, F>(history: &History, init: A, mut f: F) -> A
+where
+    F: FnMut(A, &HistoryEntry, serde_json::Value) -> A,
+{
+    history.traverse(init, |acc, entry| {
+        // Changes were validated when the history was evaluated, so decoding
+        // can't fail here
+        let ops = ops(entry.contents())
+            .and_then(Result::ok)
+            .unwrap_or_default();
+        ControlFlow::Continue(ops.into_iter().fold(acc, |acc, op| f(acc, entry, op)))
+    })
+}
+
+/// An operation log which is valid with respect to a schema.
+///
+/// As operations are validated individually, there is no state to keep apart
+/// from the schema.
+#[derive(Debug)]
+pub(crate) struct ValidatedOpLog {
+    schema: Schema,
+}
+
+impl ValidatedOpLog {
+    pub(crate) fn new(schema: Schema) -> Self {
+        Self { schema }
+    }
+
+    pub(crate) fn propose_change(
+        &self,
+        change_bytes: &[u8],
+        principal: &Principal,
+    ) -> Result<(), ProposalError> {
+        let ops: Vec<serde_json::Value> = serde_json::from_slice(change_bytes)
+            .map_err(|e| ProposalError::InvalidChange(Box::new(e)))?;
+        for op in &ops {
+            self.schema
+                .validate_json(op)
+                .map_err(|e| ProposalError::InvalidatesSchema(Box::new(e)))?;
+            self.schema.policy().check_op(principal, op)?;
+        }
+        Ok(())
+    }
+}
+
+/// A history of either [`HistoryType`] which is valid with respect to a schema.
+#[derive(Debug)]
+pub(crate) enum ValidatedHistory {
+    Automerge(crate::ValidatedAutomerge),
+    OpLog(ValidatedOpLog),
+}
+
+impl ValidatedHistory {
+    pub(crate) fn new(history_type: HistoryType, schema: Schema) -> Self {
+        match history_type {
+            HistoryType::Automerge => Self::Automerge(crate::ValidatedAutomerge::new(schema)),
+            HistoryType::OpLog => Self::OpLog(ValidatedOpLog::new(schema)),
+        }
+    }
+
+    /// Propose a change made by `principal`, checking it against both the
+    /// schema and its authorization policy.
+    pub(crate) fn propose_change(
+        &mut self,
+        contents: &EntryContents,
+        principal: &Principal,
+    ) -> Result<(), ProposalError> {
+        match (self, contents) {
+            (Self::Automerge(doc), EntryContents::Automerge(bytes)) => {
+                doc.propose_change(bytes, principal)
+            },
+            (Self::OpLog(log), EntryContents::OpLog(bytes)) => log.propose_change(bytes, principal),
+            (this, contents) => Err(ProposalError::MismatchedHistoryType {
+                expected: this.history_type(),
+                actual: contents.into(),
+            }),
+        }
+    }
+
+    /// Replay a change which is already known to be valid, e.g. because it
+    /// was loaded from the cache.
+    pub(crate) fn replay_change(&mut self, contents: &EntryContents) -> Result<(), ProposalError> {
+        match (self, contents) {
+            (Self::Automerge(doc), EntryContents::Automerge(bytes)) => doc.replay_change(bytes),
+            // Operations are validated individually, so there is nothing to
+            // replay
+            (Self::OpLog(_), EntryContents::OpLog(_)) => Ok(()),
+            (this, contents) => Err(ProposalError::MismatchedHistoryType {
+                expected: this.history_type(),
+                actual: contents.into(),
+            }),
+        }
+    }
+
+    fn history_type(&self) -> HistoryType {
+        match self {
+            Self::Automerge(_) => HistoryType::Automerge,
+            Self::OpLog(_) => HistoryType::OpLog,
+        }
+    }
+}
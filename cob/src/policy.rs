@@ -0,0 +1,214 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Authorization policies restricting which principals may change which parts
+//! of a collaborative object.
+//!
+//! By default, any principal authorized by the [`AuthorizingIdentity`] of an
+//! object may change any part of it. A schema may opt in to a stricter policy
+//! by including an `"authorization"` keyword at its root:
+//!
+//! ```json
+//! "authorization": {
+//!     "delegates": ["/state"],
+//!     "authors": ["/comments"]
+//! }
+//! ```
+//!
+//! * `delegates` lists JSON pointers to values which only delegates of the
+//!   authorizing identity may change after the object was created.
+//! * `authors` lists JSON pointers to arrays of objects with an `"author"`
+//!   property holding the URN of a person. Anyone may append entries authored
+//!   by themselves, but only the author of an entry may change or remove it.
+//!   Entries are identified by their author and their position among the
+//!   entries of the same author, which entries concurrently added by others
+//!   don't disturb.
+//!
+//! The policy is part of the schema, and thus the same for every peer
+//! evaluating the object. It is enforced during evaluation of the change graph,
+//! which means that changes violating it are pruned along with their
+//! descendants.
+
+use std::{collections::BTreeSet, convert::TryFrom};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use link_identities::git::{Urn, VerifiedPerson};
+
+use crate::AuthorizingIdentity;
+
+/// The keyword under which a [`Policy`] is given in a schema.
+pub const KEYWORD: &str = "authorization";
+
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    pub enum Parse {
+        #[error(transparent)]
+        Serde(#[from] serde_json::Error),
+        #[error("invalid JSON pointer `{0}`, pointers must be empty or start with `/`")]
+        InvalidPointer(String),
+    }
+}
+
+/// A change violated the [`Policy`] of an object.
+#[derive(Debug, Error)]
+pub enum Violation {
+    #[error("only delegates may change `{pointer}`")]
+    NotDelegate { pointer: String },
+    #[error("only the author may change `{pointer}`")]
+    NotAuthor { pointer: String },
+    #[error("`{pointer}` must be authored by the principal adding it")]
+    Impersonation { pointer: String },
+}
+
+/// The principal making a change to an object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Principal {
+    pub urn: Urn,
+    /// Whether the principal is a delegate of the authorizing identity.
+    pub is_delegate: bool,
+}
+
+impl Principal {
+    pub fn new(authorizing_identity: &dyn AuthorizingIdentity, person: &VerifiedPerson) -> Self {
+        Self {
+            urn: person.urn(),
+            is_delegate: authorizing_identity.is_delegate(person),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    delegates: Vec<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+}
+
+impl Policy {
+    /// Whether the policy permits everything.
+    pub fn is_empty(&self) -> bool {
+        self.delegates.is_empty() && self.authors.is_empty()
+    }
+
+    /// Check that `principal` may create an object with the initial state
+    /// `doc`.
+    pub fn check_create(
+        &self,
+        principal: &Principal,
+        doc: &serde_json::Value,
+    ) -> Result<(), Violation> {
+        self.check_authors(principal, &serde_json::Value::Null, doc)
+    }
+
+    /// Check that `principal` may change the state of an object from `before`
+    /// to `after`.
+    pub fn check_change(
+        &self,
+        principal: &Principal,
+        before: &serde_json::Value,
+        after: &serde_json::Value,
+    ) -> Result<(), Violation> {
+        if !principal.is_delegate {
+            for pointer in &self.delegates {
+                if before.pointer(pointer) != after.pointer(pointer) {
+                    return Err(Violation::NotDelegate {
+                        pointer: pointer.clone(),
+                    });
+                }
+            }
+        }
+        self.check_authors(principal, before, after)
+    }
+
+    /// Check that `principal` may make the operation `op` of an
+    /// [`crate::op_log`], which is treated like setting the values it contains
+    /// on an empty document.
+    pub fn check_op(&self, principal: &Principal, op: &serde_json::Value) -> Result<(), Violation> {
+        self.check_change(principal, &serde_json::Value::Null, op)
+    }
+
+    fn check_authors(
+        &self,
+        principal: &Principal,
+        before: &serde_json::Value,
+        after: &serde_json::Value,
+    ) -> Result<(), Violation> {
+        let urn = principal.urn.to_string();
+        for pointer in &self.authors {
+            let old = entries(before, pointer);
+            let new = entries(after, pointer);
+            let others = old
+                .iter()
+                .chain(new)
+                .map(author)
+                .filter(|author| *author != Some(&urn))
+                .collect::<BTreeSet<_>>();
+            for other in others {
+                let old = authored_by(old, other);
+                let new = authored_by(new, other);
+                let unchanged = old
+                    .iter()
+                    .zip(&new)
+                    .take_while(|((_, a), (_, b))| a == b)
+                    .count();
+                if unchanged < old.len() {
+                    return Err(Violation::NotAuthor {
+                        pointer: format!("{}/{}", pointer, old[unchanged].0),
+                    });
+                }
+                if unchanged < new.len() {
+                    return Err(Violation::Impersonation {
+                        pointer: format!("{}/{}", pointer, new[unchanged].0),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&serde_json::Value> for Policy {
+    type Error = error::Parse;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let policy = Policy::deserialize(value)?;
+        for pointer in policy.delegates.iter().chain(policy.authors.iter()) {
+            if !(pointer.is_empty() || pointer.starts_with('/')) {
+                return Err(error::Parse::InvalidPointer(pointer.clone()));
+            }
+        }
+        Ok(policy)
+    }
+}
+
+fn entries<'a>(doc: &'a serde_json::Value, pointer: &str) -> &'a [serde_json::Value] {
+    doc.pointer(pointer)
+        .and_then(serde_json::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+/// The `entries` authored by `author`, along with their index.
+fn authored_by<'a>(
+    entries: &'a [serde_json::Value],
+    author: Option<&String>,
+) -> Vec<(usize, &'a serde_json::Value)> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| self::author(entry) == author)
+        .collect()
+}
+
+fn author(entry: &serde_json::Value) -> Option<&String> {
+    match entry.get("author") {
+        Some(serde_json::Value::String(author)) => Some(author),
+        _ => None,
+    }
+}
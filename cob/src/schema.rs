@@ -8,10 +8,13 @@ use std::{
     fmt,
 };
 
+use crate::policy::{self, Policy};
+
 #[derive(Debug)]
 pub struct Schema {
     json: serde_json::Value,
     schema: jsonschema::JSONSchema,
+    policy: Policy,
 }
 
 impl PartialEq for Schema {
@@ -21,10 +24,28 @@ impl PartialEq for Schema {
 }
 
 impl Schema {
+    pub fn json(&self) -> &serde_json::Value {
+        &self.json
+    }
+
     pub fn json_bytes(&self) -> Vec<u8> {
         self.json.to_string().as_bytes().into()
     }
 
+    /// The authorization [`Policy`] given by the schema. If the schema does
+    /// not specify one, the policy is empty.
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Validate a plain JSON `value`, e.g. an operation of an
+    /// [`crate::op_log`].
+    pub fn validate_json(&self, value: &serde_json::Value) -> Result<(), error::ValidationErrors> {
+        self.schema
+            .validate(value)
+            .map_err(error::ValidationErrors::from)
+    }
+
     pub fn validate(&self, doc: &mut automerge::Frontend) -> Result<(), error::ValidationErrors> {
         let value = doc.state().to_json();
         let output = self.schema.apply(&value).basic();
@@ -67,6 +88,7 @@ impl Clone for Schema {
             json: self.json.clone(),
             // The unwrap here is fine as we've already validated the schema during construction
             schema: jsonschema::JSONSchema::compile(&self.json).unwrap(),
+            policy: self.policy.clone(),
         }
     }
 }
@@ -106,6 +128,8 @@ pub mod error {
         InvalidVocabulary,
         #[error("invalid keyword {keyword} at {path}")]
         InvalidKeyword { path: String, keyword: String },
+        #[error("invalid authorization policy: {0}")]
+        Policy(#[from] crate::policy::error::Parse),
     }
 
     #[derive(Debug, Error)]
@@ -130,6 +154,7 @@ impl TryFrom<&serde_json::Value> for Schema {
     type Error = error::Parse;
 
     fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let mut policy = Policy::default();
         if let serde_json::Value::Object(kvs) = value {
             if let Some(serde_json::Value::Object(vocabs)) = kvs.get("$vocabulary") {
                 if vocabs.len() != 1 {
@@ -142,6 +167,9 @@ impl TryFrom<&serde_json::Value> for Schema {
                     return Err(error::Parse::InvalidVocabulary);
                 }
                 validate_keywords(Path::Root, value)?;
+                if let Some(p) = kvs.get(policy::KEYWORD) {
+                    policy = Policy::try_from(p)?;
+                }
             } else {
                 return Err(error::Parse::InvalidVocabulary);
             }
@@ -150,6 +178,7 @@ impl TryFrom<&serde_json::Value> for Schema {
             .map(|s| Schema {
                 json: value.clone(),
                 schema: s,
+                policy,
             })
             .map_err(|e| error::Parse::Validation(e.to_string()))
     }
@@ -432,6 +461,11 @@ impl MetaKeyword {
 fn validate_keywords(path: Path<'_>, value: &serde_json::Value) -> Result<(), error::Parse> {
     if let serde_json::Value::Object(props) = value {
         for (prop, value) in props {
+            // The authorization policy is not a JSON schema keyword, and only
+            // valid at the root
+            if matches!(path, Path::Root) && prop == policy::KEYWORD {
+                continue;
+            }
             if Validator::from_keyword(prop).is_some() {
                 continue;
             }
@@ -3,7 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use super::Schema;
+use super::{policy::Principal, Schema};
 
 use std::convert::TryFrom;
 
@@ -26,11 +26,18 @@ pub mod error {
     #[derive(Debug, Error)]
     pub enum ProposalError {
         #[error("invalid change: {0}")]
-        InvalidChange(Box<dyn std::error::Error>),
+        InvalidChange(Box<dyn std::error::Error + Send + Sync>),
         #[error("invalidates schema: {0}")]
-        InvalidatesSchema(Box<dyn std::error::Error>),
+        InvalidatesSchema(Box<dyn std::error::Error + Send + Sync>),
         #[error("there are missing dependencies: {missing:?}")]
         MissingDependencies { missing: Vec<automerge::ChangeHash> },
+        #[error(transparent)]
+        Unauthorized(#[from] crate::policy::Violation),
+        #[error("expected a change of history type {expected:?}, found {actual:?}")]
+        MismatchedHistoryType {
+            expected: crate::HistoryType,
+            actual: crate::HistoryType,
+        },
     }
 }
 
@@ -78,12 +85,38 @@ impl ValidatedAutomerge {
         }
     }
 
+    /// Propose a change made by `principal`, checking it against both the
+    /// schema and its authorization policy.
     pub(crate) fn propose_change(
         &mut self,
         change_bytes: &[u8],
+        principal: &Principal,
+    ) -> Result<(), error::ProposalError> {
+        self.apply_change(change_bytes, Some(principal))
+    }
+
+    /// Replay a change which is already known to be valid, e.g. because it
+    /// was loaded from the cache.
+    pub(crate) fn replay_change(
+        &mut self,
+        change_bytes: &[u8],
+    ) -> Result<(), error::ProposalError> {
+        self.apply_change(change_bytes, None)
+    }
+
+    fn apply_change(
+        &mut self,
+        change_bytes: &[u8],
+        principal: Option<&Principal>,
     ) -> Result<(), error::ProposalError> {
         let change = automerge::Change::try_from(change_bytes)
             .map_err(|e| error::ProposalError::InvalidChange(Box::new(e)))?;
+        // Only materialise the state if there is a policy to check it against
+        let authorize = principal.filter(|_| !self.schema.policy().is_empty());
+        let before = match authorize {
+            Some(_) if !self.valid_history.is_empty() => Some(self.frontend.state().to_json()),
+            _ => None,
+        };
         let old_backend = self.backend.clone();
         let patch = self
             .backend
@@ -92,18 +125,26 @@ impl ValidatedAutomerge {
         // This can only go wrong if the patch is delivered out of order, which we
         // promise we aren't doing
         self.frontend.apply_patch(patch).unwrap();
-        let validation_error = self.schema.validate(&mut self.frontend).err();
-        match validation_error {
-            None => {
-                self.valid_history.extend(change_bytes);
-            },
-            Some(e) => {
-                let value = self.frontend.state();
-                tracing::debug!(invalid_json=?value.to_json().to_string(), "change invalidated schema");
+        if let Err(e) = self.schema.validate(&mut self.frontend) {
+            let value = self.frontend.state();
+            tracing::debug!(invalid_json=?value.to_json().to_string(), "change invalidated schema");
+            self.reset(old_backend);
+            return Err(error::ProposalError::InvalidatesSchema(Box::new(e)));
+        }
+        if let Some(principal) = authorize {
+            let after = self.frontend.state().to_json();
+            let policy = self.schema.policy();
+            let authorized = match &before {
+                None => policy.check_create(principal, &after),
+                Some(before) => policy.check_change(principal, before, &after),
+            };
+            if let Err(violation) = authorized {
+                tracing::debug!(%violation, "change violated authorization policy");
                 self.reset(old_backend);
-                return Err(error::ProposalError::InvalidatesSchema(Box::new(e)));
-            },
+                return Err(violation.into());
+            }
         }
+        self.valid_history.extend(change_bytes);
         let missing_deps = self.backend.get_missing_deps(&[]);
         if !missing_deps.is_empty() {
             self.reset(old_backend);
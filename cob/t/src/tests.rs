@@ -5,6 +5,7 @@
 
 mod cache;
 mod cached_change_graph;
+mod policy;
 mod schema;
 
 use cob::TypeName;
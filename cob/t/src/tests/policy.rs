@@ -0,0 +1,178 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryFrom;
+
+use cob::policy::{Policy, Principal, Violation};
+use link_identities::git::Urn;
+use serde_json::json;
+
+fn urn(name: &str) -> Urn {
+    Urn::new(
+        git2::Oid::hash_object(git2::ObjectType::Blob, name.as_bytes())
+            .unwrap()
+            .into(),
+    )
+}
+
+fn principal(name: &str, is_delegate: bool) -> Principal {
+    Principal {
+        urn: urn(name),
+        is_delegate,
+    }
+}
+
+fn policy() -> Policy {
+    Policy::try_from(&json!({
+        "delegates": ["/state"],
+        "authors": ["/comments"]
+    }))
+    .unwrap()
+}
+
+fn comment(author: &str, body: &str) -> serde_json::Value {
+    json!({ "author": urn(author).to_string(), "body": body })
+}
+
+#[test]
+fn schema_with_policy_can_be_parsed() {
+    let schema = json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true,
+        },
+        "type": "object",
+        "properties": {
+            "state": {"type": "string"}
+        },
+        "authorization": {
+            "delegates": ["/state"]
+        }
+    });
+    let schema = cob::Schema::try_from(&schema).unwrap();
+    assert!(!schema.policy().is_empty());
+}
+
+#[test]
+fn schema_without_policy_permits_everything() {
+    let schema = json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true,
+        },
+        "type": "object"
+    });
+    assert!(cob::Schema::try_from(&schema).unwrap().policy().is_empty());
+}
+
+#[test]
+fn nested_policy_fails() {
+    let schema = json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true,
+        },
+        "type": "object",
+        "properties": {
+            "state": {
+                "type": "string",
+                "authorization": { "delegates": [""] }
+            }
+        }
+    });
+    assert!(matches!(
+        cob::Schema::try_from(&schema),
+        Err(cob::schema::error::Parse::InvalidKeyword { .. })
+    ));
+}
+
+#[test]
+fn invalid_pointer_fails() {
+    assert!(Policy::try_from(&json!({ "delegates": ["state"] })).is_err());
+    assert!(Policy::try_from(&json!({ "owners": ["/state"] })).is_err());
+}
+
+#[test]
+fn anyone_can_create() {
+    let doc = json!({ "state": "open", "comments": [comment("bob", "first")] });
+    assert!(policy()
+        .check_create(&principal("bob", false), &doc)
+        .is_ok());
+}
+
+#[test]
+fn only_delegates_change_state() {
+    let before = json!({ "state": "open", "comments": [] });
+    let after = json!({ "state": "closed", "comments": [] });
+    assert!(matches!(
+        policy().check_change(&principal("bob", false), &before, &after),
+        Err(Violation::NotDelegate { .. })
+    ));
+    assert!(policy()
+        .check_change(&principal("alice", true), &before, &after)
+        .is_ok());
+}
+
+#[test]
+fn anyone_appends_own_entries() {
+    let before = json!({ "state": "open", "comments": [comment("alice", "first")] });
+    let after = json!({
+        "state": "open",
+        "comments": [comment("alice", "first"), comment("bob", "second")]
+    });
+    assert!(policy()
+        .check_change(&principal("bob", false), &before, &after)
+        .is_ok());
+    assert!(matches!(
+        policy().check_change(&principal("eve", false), &before, &after),
+        Err(Violation::Impersonation { .. })
+    ));
+}
+
+#[test]
+fn only_authors_edit_entries() {
+    let before = json!({ "comments": [comment("alice", "first")] });
+    let edited = json!({ "comments": [comment("alice", "edited")] });
+    let removed = json!({ "comments": [] });
+    let stolen = json!({ "comments": [comment("bob", "first")] });
+
+    for after in [&edited, &removed, &stolen] {
+        assert!(matches!(
+            policy().check_change(&principal("bob", true), &before, after),
+            Err(Violation::NotAuthor { .. })
+        ));
+    }
+    assert!(policy()
+        .check_change(&principal("alice", false), &before, &edited)
+        .is_ok());
+    assert!(policy()
+        .check_change(&principal("alice", false), &before, &removed)
+        .is_ok());
+    assert!(matches!(
+        policy().check_change(&principal("alice", false), &before, &stolen),
+        Err(Violation::Impersonation { .. })
+    ));
+}
+
+#[test]
+fn concurrent_appends_keep_entries() {
+    // Bob's entry was appended concurrently to Alice's, and ends up after it
+    let before = json!({
+        "comments": [comment("alice", "first"), comment("bob", "second")]
+    });
+    let after = json!({
+        "comments": [
+            comment("alice", "first"),
+            comment("alice", "third"),
+            comment("bob", "second"),
+        ]
+    });
+    assert!(policy()
+        .check_change(&principal("alice", false), &before, &after)
+        .is_ok());
+
+    let removed = json!({
+        "comments": [comment("alice", "first"), comment("alice", "third")]
+    });
+    assert!(matches!(
+        policy().check_change(&principal("alice", false), &before, &removed),
+        Err(Violation::NotAuthor { .. })
+    ));
+}
@@ -13,12 +13,14 @@ use crate::git::{
 use std::{collections::HashMap, convert::TryFrom, str::FromStr};
 
 pub use cob::{
+    op_log,
     AuthorizingIdentity,
     ChangeGraphInfo,
     CollaborativeObject,
     CreateObjectArgs,
     EntryContents,
     History,
+    HistoryType,
     IdentityStorage,
     ObjectId,
     ObjectRefs,
@@ -29,7 +31,12 @@ pub use cob::{
 use link_crypto::BoxedSigner;
 use link_identities::git::{SomeIdentity, Urn};
 
+mod doc;
+pub mod issue;
+pub mod patch;
+
 pub mod error {
+    pub use super::doc::Error as Doc;
     use super::RefsError;
     use crate::git::identities::Error as IdentitiesError;
     use cob::error::SchemaParse;
@@ -220,6 +227,28 @@ impl<'a> CollaborativeObjects<'a> {
     }
 }
 
+/// Materialise the current state of `history` as JSON.
+///
+/// The state of an [`HistoryType::OpLog`] history is up to the application to
+/// determine, so the operations are returned as a JSON array, in causal order.
+///
+/// Note that `history` is assumed to have been validated against the schema of
+/// its object, as is the case for the [`CollaborativeObject`]s returned by
+/// [`CollaborativeObjects`].
+pub fn materialise(history: &History) -> Result<serde_json::Value, error::Doc> {
+    match history.history_type() {
+        HistoryType::Automerge => Ok(doc::Doc::load(history)?.to_json()),
+        HistoryType::OpLog => Ok(serde_json::Value::Array(op_log::fold(
+            history,
+            Vec::new(),
+            |mut ops, _, op| {
+                ops.push(op);
+                ops
+            },
+        ))),
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum RefsError {
     #[error(transparent)]
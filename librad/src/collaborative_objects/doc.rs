@@ -0,0 +1,173 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Helpers for building Automerge changes on top of the [`History`] of a
+//! collaborative object, for use by the typed object APIs.
+
+use std::ops::ControlFlow;
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+use super::{EntryContents, History};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Backend(#[from] automerge::BackendError),
+    #[error(transparent)]
+    Frontend(#[from] automerge::FrontendError),
+    #[error(transparent)]
+    InvalidPatch(#[from] automerge::InvalidPatch),
+    #[error(transparent)]
+    InvalidChange(#[from] automerge::InvalidChangeRequest),
+    #[error("invalid change in history: {0}")]
+    Decode(String),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("the change did not modify the document")]
+    Empty,
+    #[error("the document root must be an object")]
+    NotAnObject,
+    #[error("the history is not an Automerge history")]
+    NotAutomerge,
+}
+
+/// The materialised state of a collaborative object, along with the means to
+/// extend it.
+pub(super) struct Doc {
+    frontend: automerge::Frontend,
+    backend: automerge::Backend,
+}
+
+impl Doc {
+    /// An empty document.
+    pub fn new() -> Self {
+        Self {
+            frontend: automerge::Frontend::new(),
+            backend: automerge::Backend::new(),
+        }
+    }
+
+    /// Replay `history`.
+    ///
+    /// Note that `history` is assumed to have been validated against the schema
+    /// of the object already.
+    pub fn load(history: &History) -> Result<Self, Error> {
+        let backend = history.traverse(Ok(automerge::Backend::new()), |backend, entry| {
+            let res = backend.and_then(|mut backend| match entry.contents() {
+                EntryContents::Automerge(bytes) => {
+                    let change = automerge::Change::from_bytes(bytes.clone())
+                        .map_err(|e| Error::Decode(e.to_string()))?;
+                    backend.apply_changes(vec![change])?;
+                    Ok(backend)
+                },
+                EntryContents::OpLog(_) => Err(Error::NotAutomerge),
+            });
+            match res {
+                Ok(backend) => ControlFlow::Continue(Ok(backend)),
+                Err(e) => ControlFlow::Break(Err(e)),
+            }
+        })?;
+        let mut frontend = automerge::Frontend::new();
+        frontend.apply_patch(backend.get_patch()?)?;
+
+        Ok(Self { frontend, backend })
+    }
+
+    /// The current state of the document as JSON.
+    pub fn to_json(&self) -> serde_json::Value {
+        self.frontend.state().to_json()
+    }
+
+    /// The current state of the document, deserialised to `T`.
+    pub fn get<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(serde_json::from_value(self.to_json())?)
+    }
+
+    /// Apply `changes` to the document, returning the [`EntryContents`] to
+    /// store in the object's history.
+    pub fn change<I>(&mut self, changes: I) -> Result<EntryContents, Error>
+    where
+        I: IntoIterator<Item = automerge::LocalChange>,
+    {
+        let (_, change) = self
+            .frontend
+            .change::<_, _, automerge::InvalidChangeRequest>(None, |d| {
+                for change in changes {
+                    d.add_change(change)?;
+                }
+                Ok(())
+            })?;
+        let change = change.ok_or(Error::Empty)?;
+        let (_, change) = self.backend.apply_local_change(change)?;
+        Ok(EntryContents::Automerge(change.raw_bytes().to_vec()))
+    }
+}
+
+/// Set `key` of the root object to `value`.
+pub(super) fn set<T>(key: &str, value: &T) -> Result<automerge::LocalChange, Error>
+where
+    T: Serialize,
+{
+    set_path(automerge::Path::root().key(key), value)
+}
+
+/// Set the value at `path` to `value`.
+pub(super) fn set_path<T>(path: automerge::Path, value: &T) -> Result<automerge::LocalChange, Error>
+where
+    T: Serialize,
+{
+    Ok(automerge::LocalChange::set(path, value_of(value)?))
+}
+
+/// Append `value` to the list at `key` of the root object, which currently
+/// has `len` elements.
+pub(super) fn push<T>(key: &str, len: usize, value: &T) -> Result<automerge::LocalChange, Error>
+where
+    T: Serialize,
+{
+    Ok(automerge::LocalChange::insert(
+        automerge::Path::root().key(key).index(len as u32),
+        value_of(value)?,
+    ))
+}
+
+/// Remove the elements at `indices` from the list at `key` of the root object.
+pub(super) fn remove<I>(key: &str, indices: I) -> Vec<automerge::LocalChange>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut indices = indices.into_iter().collect::<Vec<_>>();
+    // Remove from the back, so earlier removals don't shift later indices
+    indices.sort_unstable_by(|a, b| b.cmp(a));
+    indices.dedup();
+    indices
+        .into_iter()
+        .map(|ix| automerge::LocalChange::delete(automerge::Path::root().key(key).index(ix as u32)))
+        .collect()
+}
+
+/// Set each field of `value`, which must serialise to a JSON object, on the
+/// root object.
+pub(super) fn init<T>(value: &T) -> Result<Vec<automerge::LocalChange>, Error>
+where
+    T: Serialize,
+{
+    match serde_json::to_value(value)? {
+        serde_json::Value::Object(fields) => {
+            fields.iter().map(|(key, value)| set(key, value)).collect()
+        },
+        _ => Err(Error::NotAnObject),
+    }
+}
+
+fn value_of<T>(value: &T) -> Result<automerge::Value, Error>
+where
+    T: Serialize,
+{
+    Ok(automerge::Value::from_json(&serde_json::to_value(value)?))
+}
@@ -0,0 +1,560 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Issues: tasks and bug reports tracked alongside a project, as a
+//! collaborative object of type [`struct@TYPENAME`].
+//!
+//! An [`Issue`] has a title and description, a set of free-form labels, a set
+//! of assignees, and a list of [`Comment`]s. Comments form threads: a comment
+//! may be a reply to an earlier comment, which is referred to by its
+//! [`CommentId`].
+//!
+//! Only delegates of the project may change the state, labels, or assignees of
+//! an issue once it was opened, and only the author of a comment may change
+//! it, see [`cob::policy`].
+//!
+//! The document layout is given by [`struct@SCHEMA`]. Documents carry an
+//! [`Issue::version`], so that later versions of the layout can be told apart
+//! from this one.
+//! Objects created with a different schema are refused, and skipped when
+//! listing issues.
+
+use std::{fmt, str::FromStr};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{
+    doc::{self, Doc},
+    CollaborativeObject,
+    CollaborativeObjects,
+    NewObjectSpec,
+    ObjectId,
+    TypeName,
+    UpdateObjectSpec,
+};
+use crate::git::{identities::local::LocalIdentity, Urn};
+
+/// The [`TypeName`] of issue objects.
+pub static TYPENAME: Lazy<TypeName> =
+    Lazy::new(|| TypeName::from_str("xyz.radicle.issue").unwrap());
+
+/// The version of the document layout created by this module.
+pub const VERSION: u64 = 1;
+
+/// The JSON schema of version [`VERSION`] of issue objects.
+pub static SCHEMA: Lazy<serde_json::Value> = Lazy::new(|| {
+    json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true
+        },
+        "$defs": {
+            "urn": {
+                "description": "The radicle URN of a person",
+                "type": "string"
+            },
+            "comment_id": {
+                "description": "The randomly chosen id of a comment",
+                "type": "string",
+                "format": "uuid"
+            }
+        },
+        "type": "object",
+        "properties": {
+            "version": {
+                "type": "integer",
+                "minimum": 1
+            },
+            "title": {
+                "type": "string"
+            },
+            "description": {
+                "type": "string"
+            },
+            "author": {
+                "$ref": "#/$defs/urn"
+            },
+            "state": {
+                "type": "string",
+                "enum": ["open", "closed"]
+            },
+            "labels": {
+                "type": "array",
+                "items": {
+                    "type": "string"
+                }
+            },
+            "assignees": {
+                "type": "array",
+                "items": {
+                    "$ref": "#/$defs/urn"
+                }
+            },
+            "comments": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "$ref": "#/$defs/comment_id" },
+                        "author": { "$ref": "#/$defs/urn" },
+                        "body": { "type": "string" },
+                        "reply_to": {
+                            "description": "The id of the comment this is a reply to",
+                            "$ref": "#/$defs/comment_id"
+                        }
+                    },
+                    "required": ["id", "author", "body"]
+                }
+            }
+        },
+        "required": [
+            "version",
+            "title",
+            "description",
+            "author",
+            "state",
+            "labels",
+            "assignees",
+            "comments"
+        ],
+        "authorization": {
+            "delegates": ["/state", "/labels", "/assignees"],
+            "authors": ["/comments"]
+        }
+    })
+});
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issue {
+    /// The version of the document layout.
+    pub version: u64,
+    pub title: String,
+    pub description: String,
+    pub author: Urn,
+    pub state: State,
+    pub labels: Vec<String>,
+    /// The persons assigned to work on the issue.
+    pub assignees: Vec<Urn>,
+    /// The comments on the issue, oldest first.
+    pub comments: Vec<Comment>,
+}
+
+impl Issue {
+    /// The comment with the given `id`, if any.
+    pub fn comment(&self, id: &CommentId) -> Option<&Comment> {
+        self.comments.iter().find(|comment| &comment.id == id)
+    }
+
+    /// The comments which are not a reply to another comment.
+    pub fn threads(&self) -> impl Iterator<Item = &Comment> + '_ {
+        self.comments
+            .iter()
+            .filter(|comment| comment.reply_to.is_none())
+    }
+
+    /// The direct replies to the comment `id`.
+    pub fn replies(&self, id: CommentId) -> impl Iterator<Item = &Comment> + '_ {
+        self.comments
+            .iter()
+            .filter(move |reply| reply.reply_to == Some(id))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Open,
+    Closed,
+}
+
+/// The identifier of a [`Comment`].
+///
+/// Comments may be added concurrently by different peers, so their position in
+/// [`Issue::comments`] is not stable. Instead, each comment is identified by a
+/// random id chosen by its author.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CommentId(Uuid);
+
+impl CommentId {
+    fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for CommentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for CommentId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::from_str(s).map(Self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: CommentId,
+    pub author: Urn,
+    pub body: String,
+    /// The id of the comment this comment is a reply to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<CommentId>,
+}
+
+/// The data required to open a new [`Issue`].
+pub struct NewIssue {
+    pub title: String,
+    pub description: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<Urn>,
+}
+
+pub mod error {
+    use thiserror::Error;
+
+    use super::{doc, CommentId, ObjectId};
+    use crate::collaborative_objects::error as cob;
+
+    #[derive(Debug, Error)]
+    pub enum Create {
+        #[error(transparent)]
+        Cob(#[from] cob::Create),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+    }
+
+    #[derive(Debug, Error)]
+    pub enum Retrieve {
+        #[error(transparent)]
+        Cob(#[from] cob::Retrieve),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+        #[error("unsupported issue version {0}")]
+        UnsupportedVersion(u64),
+        #[error("issue {0} does not conform to the issue schema")]
+        SchemaMismatch(ObjectId),
+    }
+
+    #[derive(Debug, Error)]
+    pub enum Update {
+        #[error("issue {0} not found")]
+        NotFound(ObjectId),
+        #[error("comment {0} not found")]
+        NoSuchComment(CommentId),
+        #[error(transparent)]
+        Retrieve(#[from] Retrieve),
+        #[error(transparent)]
+        Cob(#[from] cob::Update),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+    }
+
+    impl From<cob::Retrieve> for Update {
+        fn from(e: cob::Retrieve) -> Self {
+            Self::Retrieve(e.into())
+        }
+    }
+}
+
+/// Typed access to the [`Issue`]s of a project.
+pub struct Issues<'a> {
+    cobs: CollaborativeObjects<'a>,
+}
+
+impl<'a> Issues<'a> {
+    pub fn new(cobs: CollaborativeObjects<'a>) -> Self {
+        Self { cobs }
+    }
+
+    /// Open a new issue within `project`, authored by `whoami`.
+    pub fn create(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        new: NewIssue,
+    ) -> Result<(ObjectId, Issue), error::Create> {
+        let issue = Issue {
+            version: VERSION,
+            title: new.title,
+            description: new.description,
+            author: whoami.urn(),
+            state: State::Open,
+            labels: dedup(new.labels),
+            assignees: dedup(new.assignees),
+            comments: vec![],
+        };
+
+        let mut doc = Doc::new();
+        let history = doc.change(doc::init(&issue)?)?;
+        let object = self.cobs.create(
+            whoami,
+            project,
+            NewObjectSpec {
+                schema_json: SCHEMA.clone(),
+                history,
+                typename: TYPENAME.clone(),
+                message: Some(format!("open issue: {}", issue.title)),
+            },
+        )?;
+
+        Ok((*object.id(), issue))
+    }
+
+    /// Get the issue `id` within `project`.
+    pub fn get(&self, project: &Urn, id: &ObjectId) -> Result<Option<Issue>, error::Retrieve> {
+        self.cobs
+            .retrieve(project, &TYPENAME, id)?
+            .map(|object| load_object(&object))
+            .transpose()
+    }
+
+    /// List all issues within `project`.
+    pub fn list(&self, project: &Urn) -> Result<Vec<(ObjectId, Issue)>, error::Retrieve> {
+        self.cobs
+            .list(project, &TYPENAME)?
+            .into_iter()
+            .filter_map(|object| match load_object(&object) {
+                Err(error::Retrieve::SchemaMismatch(id)) => {
+                    tracing::warn!(%id, "skipping issue with unknown schema");
+                    None
+                },
+                res => Some(res.map(|issue| (*object.id(), issue))),
+            })
+            .collect()
+    }
+
+    /// Comment on the issue `id`, optionally replying to the comment
+    /// `reply_to`.
+    ///
+    /// The new comment is the last of [`Issue::comments`] of the returned
+    /// issue.
+    pub fn comment(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        body: String,
+        reply_to: Option<CommentId>,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, "comment on issue", |issue| {
+            if let Some(parent) = reply_to {
+                if issue.comment(&parent).is_none() {
+                    return Err(error::Update::NoSuchComment(parent));
+                }
+            }
+            let comment = Comment {
+                id: CommentId::new(),
+                author: whoami.urn(),
+                body,
+                reply_to,
+            };
+            Ok(vec![doc::push("comments", issue.comments.len(), &comment)?])
+        })
+    }
+
+    /// Add `labels` to the issue `id`. Labels the issue already carries are
+    /// ignored.
+    pub fn label(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        labels: Vec<String>,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, "label issue", |issue| {
+            add("labels", &issue.labels, labels)
+        })
+    }
+
+    /// Remove `labels` from the issue `id`. Labels the issue doesn't carry are
+    /// ignored.
+    pub fn unlabel(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        labels: Vec<String>,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, "unlabel issue", |issue| {
+            Ok(remove("labels", &issue.labels, &labels))
+        })
+    }
+
+    /// Assign the persons `assignees` to the issue `id`. Persons already
+    /// assigned are ignored.
+    pub fn assign(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        assignees: Vec<Urn>,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, "assign issue", |issue| {
+            add("assignees", &issue.assignees, assignees)
+        })
+    }
+
+    /// Unassign the persons `assignees` from the issue `id`. Persons not
+    /// assigned are ignored.
+    pub fn unassign(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        assignees: Vec<Urn>,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, "unassign issue", |issue| {
+            Ok(remove("assignees", &issue.assignees, &assignees))
+        })
+    }
+
+    /// Close the issue `id`.
+    pub fn close(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+    ) -> Result<Issue, error::Update> {
+        self.set_state(whoami, project, id, State::Closed, "close issue")
+    }
+
+    /// Reopen the issue `id`.
+    pub fn reopen(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+    ) -> Result<Issue, error::Update> {
+        self.set_state(whoami, project, id, State::Open, "reopen issue")
+    }
+
+    fn set_state(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        state: State,
+        message: &str,
+    ) -> Result<Issue, error::Update> {
+        self.update(whoami, project, id, message, |issue| {
+            if issue.state == state {
+                Ok(vec![])
+            } else {
+                Ok(vec![doc::set("state", &state)?])
+            }
+        })
+    }
+
+    /// Apply the changes computed by `f` to the issue `id`. If `f` yields no
+    /// changes, the issue is returned as is and no update is recorded.
+    fn update<F>(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        message: &str,
+        f: F,
+    ) -> Result<Issue, error::Update>
+    where
+        F: FnOnce(&Issue) -> Result<Vec<automerge::LocalChange>, error::Update>,
+    {
+        let object = self
+            .cobs
+            .retrieve(project, &TYPENAME, id)?
+            .ok_or(error::Update::NotFound(*id))?;
+        check_schema(&object)?;
+        let mut doc = Doc::load(object.history())?;
+        let issue = load(&doc)?;
+        let changes = f(&issue)?;
+        if changes.is_empty() {
+            return Ok(issue);
+        }
+        let changes = doc.change(changes)?;
+        self.cobs.update(
+            whoami,
+            project,
+            UpdateObjectSpec {
+                object_id: *id,
+                typename: TYPENAME.clone(),
+                message: Some(message.to_owned()),
+                changes,
+            },
+        )?;
+
+        Ok(load(&doc)?)
+    }
+}
+
+/// Load the issue `object`, if it was created with our [`struct@SCHEMA`].
+fn load_object(object: &CollaborativeObject) -> Result<Issue, error::Retrieve> {
+    check_schema(object)?;
+    load(&Doc::load(object.history())?)
+}
+
+/// Objects of our [`struct@TYPENAME`] created with a different schema may not
+/// be laid out as we expect, so they are refused.
+fn check_schema(object: &CollaborativeObject) -> Result<(), error::Retrieve> {
+    if object.schema().json() == &*SCHEMA {
+        Ok(())
+    } else {
+        Err(error::Retrieve::SchemaMismatch(*object.id()))
+    }
+}
+
+fn load(doc: &Doc) -> Result<Issue, error::Retrieve> {
+    let issue = doc.get::<Issue>()?;
+    if issue.version > VERSION {
+        return Err(error::Retrieve::UnsupportedVersion(issue.version));
+    }
+    Ok(issue)
+}
+
+fn dedup<T: PartialEq>(xs: Vec<T>) -> Vec<T> {
+    let mut out = Vec::with_capacity(xs.len());
+    for x in xs {
+        if !out.contains(&x) {
+            out.push(x);
+        }
+    }
+    out
+}
+
+/// Append the elements of `new` not already in `current` to the list at `key`.
+fn add<T>(
+    key: &str,
+    current: &[T],
+    new: Vec<T>,
+) -> Result<Vec<automerge::LocalChange>, error::Update>
+where
+    T: PartialEq + Serialize,
+{
+    dedup(new)
+        .into_iter()
+        .filter(|x| !current.contains(x))
+        .enumerate()
+        .map(|(i, x)| Ok(doc::push(key, current.len() + i, &x)?))
+        .collect()
+}
+
+/// Remove the elements of `current` which are in `gone` from the list at
+/// `key`.
+fn remove<T>(key: &str, current: &[T], gone: &[T]) -> Vec<automerge::LocalChange>
+where
+    T: PartialEq,
+{
+    doc::remove(
+        key,
+        current
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| gone.contains(x))
+            .map(|(ix, _)| ix),
+    )
+}
@@ -0,0 +1,578 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Patches: proposals to merge a series of commits into a project, as a
+//! collaborative object of type [`struct@TYPENAME`].
+//!
+//! A [`Patch`] consists of one or more [`Revision`]s, each pointing to the
+//! commit to be merged into the patch's target branch. Revisions can be
+//! reviewed, and commented on -- either as a whole, or anchored to a line of a
+//! file.
+//!
+//! Only delegates of the project may merge or close a patch, and only the
+//! author of a revision, review, or comment may change it, see
+//! [`cob::policy`].
+//!
+//! The document layout is given by [`struct@SCHEMA`]. Documents carry a
+//! [`Patch::version`], so that later versions of the layout can be told apart
+//! from this one.
+//! Objects created with a different schema are refused, and skipped when
+//! listing patches.
+
+use std::str::FromStr as _;
+
+use git_ext::Oid;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{
+    doc::{self, Doc},
+    CollaborativeObject,
+    CollaborativeObjects,
+    NewObjectSpec,
+    ObjectId,
+    TypeName,
+    UpdateObjectSpec,
+};
+use crate::git::{identities::local::LocalIdentity, Urn};
+
+/// The [`TypeName`] of patch objects.
+pub static TYPENAME: Lazy<TypeName> =
+    Lazy::new(|| TypeName::from_str("xyz.radicle.patch").unwrap());
+
+/// The version of the document layout created by this module.
+pub const VERSION: u64 = 1;
+
+/// The JSON schema of version [`VERSION`] of patch objects.
+pub static SCHEMA: Lazy<serde_json::Value> = Lazy::new(|| {
+    json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true
+        },
+        "$defs": {
+            "oid": {
+                "type": "string"
+            },
+            "urn": {
+                "description": "The radicle URN of a person",
+                "type": "string"
+            }
+        },
+        "type": "object",
+        "properties": {
+            "version": {
+                "type": "integer",
+                "minimum": 1
+            },
+            "title": {
+                "type": "string"
+            },
+            "description": {
+                "type": "string"
+            },
+            "author": {
+                "$ref": "#/$defs/urn"
+            },
+            "target": {
+                "description": "The name of the branch the patch is to be merged into",
+                "type": "string"
+            },
+            "state": {
+                "type": "string",
+                "enum": ["open", "closed", "merged"]
+            },
+            "revisions": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "commit": { "$ref": "#/$defs/oid" },
+                        "base": { "$ref": "#/$defs/oid" },
+                        "author": { "$ref": "#/$defs/urn" },
+                        "description": { "type": "string" }
+                    },
+                    "required": ["commit", "author", "description"]
+                }
+            },
+            "reviews": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "revision": { "$ref": "#/$defs/oid" },
+                        "author": { "$ref": "#/$defs/urn" },
+                        "verdict": {
+                            "type": "string",
+                            "enum": ["accept", "reject", "comment"]
+                        },
+                        "comment": { "type": "string" }
+                    },
+                    "required": ["revision", "author", "verdict"]
+                }
+            },
+            "comments": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "author": { "$ref": "#/$defs/urn" },
+                        "body": { "type": "string" },
+                        "anchor": {
+                            "type": "object",
+                            "properties": {
+                                "revision": { "$ref": "#/$defs/oid" },
+                                "path": { "type": "string" },
+                                "line": {
+                                    "type": "integer",
+                                    "minimum": 1
+                                }
+                            },
+                            "required": ["revision", "path", "line"]
+                        }
+                    },
+                    "required": ["author", "body"]
+                }
+            },
+            "merged": {
+                "type": "object",
+                "properties": {
+                    "revision": { "$ref": "#/$defs/oid" },
+                    "author": { "$ref": "#/$defs/urn" }
+                },
+                "required": ["revision", "author"]
+            }
+        },
+        "required": [
+            "version",
+            "title",
+            "description",
+            "author",
+            "target",
+            "state",
+            "revisions",
+            "reviews",
+            "comments"
+        ],
+        "authorization": {
+            "delegates": ["/state", "/merged"],
+            "authors": ["/revisions", "/reviews", "/comments"]
+        }
+    })
+});
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Patch {
+    /// The version of the document layout.
+    pub version: u64,
+    pub title: String,
+    pub description: String,
+    pub author: Urn,
+    /// The name of the branch the patch is to be merged into.
+    pub target: String,
+    pub state: State,
+    /// The revisions of the patch, oldest first.
+    pub revisions: Vec<Revision>,
+    pub reviews: Vec<Review>,
+    pub comments: Vec<Comment>,
+    /// The revision which got merged, if the [`State`] is
+    /// [`State::Merged`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merged: Option<Merge>,
+}
+
+impl Patch {
+    /// The most recent revision.
+    pub fn latest(&self) -> Option<&Revision> {
+        self.revisions.last()
+    }
+
+    /// Find the revision pointing to `commit`.
+    pub fn revision(&self, commit: &Oid) -> Option<&Revision> {
+        self.revisions.iter().find(|rev| &rev.commit == commit)
+    }
+
+    /// The reviews of the revision pointing to `commit`.
+    pub fn reviews_of<'a>(&'a self, commit: &'a Oid) -> impl Iterator<Item = &'a Review> + 'a {
+        self.reviews.iter().filter(move |r| &r.revision == commit)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Open,
+    Closed,
+    Merged,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revision {
+    /// The commit proposed to be merged.
+    pub commit: Oid,
+    /// The commit `commit` is based on, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<Oid>,
+    pub author: Urn,
+    /// What changed compared to the previous revision.
+    pub description: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Accept,
+    Reject,
+    Comment,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Review {
+    /// The commit of the [`Revision`] under review.
+    pub revision: Oid,
+    pub author: Urn,
+    pub verdict: Verdict,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub author: Urn,
+    pub body: String,
+    /// Where in the code the comment applies, if anywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<Anchor>,
+}
+
+/// The location of an inline comment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Anchor {
+    /// The commit of the [`Revision`] the comment refers to.
+    pub revision: Oid,
+    /// Path of the file, relative to the repository root.
+    pub path: String,
+    /// Line number, starting at 1.
+    pub line: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Merge {
+    /// The commit of the [`Revision`] which got merged.
+    pub revision: Oid,
+    pub author: Urn,
+}
+
+/// The data required to open a new [`Patch`].
+pub struct NewPatch {
+    pub title: String,
+    pub description: String,
+    /// The name of the branch the patch is to be merged into.
+    pub target: String,
+    /// The commit of the initial [`Revision`].
+    pub commit: Oid,
+    /// The commit `commit` is based on, if known.
+    pub base: Option<Oid>,
+}
+
+pub mod error {
+    use thiserror::Error;
+
+    use super::{doc, ObjectId, Oid};
+    use crate::collaborative_objects::error as cob;
+
+    #[derive(Debug, Error)]
+    pub enum Create {
+        #[error(transparent)]
+        Cob(#[from] cob::Create),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+    }
+
+    #[derive(Debug, Error)]
+    pub enum Retrieve {
+        #[error(transparent)]
+        Cob(#[from] cob::Retrieve),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+        #[error("unsupported patch version {0}")]
+        UnsupportedVersion(u64),
+        #[error("patch {0} does not conform to the patch schema")]
+        SchemaMismatch(ObjectId),
+    }
+
+    #[derive(Debug, Error)]
+    pub enum Update {
+        #[error("patch {0} not found")]
+        NotFound(ObjectId),
+        #[error("revision {0} not found")]
+        NoSuchRevision(Oid),
+        #[error("revision {0} already exists")]
+        RevisionExists(Oid),
+        #[error("patch has no revisions")]
+        NoRevisions,
+        #[error(transparent)]
+        Retrieve(#[from] Retrieve),
+        #[error(transparent)]
+        Cob(#[from] cob::Update),
+        #[error(transparent)]
+        Doc(#[from] doc::Error),
+    }
+
+    impl From<cob::Retrieve> for Update {
+        fn from(e: cob::Retrieve) -> Self {
+            Self::Retrieve(e.into())
+        }
+    }
+}
+
+/// Typed access to the [`Patch`]es of a project.
+pub struct Patches<'a> {
+    cobs: CollaborativeObjects<'a>,
+}
+
+impl<'a> Patches<'a> {
+    pub fn new(cobs: CollaborativeObjects<'a>) -> Self {
+        Self { cobs }
+    }
+
+    /// Open a new patch within `project`, authored by `whoami`.
+    pub fn create(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        new: NewPatch,
+    ) -> Result<(ObjectId, Patch), error::Create> {
+        let author = whoami.urn();
+        let patch = Patch {
+            version: VERSION,
+            title: new.title,
+            description: new.description,
+            author: author.clone(),
+            target: new.target,
+            state: State::Open,
+            revisions: vec![Revision {
+                commit: new.commit,
+                base: new.base,
+                author,
+                description: String::new(),
+            }],
+            reviews: vec![],
+            comments: vec![],
+            merged: None,
+        };
+
+        let mut doc = Doc::new();
+        let history = doc.change(doc::init(&patch)?)?;
+        let object = self.cobs.create(
+            whoami,
+            project,
+            NewObjectSpec {
+                schema_json: SCHEMA.clone(),
+                history,
+                typename: TYPENAME.clone(),
+                message: Some(format!("open patch: {}", patch.title)),
+            },
+        )?;
+
+        Ok((*object.id(), patch))
+    }
+
+    /// Get the patch `id` within `project`.
+    pub fn get(&self, project: &Urn, id: &ObjectId) -> Result<Option<Patch>, error::Retrieve> {
+        self.cobs
+            .retrieve(project, &TYPENAME, id)?
+            .map(|object| load_object(&object))
+            .transpose()
+    }
+
+    /// List all patches within `project`.
+    pub fn list(&self, project: &Urn) -> Result<Vec<(ObjectId, Patch)>, error::Retrieve> {
+        self.cobs
+            .list(project, &TYPENAME)?
+            .into_iter()
+            .filter_map(|object| match load_object(&object) {
+                Err(error::Retrieve::SchemaMismatch(id)) => {
+                    tracing::warn!(%id, "skipping patch with unknown schema");
+                    None
+                },
+                res => Some(res.map(|patch| (*object.id(), patch))),
+            })
+            .collect()
+    }
+
+    /// Add a new [`Revision`] pointing to `commit` to the patch `id`.
+    pub fn revise(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        commit: Oid,
+        base: Option<Oid>,
+        description: String,
+    ) -> Result<Patch, error::Update> {
+        self.update(whoami, project, id, "revise patch", |patch| {
+            if patch.revision(&commit).is_some() {
+                return Err(error::Update::RevisionExists(commit));
+            }
+            let revision = Revision {
+                commit,
+                base,
+                author: whoami.urn(),
+                description,
+            };
+            Ok(vec![doc::push(
+                "revisions",
+                patch.revisions.len(),
+                &revision,
+            )?])
+        })
+    }
+
+    /// Review the revision of patch `id` pointing to `revision`, or the latest
+    /// revision if not given.
+    pub fn review(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        revision: Option<Oid>,
+        verdict: Verdict,
+        comment: Option<String>,
+    ) -> Result<Patch, error::Update> {
+        self.update(whoami, project, id, "review patch", |patch| {
+            let review = Review {
+                revision: resolve(patch, revision)?,
+                author: whoami.urn(),
+                verdict,
+                comment,
+            };
+            Ok(vec![doc::push("reviews", patch.reviews.len(), &review)?])
+        })
+    }
+
+    /// Comment on the patch `id`, optionally anchored to a line of a file.
+    pub fn comment(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        body: String,
+        anchor: Option<Anchor>,
+    ) -> Result<Patch, error::Update> {
+        self.update(whoami, project, id, "comment on patch", |patch| {
+            if let Some(anchor) = &anchor {
+                resolve(patch, Some(anchor.revision))?;
+            }
+            let comment = Comment {
+                author: whoami.urn(),
+                body,
+                anchor,
+            };
+            Ok(vec![doc::push("comments", patch.comments.len(), &comment)?])
+        })
+    }
+
+    /// Mark the patch `id` as merged, recording `revision` -- or the latest
+    /// revision if not given -- as the one which got merged.
+    pub fn merge(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        revision: Option<Oid>,
+    ) -> Result<Patch, error::Update> {
+        self.update(whoami, project, id, "merge patch", |patch| {
+            let merge = Merge {
+                revision: resolve(patch, revision)?,
+                author: whoami.urn(),
+            };
+            Ok(vec![
+                doc::set("state", &State::Merged)?,
+                doc::set("merged", &merge)?,
+            ])
+        })
+    }
+
+    /// Close the patch `id` without merging it.
+    pub fn close(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+    ) -> Result<Patch, error::Update> {
+        self.update(whoami, project, id, "close patch", |_| {
+            Ok(vec![doc::set("state", &State::Closed)?])
+        })
+    }
+
+    fn update<F>(
+        &self,
+        whoami: &LocalIdentity,
+        project: &Urn,
+        id: &ObjectId,
+        message: &str,
+        f: F,
+    ) -> Result<Patch, error::Update>
+    where
+        F: FnOnce(&Patch) -> Result<Vec<automerge::LocalChange>, error::Update>,
+    {
+        let object = self
+            .cobs
+            .retrieve(project, &TYPENAME, id)?
+            .ok_or(error::Update::NotFound(*id))?;
+        check_schema(&object)?;
+        let mut doc = Doc::load(object.history())?;
+        let patch = load(&doc)?;
+        let changes = doc.change(f(&patch)?)?;
+        self.cobs.update(
+            whoami,
+            project,
+            UpdateObjectSpec {
+                object_id: *id,
+                typename: TYPENAME.clone(),
+                message: Some(message.to_owned()),
+                changes,
+            },
+        )?;
+
+        Ok(load(&doc)?)
+    }
+}
+
+/// Load the patch `object`, if it was created with our [`struct@SCHEMA`].
+fn load_object(object: &CollaborativeObject) -> Result<Patch, error::Retrieve> {
+    check_schema(object)?;
+    load(&Doc::load(object.history())?)
+}
+
+/// Objects of our [`struct@TYPENAME`] created with a different schema may not
+/// be laid out as we expect, so they are refused.
+fn check_schema(object: &CollaborativeObject) -> Result<(), error::Retrieve> {
+    if object.schema().json() == &*SCHEMA {
+        Ok(())
+    } else {
+        Err(error::Retrieve::SchemaMismatch(*object.id()))
+    }
+}
+
+fn load(doc: &Doc) -> Result<Patch, error::Retrieve> {
+    let patch = doc.get::<Patch>()?;
+    if patch.version > VERSION {
+        return Err(error::Retrieve::UnsupportedVersion(patch.version));
+    }
+    Ok(patch)
+}
+
+fn resolve(patch: &Patch, revision: Option<Oid>) -> Result<Oid, error::Update> {
+    match revision {
+        None => patch
+            .latest()
+            .map(|rev| rev.commit)
+            .ok_or(error::Update::NoRevisions),
+        Some(commit) => patch
+            .revision(&commit)
+            .map(|rev| rev.commit)
+            .ok_or(error::Update::NoSuchRevision(commit)),
+    }
+}
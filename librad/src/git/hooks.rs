@@ -1,16 +1,18 @@
 // Copyright © 2022 The Radicle Link Contributors
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::{fs, io, path::Path};
+use std::{convert::TryFrom as _, fs, io, path::Path};
 
+use git_ext::{self as ext, RefLike};
 pub use link_hooks::{
     hook::{self, Hook, Hooks, Notification, Process as _},
     Data,
     Track,
 };
+use link_replication::Updated;
 use tokio::process::Child;
 
-use crate::paths::Paths;
+use crate::{git::Urn, paths::Paths};
 
 pub const DATA: &str = "urn_changed";
 pub const TRACK: &str = "tracking_changed";
@@ -40,6 +42,50 @@ pub async fn hooks(paths: &Paths, config: hook::Config) -> io::Result<Hooks<Chil
     Ok(Hooks::new(config, data_hooks, track_hooks))
 }
 
+/// The [`Data`] notifications for the refs `updated` by a replication.
+///
+/// Each updated ref yields a notification for the [`Urn`] of its namespace,
+/// with the ref path relative to the namespace as the [`Urn::path`]. If the
+/// `rad/id` of a namespace was created, an additional notification without a
+/// path is yielded, signalling the creation of the namespace as a whole.
+///
+/// Symbolic refs have no target object, and so are skipped.
+pub fn data(updated: &[Updated]) -> impl Iterator<Item = Data<ext::Oid>> + '_ {
+    let zero = || ext::Oid::from(git2::Oid::zero());
+    updated.iter().flat_map(move |up| {
+        let (name, old, new) = match up {
+            Updated::Direct { name, target, prev } => (
+                name,
+                prev.map(ext::Oid::from).unwrap_or_else(zero),
+                ext::Oid::from(*target),
+            ),
+            Updated::Prune {
+                name,
+                prev: Some(prev),
+            } => (name, ext::Oid::from(*prev), zero()),
+            Updated::Prune { prev: None, .. } | Updated::Symbolic { .. } => return vec![],
+        };
+        let urn = match Urn::try_from(RefLike::from(name)) {
+            Ok(urn) => urn,
+            Err(err) => {
+                tracing::warn!(name = %name, err = %err, "skipping ref outside of a namespace");
+                return vec![];
+            },
+        };
+
+        let mut data = Vec::with_capacity(2);
+        if old == zero() && urn.path.as_ref().map(|p| p.as_str()) == Some("refs/rad/id") {
+            data.push(Data {
+                urn: urn.clone().with_path(None),
+                old,
+                new,
+            });
+        }
+        data.push(Data { urn, old, new });
+        data
+    })
+}
+
 async fn load(dir: impl AsRef<Path>) -> io::Result<Vec<Hook<Child>>> {
     let dir = dir.as_ref();
     let mut hooks = Vec::new();
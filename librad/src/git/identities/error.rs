@@ -22,6 +22,9 @@ pub enum Error {
     #[error("the URN {0} does not exist")]
     NotFound(Urn),
 
+    #[error("no proposal of revision {revision} for {urn}")]
+    NoProposal { urn: Urn, revision: ext::Oid },
+
     #[error("failed to build ref from URN")]
     RefFromUrn(#[from] reference::FromUrnError),
 
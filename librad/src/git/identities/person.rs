@@ -21,10 +21,11 @@ use crate::{
     identities::{
         self,
         delegation,
-        git::{Identities, Verifying},
+        git::{Identities, Revocation, Verifying},
         urn,
     },
     PeerId,
+    PublicKey,
 };
 
 pub use identities::{
@@ -155,6 +156,50 @@ where
     Ok(next)
 }
 
+/// Update the quorum threshold of the [`Person`] at `urn`.
+///
+/// A `threshold` of `None` reverts to requiring a majority of the
+/// delegations.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn update_threshold(
+    storage: &Storage,
+    urn: &Urn,
+    threshold: Option<usize>,
+) -> Result<Person, Error> {
+    let prev = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
+    let prev = Verifying::from(prev).signed()?;
+    let next = identities(storage).update_threshold(prev, threshold, storage.signer())?;
+
+    common::IdRef::from(urn).update(storage, next.content_id, "update threshold")?;
+    Refs::update(storage, urn)?;
+
+    Ok(next)
+}
+
+/// Revoke the device `key` of the [`Person`] at `urn`, optionally replacing it
+/// with the key of a new device.
+///
+/// The `revocation` is recorded in the new revision. Note that the revision
+/// needs to be signed by a quorum of the current delegations, which may
+/// require the remaining devices to [`merge`] it.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn revoke(
+    storage: &Storage,
+    urn: &Urn,
+    key: PublicKey,
+    replacement: Option<PublicKey>,
+    revocation: Revocation,
+) -> Result<Person, Error> {
+    let prev = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
+    let prev = Verifying::from(prev).signed()?;
+    let next = identities(storage).revoke(prev, key, replacement, revocation, storage.signer())?;
+
+    common::IdRef::from(urn).update(storage, next.content_id, "revoke key")?;
+    Refs::update(storage, urn)?;
+
+    Ok(next)
+}
+
 /// Merge and sign the [`Person`] state as seen by `from`.
 #[tracing::instrument(level = "debug", skip(storage))]
 pub fn merge(storage: &Storage, urn: &Urn, from: PeerId) -> Result<Person, Error> {
@@ -9,6 +9,7 @@ use either::Either;
 use git_ext::{is_not_found_err, OneLevel};
 
 pub mod heads;
+pub mod proposals;
 
 use super::{
     super::{
@@ -23,7 +24,17 @@ use super::{
 use crate::{
     identities::{
         self,
-        git::{Identities, IndirectDelegation, Project, Revision, VerifiedProject, Verifying},
+        git::{
+            error::{History, Tip},
+            Genesis,
+            HistoryMode,
+            Identities,
+            IndirectDelegation,
+            Project,
+            Revision,
+            VerifiedProject,
+            Verifying,
+        },
         urn,
     },
     PeerId,
@@ -177,17 +188,35 @@ where
     Ok(next)
 }
 
+/// Update the quorum threshold of the [`Project`] at `urn`.
+///
+/// A `threshold` of `None` reverts to requiring a majority of the
+/// delegations.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn update_threshold(
+    storage: &Storage,
+    urn: &Urn,
+    threshold: Option<usize>,
+) -> Result<Project, Error> {
+    let prev = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
+    let prev = Verifying::from(prev).signed()?;
+    let next = identities(storage).update_threshold(prev, threshold, storage.signer())?;
+
+    ProjectRefs::Update(&next, "update threshold").apply(storage)?;
+    Sigrefs::update(storage, urn)?;
+
+    Ok(next)
+}
+
 /// Merge and sign the [`Project`] state as seen by `from`.
+///
+/// If a [`proposals::Proposal`] we signed has reached quorum in the meantime,
+/// `rad/id` is fast-forwarded to it.
 #[tracing::instrument(level = "debug", skip(storage))]
 pub fn merge(storage: &Storage, urn: &Urn, from: PeerId) -> Result<Project, Error> {
     let ours = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
     let theirs = {
-        let (path, rad) = OneLevel::from_qualified(urn::DEFAULT_PATH.clone());
-        let rad = rad.expect("default path should be refs/rad/id");
-        let their_urn = Urn {
-            id: urn.id,
-            path: Some(reflike!("refs/remotes").join(from).join(rad).join(path)),
-        };
+        let their_urn = remote_urn(urn, from);
         get(storage, &their_urn)?.ok_or(Error::NotFound(their_urn))?
     };
 
@@ -196,6 +225,7 @@ pub fn merge(storage: &Storage, urn: &Urn, from: PeerId) -> Result<Project, Erro
     let next = identities(storage).update_from(ours, theirs, storage.signer())?;
 
     ProjectRefs::Update(&next, &format!("merge from {}", from)).apply(storage)?;
+    let next = proposals::settle(storage, urn)?.unwrap_or(next);
     Sigrefs::update(storage, urn)?;
 
     Ok(next)
@@ -214,6 +244,79 @@ where
     Ok(verified(storage).newer(a, b)?)
 }
 
+/// The relation of our view of a [`Project`] to the view of a peer, see
+/// [`review`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Review {
+    /// Both views are the same.
+    UpToDate,
+    /// Our view is ahead of the peer's.
+    Ahead,
+    /// The peer's view is ahead of ours, and can be merged.
+    Behind,
+    /// The views have diverged, and can't be merged.
+    Forked { ours: Tip, theirs: Tip },
+}
+
+/// Compare our view of the [`Project`] at `urn` to the view of `from`.
+///
+/// Unlike [`merge`], this requires both histories to be linear, and does not
+/// attempt to reconcile concurrent updates.
+///
+/// If the histories have forked, the result lists the tip and signers of both
+/// sides.
+///
+/// # Errors
+///
+/// * either history contains merge commits
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn review<S>(storage: &S, urn: &Urn, from: PeerId) -> Result<Review, Error>
+where
+    S: AsRef<storage::ReadOnly>,
+{
+    let storage = storage.as_ref();
+    let lookup = |urn| {
+        let refname = Reference::rad_id(Namespace::from(urn));
+        storage.reference_oid(&refname).map(|oid| oid.into())
+    };
+    let verify = |urn: Urn| -> Result<VerifiedProject, Error> {
+        let tip = get(storage, &urn)?.ok_or(Error::NotFound(urn))?.content_id;
+        identities(storage)
+            .with_history(HistoryMode::Linear)
+            .verify(*tip, lookup)
+            .map_err(|e| Error::Verify(e.into()))
+    };
+
+    let ours = verify(urn.clone())?;
+    let theirs = verify(remote_urn(urn, from))?;
+    if ours.content_id == theirs.content_id {
+        return Ok(Review::UpToDate);
+    }
+
+    let ours_id = ours.content_id;
+    match verified(storage)
+        .with_history(HistoryMode::Linear)
+        .newer(ours, theirs)
+    {
+        Ok(newer) if newer.content_id == ours_id => Ok(Review::Ahead),
+        Ok(_) => Ok(Review::Behind),
+        Err(History::Fork { left, right }) => Ok(Review::Forked {
+            ours: Tip::of(&left),
+            theirs: Tip::of(&right),
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn remote_urn(urn: &Urn, peer: PeerId) -> Urn {
+    let (path, rad) = OneLevel::from_qualified(urn::DEFAULT_PATH.clone());
+    let rad = rad.expect("default path should be refs/rad/id");
+    Urn {
+        id: urn.id,
+        path: Some(reflike!("refs/remotes").join(peer).join(rad).join(path)),
+    }
+}
+
 enum ProjectRefs<'a> {
     Create(&'a Project),
     Update(&'a Project, &'a str),
@@ -267,16 +370,18 @@ impl<'a> ProjectRefs<'a> {
     }
 }
 
+/// Projects may be created with several delegations (see [`create`]), so their
+/// root revision only needs to be signed by its creator.
 fn identities<S>(storage: &S) -> Identities<Project>
 where
     S: AsRef<storage::ReadOnly>,
 {
-    storage.as_ref().identities()
+    storage.as_ref().identities().with_genesis(Genesis::Single)
 }
 
 fn verified<S>(storage: &S) -> Identities<VerifiedProject>
 where
     S: AsRef<storage::ReadOnly>,
 {
-    storage.as_ref().identities()
+    storage.as_ref().identities().with_genesis(Genesis::Single)
 }
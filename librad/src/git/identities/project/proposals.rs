@@ -0,0 +1,237 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Proposed revisions of a [`Project`].
+//!
+//! A delegate [`propose`]s a new revision by storing it under
+//! `refs/rad/id-proposals/<revision>` instead of updating `rad/id` directly.
+//! Like the other `rad` refs, the proposal is included in the signed refs and
+//! replicated. Other delegates [`sign`] the proposal, merging the signatures
+//! of all copies they have seen, until the proposal reaches quorum. At this
+//! point `rad/id` is fast-forwarded to it.
+
+use std::{collections::BTreeSet, fmt::Debug};
+
+use git_ext::is_not_found_err;
+
+use super::{
+    super::{
+        super::{
+            refs::Refs as Sigrefs,
+            storage::{self, ReadOnlyStorage as _, Storage},
+            types::{Force, Namespace, Reference, RefsCategory},
+        },
+        common,
+        error::Error,
+        local::LocalIdentity,
+    },
+    get,
+    identities,
+    IndirectDelegation,
+    Project,
+    ProjectPayload,
+    ProjectRefs,
+    Revision,
+    Urn,
+    Verifying,
+};
+use crate::{PeerId, PublicKey};
+
+/// The state of a proposed revision, see [`status`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proposal {
+    /// The proposed revision.
+    pub revision: Revision,
+    /// The delegates which signed any copy of the proposal we have seen.
+    pub signers: BTreeSet<PublicKey>,
+    /// Whether the proposal has been adopted as the current `rad/id`.
+    pub adopted: bool,
+}
+
+/// Propose a new revision of the [`Project`] at `urn`.
+///
+/// The revision is signed by us, but only stored under
+/// `refs/rad/id-proposals/<revision>`. If our signature alone is sufficient
+/// to reach quorum, `rad/id` is fast-forwarded to the proposal right away.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn propose<L, P, D>(
+    storage: &Storage,
+    urn: &Urn,
+    whoami: L,
+    payload: P,
+    delegations: D,
+) -> Result<Project, Error>
+where
+    L: Into<Option<LocalIdentity>> + Debug,
+    P: Into<Option<ProjectPayload>> + Debug,
+    D: Into<Option<IndirectDelegation>> + Debug,
+{
+    let prev = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
+    let prev = Verifying::from(prev).signed()?;
+    let next = identities(storage).update(prev, payload, delegations, storage.signer())?;
+
+    ProposalRef(urn, next.revision).update(storage, &next, "propose")?;
+    if let Some(local_id) = whoami.into() {
+        local_id.link(storage, urn)?;
+    }
+    settle(storage, urn)?;
+    Sigrefs::update(storage, urn)?;
+
+    Ok(next)
+}
+
+/// Sign the proposed `revision` of the [`Project`] at `urn`.
+///
+/// The signatures of all copies of the proposal, ours and the ones of tracked
+/// peers, are merged and our signature is added. If the result reaches
+/// quorum, `rad/id` is fast-forwarded to it.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn sign(storage: &Storage, urn: &Urn, revision: Revision) -> Result<Project, Error> {
+    let proposals = copies(storage, urn, Some(revision))?
+        .into_iter()
+        .map(|proposal| Verifying::from(proposal).signed())
+        .collect::<Result<Vec<_>, _>>()?;
+    if proposals.is_empty() {
+        return Err(Error::NoProposal {
+            urn: urn.clone(),
+            revision,
+        });
+    }
+    let next = identities(storage).merge_signatures(proposals, storage.signer())?;
+
+    ProposalRef(urn, revision).update(storage, &next, "sign")?;
+    settle(storage, urn)?;
+    Sigrefs::update(storage, urn)?;
+
+    Ok(next)
+}
+
+/// List the proposed revisions of the [`Project`] at `urn`, ours as well as
+/// the ones of tracked peers.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn status<S>(storage: &S, urn: &Urn) -> Result<Vec<Proposal>, Error>
+where
+    S: AsRef<storage::ReadOnly>,
+{
+    let storage = storage.as_ref();
+    let current = get(storage, urn)?.ok_or_else(|| Error::NotFound(urn.clone()))?;
+
+    let mut proposals: Vec<Proposal> = Vec::new();
+    for proposal in copies(storage, urn, None)? {
+        let signers = proposal.signatures.keys().copied();
+        match proposals
+            .iter_mut()
+            .find(|p| p.revision == proposal.revision)
+        {
+            Some(p) => p.signers.extend(signers),
+            None => proposals.push(Proposal {
+                revision: proposal.revision,
+                signers: signers.collect(),
+                adopted: proposal.revision == current.revision,
+            }),
+        }
+    }
+
+    Ok(proposals)
+}
+
+/// Fast-forward `rad/id` to a proposal which reached quorum, if any, and
+/// remove our proposals which are no longer ahead of `rad/id`.
+///
+/// Only proposals which build on our `rad/id` and carry our signature are
+/// considered, mirroring the rules of a fast-forward in
+/// [`crate::identities::git::Identities::update_from`].
+pub(super) fn settle(storage: &Storage, urn: &Urn) -> Result<Option<Project>, Error> {
+    let repo = storage.as_raw();
+    let ours = common::IdRef::from(urn).oid(storage)?;
+    let our_pk = storage.peer_id().as_public_key();
+    let lookup = |urn| {
+        let refname = Reference::rad_id(Namespace::from(urn));
+        storage.reference_oid(&refname).map(|oid| oid.into())
+    };
+
+    let mut adopted = None;
+    for proposal in copies(storage, urn, None)? {
+        let tip = proposal.content_id;
+        if !proposal.signatures.contains_key(our_pk) || !repo.graph_descendant_of(*tip, *ours)? {
+            continue;
+        }
+        // Copies replicated from other peers may not verify at all, which is
+        // no different from not having reached quorum yet.
+        match identities(storage).verify(*tip, lookup) {
+            Ok(verified) if verified.content_id == tip => {
+                adopted = Some(verified.into_inner());
+                break;
+            },
+            _ => continue,
+        }
+    }
+
+    let current = match &adopted {
+        Some(project) => {
+            ProjectRefs::Update(project, "adopt proposal").apply(storage)?;
+            project.content_id
+        },
+        None => ours,
+    };
+
+    let local = Reference::rad_id_proposals(Namespace::from(urn), None::<PeerId>);
+    let mut stale = Vec::new();
+    for reference in storage.references(&local)? {
+        let reference = reference?;
+        let tip = reference.peel_to_commit()?.id();
+        if tip == *current || repo.graph_descendant_of(*current, tip)? {
+            stale.extend(reference.name().map(ToOwned::to_owned));
+        }
+    }
+    for name in stale {
+        match repo.find_reference(&name) {
+            Ok(mut reference) => reference.delete()?,
+            Err(e) if is_not_found_err(&e) => {},
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(adopted)
+}
+
+/// All copies of the proposals of the [`Project`] at `urn`, optionally limited
+/// to `revision`.
+fn copies<S>(storage: &S, urn: &Urn, revision: Option<Revision>) -> Result<Vec<Project>, Error>
+where
+    S: AsRef<storage::ReadOnly>,
+{
+    let storage = storage.as_ref();
+    let glob = globset::Glob::new(&format!(
+        "refs/namespaces/{}/refs/**/{}/id-proposals/{}",
+        Namespace::from(urn),
+        RefsCategory::Rad,
+        revision
+            .map(|rev| rev.to_string())
+            .unwrap_or_else(|| "*".into()),
+    ))
+    .expect("proposals glob is valid");
+
+    let mut proposals = Vec::new();
+    for reference in storage.references_glob(glob.compile_matcher())? {
+        let tip = reference?.peel_to_commit()?.id();
+        proposals.push(identities(storage).get(tip)?);
+    }
+
+    Ok(proposals)
+}
+
+/// Ad-hoc helper type for managing our `rad/id-proposals/<revision>` refs
+struct ProposalRef<'a>(&'a Urn, Revision);
+
+impl<'a> ProposalRef<'a> {
+    fn update(&self, storage: &Storage, proposal: &Project, msg: &str) -> Result<(), Error> {
+        Reference::rad_id_proposal(Namespace::from(self.0), None::<PeerId>, self.1).create(
+            storage.as_raw(),
+            *proposal.content_id,
+            Force::True,
+            msg,
+        )?;
+        Ok(())
+    }
+}
@@ -25,7 +25,9 @@ use crate::{
 };
 
 pub mod config;
+pub mod gc;
 pub mod glob;
+pub mod maintenance;
 pub mod pool;
 pub mod read;
 pub mod watch;
@@ -0,0 +1,173 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Garbage collection of the monorepo.
+//!
+//! Untracking a peer (with pruning) only removes its refs, the objects they
+//! pointed to remain in the monorepo. [`collect`] determines which objects are
+//! still reachable from any ref, across all namespaces, and removes the rest.
+//!
+//! # Concurrency
+//!
+//! A [`Storage`] may be written to by other threads (eg. via a [`super::Pool`])
+//! or processes while garbage is being collected. Writers typically store
+//! objects before the refs pointing to them are updated, so those objects
+//! appear unreachable for a short while. To not lose them, unreachable objects
+//! are only removed once they are older than [`Options::expire`].
+//! Concurrent runs of [`collect`] are serialised by `git` itself.
+
+use std::{
+    io,
+    path::Path,
+    process::{Command, Output},
+    time::Duration,
+};
+
+use thiserror::Error;
+
+use super::Storage;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("`git {cmd}` failed with {status}: {stderr}")]
+    Failed {
+        cmd: &'static str,
+        status: std::process::ExitStatus,
+        stderr: String,
+    },
+
+    #[error("unexpected output of `git count-objects`: {0}")]
+    CountObjects(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Options for [`collect`].
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Unreachable objects younger than this are kept.
+    ///
+    /// Setting this to a very short duration risks deleting objects of
+    /// concurrent writers, see the [module documentation][self].
+    pub expire: Duration,
+    /// Recompute all deltas instead of reusing existing ones. This is
+    /// considerably slower, but may result in smaller packs.
+    pub aggressive: bool,
+}
+
+impl Default for Options {
+    /// The defaults of `git gc`, ie. unreachable objects are kept for two
+    /// weeks.
+    fn default() -> Self {
+        Self {
+            expire: Duration::from_secs(14 * 24 * 60 * 60),
+            aggressive: false,
+        }
+    }
+}
+
+/// The object counts and sizes, as reported by `git count-objects`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Objects {
+    /// Number of loose objects.
+    pub loose: u64,
+    /// Disk space consumed by loose objects, in KiB.
+    pub loose_size: u64,
+    /// Number of objects in packs.
+    pub packed: u64,
+    /// Number of packs.
+    pub packs: u64,
+    /// Disk space consumed by packs, in KiB.
+    pub packs_size: u64,
+}
+
+impl Objects {
+    /// Disk space consumed by all objects, in KiB.
+    pub fn size(&self) -> u64 {
+        self.loose_size + self.packs_size
+    }
+}
+
+/// The [`Objects`] before and after garbage collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub before: Objects,
+    pub after: Objects,
+}
+
+/// Remove the objects which are no longer reachable from any ref in `storage`,
+/// and repack the remaining ones.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn collect(storage: &Storage, opts: &Options) -> Result<Stats, Error> {
+    let git_dir = storage.path();
+    let before = count_objects(git_dir)?;
+
+    let mut gc = git(git_dir);
+    gc.args(&["gc", "--quiet"])
+        .arg(format!("--prune={}.seconds.ago", opts.expire.as_secs()));
+    if opts.aggressive {
+        gc.arg("--aggressive");
+    }
+    run("gc", &mut gc)?;
+
+    let after = count_objects(git_dir)?;
+    tracing::info!(
+        before = before.size(),
+        after = after.size(),
+        "collected garbage"
+    );
+
+    Ok(Stats { before, after })
+}
+
+/// Count the objects in the repository at `git_dir`.
+pub fn count_objects(git_dir: &Path) -> Result<Objects, Error> {
+    let out = run("count-objects", git(git_dir).args(&["count-objects", "-v"]))?;
+    let out = String::from_utf8_lossy(&out.stdout);
+
+    let mut objects = Objects::default();
+    for line in out.lines() {
+        let (key, val) = line
+            .split_once(": ")
+            .ok_or_else(|| Error::CountObjects(line.to_owned()))?;
+        let val = || {
+            val.trim()
+                .parse::<u64>()
+                .map_err(|_| Error::CountObjects(line.to_owned()))
+        };
+        match key {
+            "count" => objects.loose = val()?,
+            "size" => objects.loose_size = val()?,
+            "in-pack" => objects.packed = val()?,
+            "packs" => objects.packs = val()?,
+            "size-pack" => objects.packs_size = val()?,
+            _ => {},
+        }
+    }
+
+    Ok(objects)
+}
+
+pub(super) fn git(git_dir: &Path) -> Command {
+    let mut git = Command::new("git");
+    git.env_clear()
+        .envs(std::env::vars().filter(|(key, _)| key == "PATH" || key.starts_with("GIT_TRACE")))
+        .arg("--git-dir")
+        .arg(git_dir);
+    git
+}
+
+pub(super) fn run(cmd: &'static str, git: &mut Command) -> Result<Output, Error> {
+    let out = git.output()?;
+    if out.status.success() {
+        Ok(out)
+    } else {
+        Err(Error::Failed {
+            cmd,
+            status: out.status,
+            stderr: String::from_utf8_lossy(&out.stderr).into_owned(),
+        })
+    }
+}
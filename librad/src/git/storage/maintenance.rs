@@ -0,0 +1,170 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Routine maintenance of the monorepo.
+//!
+//! Every fetch adds a new pack to the monorepo, and every local update loose
+//! objects. Over time, this slows down object lookups, and thus both fetching
+//! and serving. [`run`] executes a set of [`Task`]s which keep the number of
+//! packs bounded and maintain auxiliary indices, similar to what
+//! `git maintenance` does. Unlike [`super::gc`], it never removes unreachable
+//! objects, nor packs unreachable loose objects, and is thus cheap enough to
+//! be run frequently.
+//!
+//! # Delta islands
+//!
+//! All packing honours a delta island per namespace (see [`configure`]), so
+//! no object is stored as a delta against an object of another namespace.
+//! Packs served for one URN can thus be built from reused deltas without
+//! having to include objects of other URNs.
+//!
+//! # Concurrency
+//!
+//! Objects are only ever moved between packs, never removed, so concurrent
+//! readers and writers are not affected, except for caches of the set of
+//! packs: `multi-pack-index expire` deletes packs whose objects are all
+//! contained in other packs. After [`run`] completes, such caches (eg.
+//! [`link_git::odb::index::Shared`]) should be reloaded to release the removed
+//! packs.
+
+use std::path::Path;
+
+use thiserror::Error;
+
+use super::{
+    gc::{self, count_objects, Stats},
+    Storage,
+};
+
+/// Partitions the objects into one delta island per namespace.
+const ISLAND: &str = "^refs/namespaces/([^/]+)/";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error(transparent)]
+    Git(#[from] gc::Error),
+
+    #[error(transparent)]
+    Config(#[from] git2::Error),
+}
+
+/// A maintenance task, see [`run`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Task {
+    /// Move loose objects into a pack.
+    LooseObjects,
+    /// Update the multi-pack-index, and consolidate all packs into one if
+    /// there are more than [`Options::pack_limit`].
+    IncrementalRepack,
+    /// Update the commit-graph, which speeds up history traversals.
+    CommitGraph,
+}
+
+impl Task {
+    /// All tasks, in the order they should be run.
+    pub const ALL: [Self; 3] = [
+        Self::LooseObjects,
+        Self::IncrementalRepack,
+        Self::CommitGraph,
+    ];
+}
+
+/// Options for [`run`].
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The tasks to run, in order.
+    pub tasks: Vec<Task>,
+    /// The number of packs above which [`Task::IncrementalRepack`]
+    /// consolidates all packs into one.
+    pub pack_limit: u64,
+}
+
+impl Default for Options {
+    /// Run all [`Task`]s, using the `gc.autoPackLimit` default of `git`.
+    fn default() -> Self {
+        Self {
+            tasks: Task::ALL.to_vec(),
+            pack_limit: 50,
+        }
+    }
+}
+
+/// Set the configuration the [`Task`]s rely on.
+///
+/// This is idempotent, and called by [`run`].
+pub fn configure(storage: &Storage) -> Result<(), Error> {
+    let mut config = git2::Config::open(&storage.config_path())?;
+    config.set_multivar("pack.island", "^\\^refs/namespaces/", ISLAND)?;
+    config.set_bool("repack.useDeltaIslands", true)?;
+    config.set_bool("core.multiPackIndex", true)?;
+    config.set_bool("core.commitGraph", true)?;
+    Ok(())
+}
+
+/// Run the [`Options::tasks`] on `storage`.
+#[tracing::instrument(level = "debug", skip(storage))]
+pub fn run(storage: &Storage, opts: &Options) -> Result<Stats, Error> {
+    configure(storage)?;
+
+    let git_dir = storage.path();
+    let before = count_objects(git_dir)?;
+    for task in &opts.tasks {
+        tracing::debug!(?task, "running maintenance task");
+        match task {
+            Task::LooseObjects => loose_objects(git_dir)?,
+            Task::IncrementalRepack => incremental_repack(git_dir, opts.pack_limit)?,
+            Task::CommitGraph => commit_graph(git_dir)?,
+        }
+    }
+    let after = count_objects(git_dir)?;
+    tracing::info!(
+        packs_before = before.packs,
+        packs_after = after.packs,
+        "completed maintenance"
+    );
+
+    Ok(Stats { before, after })
+}
+
+fn loose_objects(git_dir: &Path) -> Result<(), gc::Error> {
+    // Without `-a` or `-A`, only loose objects reachable from any ref are
+    // packed. Unreachable loose objects are left alone, and thus keep the
+    // mtime `gc` expires them by.
+    gc::run("repack", gc::git(git_dir).args(&["repack", "-i", "-q"]))?;
+    // Remove the loose objects which are now packed
+    gc::run(
+        "prune-packed",
+        gc::git(git_dir).args(&["prune-packed", "-q"]),
+    )?;
+    Ok(())
+}
+
+fn incremental_repack(git_dir: &Path, pack_limit: u64) -> Result<(), gc::Error> {
+    let packs = count_objects(git_dir)?.packs;
+    if packs > pack_limit {
+        // `-A` keeps unreachable objects as loose objects, so objects written
+        // concurrently are not lost. They are removed by `gc` once expired.
+        gc::run(
+            "repack",
+            gc::git(git_dir).args(&["repack", "-A", "-d", "-i", "-q"]),
+        )?;
+    }
+    gc::run(
+        "multi-pack-index",
+        gc::git(git_dir).args(&["multi-pack-index", "write"]),
+    )?;
+    gc::run(
+        "multi-pack-index",
+        gc::git(git_dir).args(&["multi-pack-index", "expire"]),
+    )?;
+    Ok(())
+}
+
+fn commit_graph(git_dir: &Path) -> Result<(), gc::Error> {
+    gc::run(
+        "commit-graph",
+        gc::git(git_dir).args(&["commit-graph", "write", "--reachable", "--split"]),
+    )?;
+    Ok(())
+}
@@ -20,6 +20,7 @@ pub use link_tracking::{
             error,
             get,
             is_tracked,
+            manifest,
             modify,
             policy,
             reference,
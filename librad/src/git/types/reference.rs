@@ -292,6 +292,25 @@ impl<N, R> Reference<N, R, One> {
         }
     }
 
+    /// Build a reference that points to:
+    ///     * `refs/namespaces/<namespace>/refs/rad/id-proposals/<revision>`
+    ///     * `refs/namespaces/<namespace>/refs/remote/<peer_id>/rad/
+    ///       id-proposals/<revision>`
+    pub fn rad_id_proposal(
+        namespace: impl Into<Option<N>>,
+        remote: impl Into<Option<R>>,
+        revision: ext::Oid,
+    ) -> Self {
+        Self {
+            remote: remote.into(),
+            category: RefsCategory::Rad,
+            name: reflike!("id-proposals").join(
+                ext::RefLike::try_from(revision.to_string()).expect("oid is a valid ref name"),
+            ),
+            namespace: namespace.into(),
+        }
+    }
+
     /// Build a reference that points to:
     ///     * `refs/namespaces/<namespace>/refs/rad/self`
     ///     * `refs/namespaces/<namespace>/refs/remote/<peer_id>/rad/self`
@@ -448,6 +467,18 @@ impl<N, R> Reference<N, R, Many> {
         }
     }
 
+    /// Build a reference that points to:
+    ///     * `refs[/namespaces/<namespace>/refs][/remotes/<remote>]/rad/
+    ///       id-proposals/*`
+    pub fn rad_id_proposals(namespace: impl Into<Option<N>>, remote: impl Into<Option<R>>) -> Self {
+        Self {
+            remote: remote.into(),
+            category: RefsCategory::Rad,
+            name: refspec_pattern!("id-proposals/*"),
+            namespace: namespace.into(),
+        }
+    }
+
     /// Build a reference that points to:
     ///     * `refs[/namespaces/<namespace>/refs][/remotes/<remote>]/heads/*`
     pub fn heads(namespace: impl Into<Option<N>>, remote: impl Into<Option<R>>) -> Self {
@@ -5,6 +5,7 @@
 
 use std::{borrow::Cow, fmt::Display, str::FromStr};
 
+pub mod bandwidth;
 pub mod codec;
 pub mod connection;
 pub mod discovery;
@@ -27,8 +28,9 @@ pub mod x509;
 /// permits gradual rollout scenarios of major network upgrades.
 ///
 /// For the negotiation of optional (compatible _per definitionem_) protocol
-/// features, the [`protocol::PeerAdvertisement`] reserves space for advertising
-/// [`protocol::Capability`]s.
+/// features, the [`protocol::PeerAdvertisement`] advertises the
+/// [`protocol::Capability`]s of a peer. Clients of optional sub-protocols check
+/// them before opening a stream.
 ///
 /// [ALPN]: https://tools.ietf.org/html/rfc7301
 pub const PROTOCOL_VERSION: u8 = 2;
@@ -0,0 +1,529 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Accounting and quotas of the bytes transferred via `git` streams.
+//!
+//! Serving `upload-pack` to a remote peer counts as [`Direction::Upload`],
+//! fetching from it during replication as [`Direction::Download`]. In either
+//! direction, the bytes are counted per remote peer and per URN, and may be
+//! limited by a [`Limit`] for each: a budget of bytes which replenishes
+//! continuously over a time window.
+//!
+//! When a budget is exhausted, the stream transferring the bytes fails with an
+//! [`Exceeded`] error, and so do new streams of the same peer or URN until the
+//! budget has (partially) replenished.
+//!
+//! Budgets are forgotten once they are fully replenished, and the usage is
+//! only kept for the [`MAX_ENTRIES`] most recently active peers and URNs.
+
+use std::{
+    cmp::max,
+    collections::HashMap,
+    convert::TryFrom as _,
+    fmt,
+    hash::Hash,
+    io,
+    num::{NonZeroU32, NonZeroU64},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use dashmap::{mapref::one::RefMut, DashMap};
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    ready,
+};
+use nonzero_ext::nonzero;
+use once_cell::sync::OnceCell;
+use thiserror::Error;
+
+use crate::{git::Urn, PeerId};
+
+/// Bytes are charged against a [`Limit`] in units of this size.
+const UNIT: u64 = 1024;
+
+/// The number of peers, and of URNs, for which the [`Usage`] is kept.
+pub const MAX_ENTRIES: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Bytes sent to a remote peer, ie. serving `upload-pack`.
+    Upload,
+    /// Bytes received from a remote peer, ie. fetching.
+    Download,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Upload => f.write_str("upload"),
+            Self::Download => f.write_str("download"),
+        }
+    }
+}
+
+/// What a [`Limit`] applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Scope {
+    Peer(PeerId),
+    Urn(Urn),
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Peer(peer) => write!(f, "peer {}", peer),
+            Self::Urn(urn) => write!(f, "{}", urn),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{direction} quota for {scope} exceeded")]
+pub struct Exceeded {
+    pub direction: Direction,
+    pub scope: Scope,
+}
+
+impl From<Exceeded> for io::Error {
+    fn from(e: Exceeded) -> Self {
+        io::Error::new(io::ErrorKind::Other, e)
+    }
+}
+
+/// A budget of `bytes`, which replenishes fully over `per`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limit {
+    pub bytes: NonZeroU64,
+    pub per: Duration,
+}
+
+impl Limit {
+    /// The time it takes to replenish one [`UNIT`].
+    fn interval(&self) -> Duration {
+        let units = max(1, self.bytes.get() / UNIT);
+        let nanos = self.per.as_nanos() / u128::from(units);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+/// The [`Limit`]s of one [`Direction`].
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    /// Bytes per remote peer, across all URNs.
+    pub per_peer: Option<Limit>,
+    /// Bytes per URN, across all remote peers.
+    pub per_urn: Option<Limit>,
+}
+
+/// Bandwidth quota.
+///
+/// Default: unlimited
+#[derive(Clone, Debug, Default)]
+pub struct Quota {
+    pub upload: Limits,
+    pub download: Limits,
+}
+
+/// The bytes transferred in each [`Direction`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub upload: u64,
+    pub download: u64,
+}
+
+impl Usage {
+    fn add(&mut self, direction: Direction, bytes: u64) {
+        match direction {
+            Direction::Upload => self.upload = self.upload.saturating_add(bytes),
+            Direction::Download => self.download = self.download.saturating_add(bytes),
+        }
+    }
+}
+
+/// The [`Usage`] since the [`Bandwidth`] was created.
+///
+/// The `total` accounts for all bytes, while `peers` and `urns` only contain
+/// the [`MAX_ENTRIES`] most recently active of each.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    pub total: Usage,
+    pub peers: HashMap<PeerId, Usage>,
+    pub urns: HashMap<Urn, Usage>,
+}
+
+/// The budgets of a [`Limit`], per peer or per URN.
+///
+/// A budget is represented by the instant at which it will be fully
+/// replenished. Budgets which are full are equivalent to absent ones, and are
+/// dropped whenever the map has grown past `sweep_at`.
+struct Limiter<K: Eq + Hash> {
+    limit: Limit,
+    budgets: DashMap<K, Instant>,
+    sweep_at: AtomicUsize,
+}
+
+impl<K: Eq + Hash + Clone> Limiter<K> {
+    fn new(limit: Limit) -> Self {
+        Self {
+            limit,
+            budgets: DashMap::new(),
+            sweep_at: AtomicUsize::new(MAX_ENTRIES),
+        }
+    }
+
+    /// Lock the budget of `key`.
+    fn budget(&self, key: K, now: Instant) -> Budget<'_, K> {
+        if self.budgets.len() >= self.sweep_at.load(Ordering::Relaxed) {
+            self.budgets.retain(|_, replenished| *replenished > now);
+            self.sweep_at
+                .store(max(MAX_ENTRIES, 2 * self.budgets.len()), Ordering::Relaxed);
+        }
+        Budget {
+            limit: &self.limit,
+            replenished: self.budgets.entry(key).or_insert(now),
+        }
+    }
+}
+
+struct Budget<'a, K: Eq + Hash> {
+    limit: &'a Limit,
+    replenished: RefMut<'a, K, Instant>,
+}
+
+impl<'a, K: Eq + Hash> Budget<'a, K> {
+    /// The instant at which the budget would be fully replenished after
+    /// charging `units` at `now`, or `None` if the budget doesn't suffice.
+    fn charged(&self, units: NonZeroU32, now: Instant) -> Option<Instant> {
+        let cost = self.limit.interval().checked_mul(units.get())?;
+        let replenished = max(*self.replenished, now).checked_add(cost)?;
+        (replenished.saturating_duration_since(now) <= self.limit.per).then(|| replenished)
+    }
+}
+
+#[derive(Default)]
+struct Limiters {
+    per_peer: Option<Limiter<PeerId>>,
+    per_urn: Option<Limiter<Urn>>,
+}
+
+impl From<Limits> for Limiters {
+    fn from(limits: Limits) -> Self {
+        Self {
+            per_peer: limits.per_peer.map(Limiter::new),
+            per_urn: limits.per_urn.map(Limiter::new),
+        }
+    }
+}
+
+/// The [`Usage`] of a peer or URN, and when it was last updated.
+#[derive(Clone, Copy)]
+struct Recent {
+    usage: Usage,
+    updated: Instant,
+}
+
+/// The [`Usage`] of the most recently active peers or URNs.
+///
+/// Once the map has grown past `sweep_at`, all but the [`MAX_ENTRIES`] most
+/// recently updated entries are dropped.
+struct Usages<K: Eq + Hash> {
+    recent: DashMap<K, Recent>,
+    sweep_at: AtomicUsize,
+}
+
+impl<K: Eq + Hash> Default for Usages<K> {
+    fn default() -> Self {
+        Self {
+            recent: DashMap::new(),
+            sweep_at: AtomicUsize::new(2 * MAX_ENTRIES),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> Usages<K> {
+    fn add(&self, key: K, direction: Direction, bytes: u64) {
+        let now = Instant::now();
+        if self.recent.len() >= self.sweep_at.load(Ordering::Relaxed) {
+            let mut updated = self
+                .recent
+                .iter()
+                .map(|entry| entry.value().updated)
+                .collect::<Vec<_>>();
+            updated.sort_unstable_by(|a, b| b.cmp(a));
+            if let Some(oldest) = updated.get(MAX_ENTRIES - 1).copied() {
+                self.recent.retain(|_, recent| recent.updated >= oldest);
+            }
+            self.sweep_at.store(
+                max(2 * MAX_ENTRIES, 2 * self.recent.len()),
+                Ordering::Relaxed,
+            );
+        }
+
+        let mut recent = self.recent.entry(key).or_insert(Recent {
+            usage: Usage::default(),
+            updated: now,
+        });
+        recent.usage.add(direction, bytes);
+        recent.updated = now;
+    }
+
+    fn stats(&self) -> HashMap<K, Usage> {
+        let mut recent = self
+            .recent
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect::<Vec<_>>();
+        recent.sort_unstable_by(|(_, a), (_, b)| b.updated.cmp(&a.updated));
+        recent
+            .into_iter()
+            .take(MAX_ENTRIES)
+            .map(|(key, recent)| (key, recent.usage))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    upload: Limiters,
+    download: Limiters,
+    total_upload: AtomicU64,
+    total_download: AtomicU64,
+    peers: Usages<PeerId>,
+    urns: Usages<Urn>,
+}
+
+/// Shared accounting of the bandwidth used by a peer.
+///
+/// Cloning is cheap, and all clones share the same counters and budgets. The
+/// [`Default`] value does not limit anything.
+#[derive(Clone, Default)]
+pub struct Bandwidth {
+    inner: Arc<Inner>,
+}
+
+impl Bandwidth {
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                upload: quota.upload.into(),
+                download: quota.download.into(),
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// Start accounting for the streams of `direction` with `peer`.
+    ///
+    /// If the `urn` is not known yet, it can be set later using
+    /// [`Account::set_urn`]. Until then, the bytes only count towards the
+    /// peer.
+    pub fn account(&self, direction: Direction, peer: PeerId, urn: Option<Urn>) -> Account {
+        let cell = OnceCell::new();
+        if let Some(urn) = urn {
+            cell.set(urn).ok();
+        }
+        Account {
+            bandwidth: self.clone(),
+            direction,
+            peer,
+            urn: Arc::new(cell),
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            total: Usage {
+                upload: self.inner.total_upload.load(Ordering::Relaxed),
+                download: self.inner.total_download.load(Ordering::Relaxed),
+            },
+            peers: self.inner.peers.stats(),
+            urns: self.inner.urns.stats(),
+        }
+    }
+
+    fn limiters(&self, direction: Direction) -> &Limiters {
+        match direction {
+            Direction::Upload => &self.inner.upload,
+            Direction::Download => &self.inner.download,
+        }
+    }
+}
+
+/// The accounting of the streams of one [`Direction`] with one remote peer,
+/// concerning one URN.
+///
+/// Cloning is cheap, and all clones share the same URN.
+#[derive(Clone)]
+pub struct Account {
+    bandwidth: Bandwidth,
+    direction: Direction,
+    peer: PeerId,
+    urn: Arc<OnceCell<Urn>>,
+}
+
+impl Account {
+    pub fn peer(&self) -> PeerId {
+        self.peer
+    }
+
+    pub fn urn(&self) -> Option<&Urn> {
+        self.urn.get()
+    }
+
+    /// Set the URN the bytes count towards.
+    ///
+    /// Has no effect if the URN is already set.
+    pub fn set_urn(&self, urn: Urn) {
+        self.urn.set(urn).ok();
+    }
+
+    /// Check whether there is any budget left, without waiting for bytes to
+    /// be transferred.
+    pub fn check(&self) -> Result<(), Exceeded> {
+        self.charge(nonzero!(1u32))
+    }
+
+    /// Wrap `stream`, so that the bytes read from or written to it count
+    /// towards this account.
+    pub fn metered<S>(&self, stream: S) -> Metered<S> {
+        Metered {
+            inner: stream,
+            account: self.clone(),
+            pending: 0,
+        }
+    }
+
+    fn record(&self, bytes: u64) {
+        let inner = &self.bandwidth.inner;
+        let total = match self.direction {
+            Direction::Upload => &inner.total_upload,
+            Direction::Download => &inner.total_download,
+        };
+        total.fetch_add(bytes, Ordering::Relaxed);
+        inner.peers.add(self.peer, self.direction, bytes);
+        if let Some(urn) = self.urn() {
+            inner.urns.add(urn.clone(), self.direction, bytes);
+        }
+    }
+
+    /// Charge `units` against the budgets of both the peer and the URN.
+    ///
+    /// Nothing is charged unless both budgets suffice.
+    fn charge(&self, units: NonZeroU32) -> Result<(), Exceeded> {
+        let limiters = self.bandwidth.limiters(self.direction);
+        let exceeded = |scope| Exceeded {
+            direction: self.direction,
+            scope,
+        };
+        let now = Instant::now();
+
+        // Both budgets stay locked until they are charged. Peer budgets are
+        // always locked before URN budgets.
+        let peer = match &limiters.per_peer {
+            None => None,
+            Some(lim) => {
+                let budget = lim.budget(self.peer, now);
+                let charged = budget
+                    .charged(units, now)
+                    .ok_or_else(|| exceeded(Scope::Peer(self.peer)))?;
+                Some((budget, charged))
+            },
+        };
+        let urn = match (&limiters.per_urn, self.urn()) {
+            (Some(lim), Some(urn)) => {
+                let budget = lim.budget(urn.clone(), now);
+                let charged = budget
+                    .charged(units, now)
+                    .ok_or_else(|| exceeded(Scope::Urn(urn.clone())))?;
+                Some((budget, charged))
+            },
+            _ => None,
+        };
+
+        if let Some((mut budget, charged)) = peer {
+            *budget.replenished = charged;
+        }
+        if let Some((mut budget, charged)) = urn {
+            *budget.replenished = charged;
+        }
+
+        Ok(())
+    }
+}
+
+/// A stream metered by an [`Account`].
+///
+/// Reading or writing fails with an [`Exceeded`] error once the budget of the
+/// account is exhausted.
+pub struct Metered<S> {
+    inner: S,
+    account: Account,
+    /// Bytes not charged yet, less than one [`UNIT`].
+    pending: u64,
+}
+
+impl<S> Metered<S> {
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn transferred(&mut self, bytes: usize) -> io::Result<()> {
+        let bytes = bytes as u64;
+        self.account.record(bytes);
+
+        self.pending += bytes;
+        let units = self.pending / UNIT;
+        self.pending %= UNIT;
+        match NonZeroU32::new(u32::try_from(units).unwrap_or(u32::MAX)) {
+            None => Ok(()),
+            Some(units) => self.account.charge(units).map_err(|e| {
+                tracing::warn!(peer = %self.account.peer, "{}", e);
+                e.into()
+            }),
+        }
+    }
+}
+
+impl<S> AsyncRead for Metered<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        Poll::Ready(this.transferred(n).map(|()| n))
+    }
+}
+
+impl<S> AsyncWrite for Metered<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        Poll::Ready(this.transferred(n).map(|()| n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
@@ -12,8 +12,16 @@ use std::{
     net::{SocketAddr, ToSocketAddrs},
 };
 
+use futures::StreamExt as _;
+
 use crate::PeerId;
 
+pub mod book;
+pub use book::AddressBook;
+
+pub mod mdns;
+pub use mdns::Mdns;
+
 pub trait Discovery {
     type Addr;
     type Stream: futures::Stream<Item = (PeerId, Vec<Self::Addr>)> + Send;
@@ -104,6 +112,25 @@ where
     }
 }
 
+impl<I> Extend<(PeerId, I)> for Static
+where
+    I: IntoIterator<Item = SocketAddr>,
+{
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (PeerId, I)>,
+    {
+        for (peer, addrs) in iter {
+            let known = self.peers.entry(peer).or_default();
+            for addr in addrs {
+                if !known.contains(&addr) {
+                    known.push(addr)
+                }
+            }
+        }
+    }
+}
+
 impl Discovery for Static {
     type Addr = SocketAddr;
     type Stream = futures::stream::Iter<btree_map::IntoIter<PeerId, Vec<SocketAddr>>>;
@@ -112,3 +139,30 @@ impl Discovery for Static {
         futures::stream::iter(self.peers.into_iter())
     }
 }
+
+/// An optional [`Discovery`], which discovers nothing if absent.
+impl<D> Discovery for Option<D>
+where
+    D: Discovery,
+{
+    type Addr = D::Addr;
+    type Stream = futures::stream::Flatten<futures::stream::Iter<std::option::IntoIter<D::Stream>>>;
+
+    fn discover(self) -> Self::Stream {
+        futures::stream::iter(self.map(Discovery::discover)).flatten()
+    }
+}
+
+/// Both [`Discovery`]s, with their discovered peers interleaved.
+impl<A, B> Discovery for (A, B)
+where
+    A: Discovery,
+    B: Discovery<Addr = A::Addr>,
+{
+    type Addr = A::Addr;
+    type Stream = futures::stream::Select<A::Stream, B::Stream>;
+
+    fn discover(self) -> Self::Stream {
+        futures::stream::select(self.0.discover(), self.1.discover())
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A persistent address book of peers seen on the network.
+//!
+//! The membership views of the protocol only live as long as the process.
+//! The [`AddressBook`] records the peers which made it into the views, along
+//! with when they were last seen and how often a connection to them was
+//! established, so that they can be [`super::Discovery`] candidates after a
+//! restart.
+//!
+//! Entries which were not seen for [`Config::max_age`] are dropped, and the
+//! book holds at most [`Config::capacity`] entries, evicting the least
+//! successful ones first.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use minicbor::{Decode, Encode};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::{
+    net::protocol::{membership::Transition, PartialPeerInfo},
+    PeerId,
+};
+
+/// Maximum number of addresses remembered per peer.
+pub const MAX_ADDRS: usize = 8;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Persist(#[from] tempfile::PersistError),
+
+    #[error(transparent)]
+    Decode(#[from] minicbor::decode::Error),
+
+    #[error(transparent)]
+    Encode(#[from] minicbor::encode::Error<io::Error>),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Maximum number of peers to remember.
+    pub capacity: usize,
+    /// Peers not seen for this long are forgotten.
+    pub max_age: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            max_age: Duration::from_secs(30 * 24 * 60 * 60),
+        }
+    }
+}
+
+/// A peer recorded in the [`AddressBook`].
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Entry {
+    #[n(0)]
+    pub peer_id: PeerId,
+    /// Addresses the peer was reachable at, advertised listen addresses
+    /// first.
+    #[n(1)]
+    pub addrs: Vec<SocketAddr>,
+    /// Seconds since the Unix epoch at which the peer was last seen.
+    #[n(2)]
+    pub seen: u64,
+    /// The number of times the peer was promoted to the active view, ie. a
+    /// connection to it was established successfully.
+    #[n(3)]
+    pub successes: u32,
+}
+
+impl From<Entry> for (PeerId, Vec<SocketAddr>) {
+    fn from(entry: Entry) -> Self {
+        (entry.peer_id, entry.addrs)
+    }
+}
+
+impl Entry {
+    /// Ordering key, best entries first.
+    fn rank(&self) -> (Reverse<u32>, Reverse<u64>) {
+        (Reverse(self.successes), Reverse(self.seen))
+    }
+}
+
+pub struct AddressBook {
+    path: PathBuf,
+    config: Config,
+    entries: BTreeMap<PeerId, Entry>,
+    dirty: bool,
+}
+
+impl AddressBook {
+    /// Create an empty address book, persisted to the file at `path`.
+    pub fn new<P>(path: P, config: Config) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            config,
+            entries: BTreeMap::new(),
+            dirty: false,
+        }
+    }
+
+    /// Load the address book persisted to the file at `path`.
+    ///
+    /// If the file does not exist, the book is empty. Entries which have aged
+    /// beyond [`Config::max_age`] are dropped.
+    pub fn load<P>(path: P, config: Config) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let entries = match fs::read(path.as_ref()) {
+            Ok(bytes) => minicbor::decode::<Vec<Entry>>(&bytes)?
+                .into_iter()
+                .map(|entry| (entry.peer_id, entry))
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut book = Self {
+            entries,
+            ..Self::new(path, config)
+        };
+        book.age(now());
+
+        Ok(book)
+    }
+
+    /// Record a membership [`Transition`].
+    ///
+    /// Promoted and demoted peers are marked as seen, and promotions count as
+    /// a success. Evictions are not recorded: the peer may only have been
+    /// evicted to make room for another, and will age out otherwise.
+    pub fn transition(&mut self, transition: &Transition<SocketAddr>) {
+        match transition {
+            Transition::Promoted(info) => self.seen(info.clone(), true),
+            Transition::Demoted(info) => self.seen(info.clone().into(), false),
+            Transition::Evicted(_) => {},
+        }
+    }
+
+    /// Mark the peer described by `info` as seen just now.
+    pub fn seen(&mut self, info: PartialPeerInfo<SocketAddr>, success: bool) {
+        let (peer_id, addrs) = <(PeerId, Vec<SocketAddr>)>::from(info);
+        let entry = self.entries.entry(peer_id).or_insert_with(|| Entry {
+            peer_id,
+            addrs: vec![],
+            seen: 0,
+            successes: 0,
+        });
+        let mut merged = Vec::with_capacity(MAX_ADDRS);
+        for addr in addrs.into_iter().chain(entry.addrs.iter().copied()) {
+            if !merged.contains(&addr) {
+                merged.push(addr)
+            }
+        }
+        merged.truncate(MAX_ADDRS);
+
+        entry.addrs = merged;
+        entry.seen = now();
+        if success {
+            entry.successes = entry.successes.saturating_add(1);
+        }
+        self.dirty = true;
+
+        if self.entries.len() > self.config.capacity {
+            self.evict(Some(&peer_id))
+        }
+    }
+
+    /// Forget `peer`.
+    pub fn remove(&mut self, peer: &PeerId) -> Option<Entry> {
+        let removed = self.entries.remove(peer);
+        self.dirty |= removed.is_some();
+        removed
+    }
+
+    pub fn get(&self, peer: &PeerId) -> Option<&Entry> {
+        self.entries.get(peer)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The `n` best entries, ie. the most successful and most recently seen
+    /// ones.
+    ///
+    /// Those are the candidates for [`super::Discovery`], eg. by extending a
+    /// [`super::Static`] discovery with them.
+    pub fn best(&self, n: usize) -> Vec<Entry> {
+        let mut entries = self.entries.values().cloned().collect::<Vec<_>>();
+        entries.sort_by_key(Entry::rank);
+        entries.truncate(n);
+        entries
+    }
+
+    /// Write the book to its file, if it was modified since it was loaded or
+    /// last persisted.
+    pub fn persist(&mut self) -> Result<(), Error> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.age(now());
+
+        let entries = self.entries.values().collect::<Vec<_>>();
+        let bytes = minicbor::to_vec(&entries)?;
+
+        let dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+        let tmp = NamedTempFile::new_in(dir)?;
+        fs::write(tmp.path(), &bytes)?;
+        tmp.as_file().sync_data()?;
+        tmp.persist(&self.path)?;
+        self.dirty = false;
+        tracing::trace!("persisted address book to {}", self.path.display());
+
+        Ok(())
+    }
+
+    /// Drop the entries older than [`Config::max_age`], and the worst ones
+    /// exceeding [`Config::capacity`].
+    fn age(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(self.config.max_age.as_secs());
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| entry.seen >= cutoff);
+        while self.entries.len() > self.config.capacity {
+            self.evict(None)
+        }
+        self.dirty |= self.entries.len() != before;
+    }
+
+    /// Drop the worst entry, unless it is `keep`.
+    fn evict(&mut self, keep: Option<&PeerId>) {
+        let worst = self
+            .entries
+            .values()
+            .filter(|entry| Some(&entry.peer_id) != keep)
+            .max_by_key(|entry| entry.rank())
+            .map(|entry| entry.peer_id);
+        if let Some(peer) = worst {
+            self.entries.remove(&peer);
+            self.dirty = true;
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,164 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Discovery of peers on the local network via mDNS / DNS-SD.
+//!
+//! Each peer registers a service of type [`SERVICE_TYPE`], named after its
+//! [`PeerId`], with the peer id and the [`Network`] name as `TXT` properties.
+//! Services of other peers on the same network are yielded by
+//! [`Discovery::discover`].
+//!
+//! The addresses are only known once the endpoint is bound, so
+//! [`Mdns::announce`] needs to be called with the listen addresses (eg. on
+//! [`crate::net::protocol::event::upstream::Endpoint::Up`]) before other peers
+//! can discover us.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+};
+
+use futures::{future, stream::BoxStream, StreamExt as _};
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use thiserror::Error;
+
+use super::Discovery;
+use crate::{net::Network, PeerId};
+
+/// The DNS-SD service type peers are announced as.
+pub const SERVICE_TYPE: &str = "_radicle-link._udp.local.";
+
+const PEER: &str = "peer";
+const NETWORK: &str = "network";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("no listen addresses to announce")]
+    NoListenAddrs,
+
+    #[error(transparent)]
+    Mdns(#[from] mdns_sd::Error),
+}
+
+/// An mDNS responder and browser for the local peer.
+///
+/// Cloning is cheap, and all clones share the same daemon.
+#[derive(Clone)]
+pub struct Mdns {
+    daemon: ServiceDaemon,
+    local_id: PeerId,
+    network: Network,
+}
+
+impl Mdns {
+    /// Start the mDNS daemon.
+    ///
+    /// Nothing is announced until [`Mdns::announce`] is called.
+    pub fn new(local_id: PeerId, network: Network) -> Result<Self, Error> {
+        Ok(Self {
+            daemon: ServiceDaemon::new()?,
+            local_id,
+            network,
+        })
+    }
+
+    /// Announce the local peer as listening on `listen_addrs`.
+    ///
+    /// Replaces any previous announcement.
+    pub fn announce(&self, listen_addrs: &[SocketAddr]) -> Result<(), Error> {
+        let info = service(&self.local_id, &self.network, listen_addrs)?;
+        self.daemon.register(info)?;
+        Ok(())
+    }
+}
+
+impl Discovery for Mdns {
+    type Addr = SocketAddr;
+    type Stream = BoxStream<'static, (PeerId, Vec<SocketAddr>)>;
+
+    fn discover(self) -> Self::Stream {
+        let Self {
+            daemon,
+            local_id,
+            network,
+        } = self;
+        match daemon.browse(SERVICE_TYPE) {
+            Err(e) => {
+                tracing::warn!(err = %e, "failed to browse mDNS services");
+                futures::stream::empty().boxed()
+            },
+            Ok(events) => events
+                .into_stream()
+                .filter_map(move |event| {
+                    let found = match event {
+                        ServiceEvent::ServiceResolved(info) => resolve(&info, &network)
+                            .filter(|(peer, addrs)| peer != &local_id && !addrs.is_empty()),
+                        _ => None,
+                    };
+                    if let Some((peer, _)) = &found {
+                        tracing::debug!(peer = %peer, "discovered peer via mDNS");
+                    }
+                    future::ready(found)
+                })
+                .boxed(),
+        }
+    }
+}
+
+/// The service announcing `local_id` as listening on `listen_addrs` in
+/// `network`.
+///
+/// If any of the `listen_addrs` is unspecified (eg. `0.0.0.0`), the addresses
+/// of all interfaces are announced.
+pub fn service(
+    local_id: &PeerId,
+    network: &Network,
+    listen_addrs: &[SocketAddr],
+) -> Result<ServiceInfo, Error> {
+    let port = listen_addrs
+        .first()
+        .map(|addr| addr.port())
+        .ok_or(Error::NoListenAddrs)?;
+    let ips = listen_addrs
+        .iter()
+        .map(|addr| addr.ip())
+        .filter(|ip| !ip.is_unspecified())
+        .collect::<Vec<_>>();
+    let auto = ips.len() < listen_addrs.len();
+
+    let name = local_id.to_string();
+    let properties = vec![
+        (PEER.to_owned(), name.clone()),
+        (NETWORK.to_owned(), network.to_string()),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+    let info = ServiceInfo::new(
+        SERVICE_TYPE,
+        &name,
+        &format!("{}.local.", name),
+        ips.as_slice(),
+        port,
+        properties,
+    )?;
+
+    Ok(if auto { info.enable_addr_auto() } else { info })
+}
+
+/// The peer and its addresses announced by the service `info`, if it is in
+/// `network`.
+pub fn resolve(info: &ServiceInfo, network: &Network) -> Option<(PeerId, Vec<SocketAddr>)> {
+    if info.get_property_val_str(NETWORK)? != network.to_string() {
+        return None;
+    }
+    let peer = info.get_property_val_str(PEER)?.parse().ok()?;
+    let port = info.get_port();
+    let addrs = info
+        .get_addresses()
+        .iter()
+        .map(|ip: &IpAddr| SocketAddr::new(*ip, port))
+        .collect();
+
+    Some((peer, addrs))
+}
@@ -11,6 +11,7 @@ use link_async::Spawner;
 use crate::{
     git::{self, identities::local::LocalIdentity, Urn},
     net::{
+        bandwidth::Bandwidth,
         protocol::{self, gossip, TinCans},
         replication::{self, Replication},
     },
@@ -96,6 +97,7 @@ pub struct Peer<S, G = config::DenyAll> {
     caches: protocol::Caches,
     spawner: Arc<Spawner>,
     repl: Replication,
+    bandwidth: Bandwidth,
 }
 
 impl<S, G> Peer<S, G>
@@ -121,10 +123,16 @@ where
             let store = git::storage::Storage::open(&config.protocol.paths, config.signer.clone())?;
             let phone = phone.clone();
             let urns = protocol::cache::urns::Filter::new(store, move |ev| phone.emit(ev))?;
-            protocol::Caches { urns }
+            let providers = protocol::cache::providers::Providers::load(
+                config.protocol.paths.providers_file(),
+                protocol::cache::providers::DEFAULT_CAPACITY,
+            )?;
+            protocol::Caches { urns, providers }
         };
 
-        let repl = Replication::new(&config.protocol.paths, config.protocol.replication)?;
+        let bandwidth = Bandwidth::new(config.protocol.rate_limits.bandwidth.clone());
+        let repl = Replication::new(&config.protocol.paths, config.protocol.replication)?
+            .with_bandwidth(bandwidth.clone());
 
         let peer_store = PeerStorage::new(
             storage::Config {
@@ -132,7 +140,7 @@ where
             },
             spawner.clone(),
             pool,
-            caches.urns.clone(),
+            caches.clone(),
             repl.clone(),
             phone.clone(),
         );
@@ -153,6 +161,7 @@ where
             caches,
             spawner,
             repl,
+            bandwidth,
         })
     }
 
@@ -173,7 +182,11 @@ where
             user_storage: self.user_store.clone().into(),
             ..self.config.clone().into()
         };
-        Client::new(config, self.spawner.clone(), self.phone.clone())
+        Client::new(config, self.spawner.clone(), self.phone.clone()).map(|client| {
+            client
+                .with_events(self.phone.clone())
+                .with_bandwidth(self.bandwidth.clone())
+        })
     }
 
     pub fn announce(&self, have: gossip::Payload) -> Result<(), gossip::Payload> {
@@ -184,13 +197,30 @@ where
         self.phone.query(want)
     }
 
+    /// Find peers which provide `urn`.
+    ///
+    /// A query for `urn` is sent to the network, and the providers are
+    /// yielded as their responses arrive, until `timeout` elapses.
+    ///
+    /// If `cached_first` is `true`, the providers remembered in the provider
+    /// cache are yielded before any network responses. Those providers may be
+    /// yielded again if they respond to the query. Providers responding to the
+    /// query are remembered in the provider cache.
     pub fn providers(
         &self,
         urn: Urn,
         timeout: Duration,
+        cached_first: bool,
     ) -> impl futures::Stream<Item = PeerInfo<SocketAddr>> {
         use protocol::event::{upstream::Gossip, Upstream};
 
+        let cached = if cached_first {
+            self.caches.providers.get(&urn)
+        } else {
+            vec![]
+        };
+        let cached = futures::stream::iter(cached.into_iter().map(PeerInfo::from));
+
         let events = self.subscribe();
         let providers = futures::stream::select(
             futures::stream::once(async move {
@@ -199,6 +229,7 @@ where
             }),
             {
                 let urn = urn.clone();
+                let cache = self.caches.providers.clone();
                 events
                     .map_err(|_| "network reconnect")
                     .try_filter_map(move |event| {
@@ -216,6 +247,10 @@ where
                             },
                             _ => None,
                         };
+                        if let Some(provider) = &provider {
+                            let (peer_id, addrs) = provider.clone().into();
+                            cache.insert(&urn, peer_id, addrs);
+                        }
                         future::ok(provider)
                     })
             },
@@ -228,8 +263,8 @@ where
             rev: None,
             origin: None,
         }) {
-            Ok(()) => providers.boxed(),
-            Err(_) => futures::stream::empty().boxed(),
+            Ok(()) => cached.chain(providers).boxed(),
+            Err(_) => cached.boxed(),
         }
     }
 
@@ -305,10 +340,16 @@ where
             .await
             .ok_or(error::Replicate::NoConnection(remote_peer))?;
         let store = self.user_store.get().await?;
-        self.repl
-            .replicate(&self.spawner, store, conn, urn, whoami)
-            .err_into()
-            .await
+        let success = self
+            .repl
+            .replicate(&self.spawner, store, conn, urn.clone(), whoami)
+            .await?;
+        self.phone.emit(protocol::event::upstream::Replicated::new(
+            remote_peer,
+            urn,
+            &success,
+        ));
+        Ok(success)
     }
 
     // TODO: Augment `Connected` such that we can provide an alternative API,
@@ -334,6 +375,21 @@ where
         Ok(self.spawner.blocking(move || blocking(&storage)).await)
     }
 
+    /// Run [`git::storage::maintenance`] on the monorepo, and reload the pack
+    /// caches used for replication afterwards.
+    pub async fn maintenance(
+        &self,
+        opts: git::storage::maintenance::Options,
+    ) -> Result<git::storage::gc::Stats, error::Maintenance> {
+        let repl = self.repl.clone();
+        self.using_storage(move |storage| -> Result<_, error::Maintenance> {
+            let stats = git::storage::maintenance::run(storage, &opts)?;
+            repl.reload().map_err(error::Maintenance::Reload)?;
+            Ok(stats)
+        })
+        .await?
+    }
+
     /// Borrow a [`git::storage::ReadOnly`] from the pool, and run a blocking
     /// computation on it.
     pub async fn using_read_only<F, T>(&self, blocking: F) -> Result<T, error::Storage>
@@ -380,6 +436,8 @@ where
             self.config.signer.clone(),
             self.peer_store.clone(),
             self.caches.clone(),
+            self.bandwidth.clone(),
+            self.repl.clone(),
         )
         .await
     }
@@ -38,6 +38,9 @@ pub enum Init {
     #[error(transparent)]
     Cache(#[from] Box<cache::urns::Error>),
 
+    #[error(transparent)]
+    Providers(#[from] cache::providers::Error),
+
     #[error(transparent)]
     Replication(#[from] replication::error::Init),
 }
@@ -60,6 +63,18 @@ pub enum Replicate {
     Replicate(#[from] replication::error::Replicate),
 }
 
+#[derive(Debug, Error)]
+pub enum Maintenance {
+    #[error(transparent)]
+    Storage(#[from] Storage),
+
+    #[error(transparent)]
+    Maintenance(#[from] storage::maintenance::Error),
+
+    #[error("failed to reload packs")]
+    Reload(#[source] link_replication::Error),
+}
+
 #[derive(Debug, Error)]
 #[error("unable to obtain connection to {0}")]
 pub struct NoConnection(pub PeerId);
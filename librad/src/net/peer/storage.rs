@@ -20,7 +20,7 @@ use crate::{
     },
     identities::urn,
     net::{
-        protocol::{broadcast, cache, gossip, Connected, TinCans},
+        protocol::{broadcast, cache, event::upstream, gossip, Connected, PeerInfo, TinCans},
         replication::{self, Replication},
     },
     rate_limit::{Keyed, RateLimiter},
@@ -39,6 +39,7 @@ pub struct Config {
 pub struct Storage {
     pool: Pool<storage::Storage>,
     urns: cache::urns::Filter,
+    providers: cache::providers::Providers,
     rate: Arc<RateLimiter<Keyed<(PeerId, Urn)>>>,
     exec: Arc<Spawner>,
     repl: Replication,
@@ -50,13 +51,15 @@ impl Storage {
         conf: Config,
         exec: Arc<Spawner>,
         pool: Pool<storage::Storage>,
-        urns: cache::urns::Filter,
+        caches: cache::Caches,
         repl: Replication,
         tins: TinCans,
     ) -> Self {
+        let cache::Caches { urns, providers } = caches;
         Self {
             pool,
             urns,
+            providers,
             rate: Arc::new(RateLimiter::keyed(
                 conf.fetch_quota,
                 nonzero!(256 * 1024usize),
@@ -94,10 +97,14 @@ impl Storage {
         match self.tins.connect(from).await {
             None => Err(Error::NoConnection { remote_peer }),
             Some(Connected(conn)) => {
-                self.repl
-                    .replicate(&self.exec, git, conn, urn, None)
+                let success = self
+                    .repl
+                    .replicate(&self.exec, git, conn, urn.clone(), None)
                     .err_into::<Error>()
-                    .await
+                    .await?;
+                self.tins
+                    .emit(upstream::Replicated::new(remote_peer, urn, &success));
+                Ok(success)
             },
         }
     }
@@ -213,7 +220,7 @@ impl broadcast::LocalStorage<SocketAddr> for Storage {
             let head = has.rev.as_ref().map(|gossip::Rev::Git(head)| *head);
 
             match self
-                .git_fetch((provider, addr_hints), urn.clone(), head)
+                .git_fetch((provider, addr_hints.clone()), urn.clone(), head)
                 .await
             {
                 Ok(_) => {
@@ -228,6 +235,7 @@ impl broadcast::LocalStorage<SocketAddr> for Storage {
                     // still not there. In this case, returning `Stale` will
                     // just terminate the broadcast here.
                     if self.git_has(urn, head).await {
+                        self.providers.insert(&has.urn, provider, addr_hints);
                         PutResult::Applied(gossip::Payload {
                             origin: Some(origin),
                             ..has
@@ -243,7 +251,10 @@ impl broadcast::LocalStorage<SocketAddr> for Storage {
                 },
 
                 Err(e) => match e {
-                    Error::KnownObject(_) => PutResult::Stale,
+                    Error::KnownObject(_) => {
+                        self.providers.insert(&has.urn, provider, addr_hints);
+                        PutResult::Stale
+                    },
                     Error::RateLimited { remote_peer, urn } => {
                         tracing::warn!(
                             "skipped fetch of {} from {} due to rate limiting",
@@ -259,6 +270,10 @@ impl broadcast::LocalStorage<SocketAddr> for Storage {
                 },
             }
         } else {
+            // Remember the provider even if we don't have the URN, so we can
+            // point others to it. The cache is bounded, so it can't be
+            // filled beyond its capacity.
+            self.providers.insert(&has.urn, provider, addr_hints);
             PutResult::Uninteresting
         }
     }
@@ -277,6 +292,23 @@ impl broadcast::LocalStorage<SocketAddr> for Storage {
         )
         .await
     }
+
+    /// Providers are remembered per URN only, so we can answer on their behalf
+    /// only if no particular revision is wanted.
+    fn providers(&self, want: &Self::Update) -> Vec<PeerInfo<SocketAddr>> {
+        if want.rev.is_some() {
+            return vec![];
+        }
+        self.providers
+            .get(&want.urn)
+            .into_iter()
+            .filter(|provider| {
+                want.origin
+                    .map_or(true, |origin| origin == provider.peer_id)
+            })
+            .map(PeerInfo::from)
+            .collect()
+    }
 }
 
 #[async_trait]
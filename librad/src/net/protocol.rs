@@ -16,6 +16,7 @@ use tracing::Instrument as _;
 
 pub use super::quic::SendOnly;
 use super::{
+    bandwidth::Bandwidth,
     connection::{LocalAddr, LocalPeer},
     quic,
     upgrade,
@@ -38,6 +39,7 @@ pub use cache::Caches;
 pub mod error;
 pub mod event;
 pub mod gossip;
+pub mod graft;
 pub mod interrogation;
 pub mod io;
 pub mod membership;
@@ -179,6 +181,8 @@ pub async fn bind<Sign, Store, Guard>(
     signer: Sign,
     storage: Store,
     caches: cache::Caches,
+    bandwidth: Bandwidth,
+    repl: replication::Replication,
 ) -> Result<Bound<Store, Guard>, error::Bootstrap>
 where
     Sign: Signer + Clone + Send + Sync + 'static,
@@ -204,10 +208,11 @@ where
         (),
     );
     let request_pull = request_pull::State::new(
-        Storage::new(storage, config.rate_limits.storage),
-        config.paths.clone(),
+        Storage::new(storage.clone(), config.rate_limits.storage.clone()),
+        repl.clone(),
         config.request_pull,
     );
+    let graft = graft::State::new(Storage::new(storage, config.rate_limits.storage), repl);
     let limits = RateLimits {
         membership: Arc::new(RateLimiter::keyed(
             config.rate_limits.membership,
@@ -221,6 +226,7 @@ where
         membership,
         gossip,
         request_pull,
+        graft,
         phone: phone.clone(),
         config: StateConfig {
             paths: Arc::new(config.paths),
@@ -228,6 +234,7 @@ where
         caches,
         spawner,
         limits,
+        bandwidth,
     };
 
     Ok(Bound {
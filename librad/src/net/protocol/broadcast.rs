@@ -24,6 +24,9 @@ pub use metrics::Metrics;
 mod storage;
 pub use storage::{LocalStorage, PutResult};
 
+/// Maximum number of providers to answer a [`Message::Want`] on behalf of.
+const MAX_CACHED_PROVIDERS: usize = 3;
+
 #[derive(Clone, Debug, PartialEq, minicbor::Encode, minicbor::Decode)]
 pub enum Message<Addr, Payload> {
     #[n(0)]
@@ -376,14 +379,29 @@ where
                     broadcast(reply, Some(remote_id))
                 }
             } else {
-                broadcast(
+                // Answer on behalf of the providers we know of. The answer
+                // goes only to the peer we received the want from -- we can't
+                // vouch for the providers, so the want is forwarded all the
+                // same.
+                let mut tocks = storage
+                    .providers(&val)
+                    .into_iter()
+                    .filter(|provider| provider.peer_id != origin.peer_id)
+                    .take(MAX_CACHED_PROVIDERS)
+                    .map(|provider| SendConnected {
+                        to: remote_id,
+                        message: Message::have(provider, val.clone()).into(),
+                    })
+                    .collect::<Vec<_>>();
+                tocks.extend(broadcast(
                     Want {
                         origin,
                         val,
                         ext: Some(ext.unwrap_or_default().next_hop()),
                     },
                     Some(remote_id),
-                )
+                ));
+                tocks
             };
 
             Ok((None, tocks))
@@ -3,7 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use crate::PeerId;
+use crate::{net::protocol::PeerInfo, PeerId};
 
 /// Result of applying a broadcast update to local storage.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -58,4 +58,13 @@ pub trait LocalStorage<Addr>: Clone + Send + Sync {
     /// This is used to notify the asking peer that they may fetch value `A`
     /// from us.
     async fn ask(&self, want: Self::Update) -> bool;
+
+    /// Ask the local storage for other peers known to provide value `A`.
+    ///
+    /// This is consulted if [`LocalStorage::ask`] returned `false`, so as to
+    /// answer the asking peer on behalf of the providers. The default
+    /// implementation doesn't know of any providers.
+    fn providers(&self, _want: &Self::Update) -> Vec<PeerInfo<Addr>> {
+        vec![]
+    }
 }
@@ -24,6 +24,7 @@ use crate::{
 #[derive(Clone)]
 pub struct Caches {
     pub urns: urns::Filter,
+    pub providers: providers::Providers,
 }
 
 pub mod urns {
@@ -217,3 +218,320 @@ pub mod urns {
         identities::any::xor_filter(&storage).map(|res| (FilterInner::from(res), start.elapsed()))
     }
 }
+
+/// Providers of URNs, as learned from gossip
+/// [`super::broadcast::Message::Have`]s.
+///
+/// For every URN, up to [`K`] providers are remembered, evicting the least
+/// recently seen one when the bucket is full. The total number of URNs is
+/// bounded, too: when exceeded, the URN whose providers were seen the longest
+/// time ago is dropped.
+///
+/// The cache can optionally be persisted to a file, in which case it is loaded
+/// when created, written back periodically if modified, and when the last
+/// handle to it is dropped.
+///
+/// [`K`]: providers::K
+pub mod providers {
+    use std::{
+        collections::BTreeMap,
+        fs,
+        io,
+        net::SocketAddr,
+        path::{Path, PathBuf},
+        sync::Weak,
+        time::SystemTime,
+    };
+
+    use data::BoundedVec;
+    use minicbor::{Decode, Encode};
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::{
+        git::Urn,
+        net::protocol::{PeerAdvertisement, PeerInfo},
+        PeerId,
+    };
+
+    /// Maximum number of providers remembered per URN.
+    pub const K: usize = 8;
+
+    /// Default maximum number of URNs to remember providers for.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Interval at which a modified cache is written to disk.
+    pub const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+    #[derive(Debug, Error)]
+    #[non_exhaustive]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] io::Error),
+
+        #[error(transparent)]
+        Persist(#[from] tempfile::PersistError),
+
+        #[error(transparent)]
+        Decode(#[from] minicbor::decode::Error),
+
+        #[error(transparent)]
+        Encode(#[from] minicbor::encode::Error<io::Error>),
+    }
+
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Stats {
+        /// The number of URNs providers are known for.
+        pub urns: usize,
+        /// The total number of providers.
+        pub providers: usize,
+    }
+
+    /// A peer which announced to have a URN.
+    #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+    #[cbor(array)]
+    pub struct Provider {
+        #[n(0)]
+        pub peer_id: PeerId,
+        /// Addresses the provider is reachable at, according to the
+        /// announcement.
+        #[n(1)]
+        pub addrs: Vec<SocketAddr>,
+        /// Seconds since the Unix epoch at which the announcement was received.
+        #[n(2)]
+        pub seen: u64,
+    }
+
+    impl From<Provider> for PeerInfo<SocketAddr> {
+        fn from(Provider { peer_id, addrs, .. }: Provider) -> Self {
+            let mut listen_addrs = BoundedVec::from(std::iter::empty());
+            listen_addrs.extend_fill(addrs);
+            Self {
+                peer_id,
+                advertised_info: PeerAdvertisement {
+                    listen_addrs,
+                    capabilities: Default::default(),
+                },
+                seen_addrs: BoundedVec::from(std::iter::empty()),
+            }
+        }
+    }
+
+    #[derive(Encode, Decode)]
+    #[cbor(array)]
+    struct Entry {
+        #[n(0)]
+        urn: Urn,
+        #[n(1)]
+        providers: Vec<Provider>,
+    }
+
+    #[derive(Clone)]
+    pub struct Providers {
+        inner: Arc<RwLock<ProvidersInner>>,
+        _persist: Option<Arc<Persist>>,
+    }
+
+    struct ProvidersInner {
+        capacity: usize,
+        /// Providers per URN, most recently seen first.
+        buckets: BTreeMap<Urn, Vec<Provider>>,
+        dirty: bool,
+    }
+
+    impl Providers {
+        /// Create an empty, in-memory cache, remembering providers for at most
+        /// `capacity` URNs.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                inner: Arc::new(RwLock::new(ProvidersInner {
+                    capacity,
+                    buckets: BTreeMap::new(),
+                    dirty: false,
+                })),
+                _persist: None,
+            }
+        }
+
+        /// Create a cache which is persisted to the file at `path`.
+        ///
+        /// If the file exists, the cache is initialised from its contents.
+        pub fn load<P>(path: P, capacity: usize) -> Result<Self, Error>
+        where
+            P: AsRef<Path>,
+        {
+            let path = path.as_ref().to_path_buf();
+            let buckets = match fs::read(&path) {
+                Ok(bytes) => minicbor::decode::<Vec<Entry>>(&bytes)?
+                    .into_iter()
+                    .map(|Entry { urn, providers }| (urn, providers))
+                    .collect(),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+                Err(e) => return Err(e.into()),
+            };
+            let mut inner = ProvidersInner {
+                capacity,
+                buckets,
+                dirty: false,
+            };
+            while inner.buckets.len() > capacity {
+                inner.evict(None)
+            }
+
+            let inner = Arc::new(RwLock::new(inner));
+            let persist = Arc::new(Persist {
+                path,
+                providers: Arc::clone(&inner),
+            });
+            thread::spawn({
+                let persist = Arc::downgrade(&persist);
+                move || persist_thread(persist)
+            });
+
+            Ok(Self {
+                inner,
+                _persist: Some(persist),
+            })
+        }
+
+        /// Remember `peer_id` as a provider of `urn`.
+        ///
+        /// The path of `urn` is ignored.
+        pub fn insert(&self, urn: &Urn, peer_id: PeerId, addrs: Vec<SocketAddr>) {
+            let urn = urn.clone().with_path(None);
+            let mut inner = self.inner.write();
+            let is_new = !inner.buckets.contains_key(&urn);
+            let bucket = inner.buckets.entry(urn.clone()).or_default();
+            bucket.retain(|provider| provider.peer_id != peer_id);
+            bucket.insert(
+                0,
+                Provider {
+                    peer_id,
+                    addrs,
+                    seen: now(),
+                },
+            );
+            bucket.truncate(K);
+            if is_new && inner.buckets.len() > inner.capacity {
+                inner.evict(Some(&urn))
+            }
+            inner.dirty = true;
+        }
+
+        /// Forget `peer_id` as a provider of `urn`.
+        pub fn remove(&self, urn: &Urn, peer_id: &PeerId) {
+            let urn = urn.clone().with_path(None);
+            let mut inner = self.inner.write();
+            if let Some(bucket) = inner.buckets.get_mut(&urn) {
+                bucket.retain(|provider| &provider.peer_id != peer_id);
+                if bucket.is_empty() {
+                    inner.buckets.remove(&urn);
+                }
+                inner.dirty = true;
+            }
+        }
+
+        /// The known providers of `urn`, most recently seen first.
+        ///
+        /// The path of `urn` is ignored.
+        pub fn get(&self, urn: &Urn) -> Vec<Provider> {
+            self.inner
+                .read()
+                .buckets
+                .get(&urn.clone().with_path(None))
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        pub fn stats(&self) -> Stats {
+            let inner = self.inner.read();
+            Stats {
+                urns: inner.buckets.len(),
+                providers: inner.buckets.values().map(Vec::len).sum(),
+            }
+        }
+    }
+
+    impl ProvidersInner {
+        /// Drop the URN whose most recently seen provider is the oldest, unless
+        /// it is `keep`.
+        fn evict(&mut self, keep: Option<&Urn>) {
+            let oldest = self
+                .buckets
+                .iter()
+                .filter(|(urn, _)| Some(*urn) != keep)
+                .min_by_key(|(_, bucket)| bucket.first().map(|p| p.seen).unwrap_or(0))
+                .map(|(urn, _)| urn.clone());
+            if let Some(urn) = oldest {
+                self.buckets.remove(&urn);
+            }
+        }
+    }
+
+    struct Persist {
+        path: PathBuf,
+        providers: Arc<RwLock<ProvidersInner>>,
+    }
+
+    impl Persist {
+        fn persist(&self) -> Result<(), Error> {
+            let entries = {
+                let mut inner = self.providers.write();
+                if !inner.dirty {
+                    return Ok(());
+                }
+                inner.dirty = false;
+                inner
+                    .buckets
+                    .iter()
+                    .map(|(urn, providers)| Entry {
+                        urn: urn.clone(),
+                        providers: providers.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let bytes = minicbor::to_vec(&entries)?;
+
+            let dir = self.path.parent().unwrap_or_else(|| Path::new("."));
+            let tmp = NamedTempFile::new_in(dir)?;
+            fs::write(tmp.path(), &bytes)?;
+            tmp.as_file().sync_data()?;
+            tmp.persist(&self.path)?;
+            tracing::trace!("persisted provider cache to {}", self.path.display());
+
+            Ok(())
+        }
+    }
+
+    impl Drop for Persist {
+        fn drop(&mut self) {
+            if let Err(e) = self.persist() {
+                tracing::warn!(err = ?e, "error persisting provider cache")
+            }
+        }
+    }
+
+    fn persist_thread(persist: Weak<Persist>) {
+        let span = tracing::info_span!("persist-providers");
+        let _guard = span.enter();
+
+        loop {
+            thread::sleep(PERSIST_INTERVAL);
+            match persist.upgrade() {
+                None => break,
+                Some(persist) => {
+                    if let Err(e) = persist.persist() {
+                        tracing::warn!(err = ?e, "error persisting provider cache")
+                    }
+                },
+            }
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
@@ -93,7 +93,9 @@ where
                     membership_passive: passive,
                     caches: CacheStats {
                         urns: state.caches.urns.stats(),
+                        providers: state.caches.providers.stats(),
                     },
+                    bandwidth: state.bandwidth.stats(),
                 })
                 .ok();
             }
@@ -6,7 +6,7 @@
 use std::{collections::HashMap, net::SocketAddr};
 
 use super::{broadcast, cache, error, gossip, interrogation, membership, quic, request_pull};
-use crate::PeerId;
+use crate::{git::Urn, net::bandwidth, PeerId};
 
 #[derive(Clone)]
 pub enum Downstream {
@@ -63,11 +63,13 @@ pub mod downstream {
         pub membership_active: usize,
         pub membership_passive: usize,
         pub caches: CacheStats,
+        pub bandwidth: bandwidth::Stats,
     }
 
     #[derive(Clone, Copy, Debug, Default)]
     pub struct CacheStats {
         pub urns: cache::urns::Stats,
+        pub providers: cache::providers::Stats,
     }
 
     #[derive(Clone)]
@@ -100,6 +102,8 @@ pub enum Upstream {
     Gossip(Box<upstream::Gossip<SocketAddr, gossip::Payload>>),
     Membership(membership::Transition<SocketAddr>),
     Caches(upstream::Caches),
+    RequestPull(upstream::RequestPull),
+    Replicated(upstream::Replicated),
 }
 
 pub mod upstream {
@@ -110,7 +114,10 @@ pub mod upstream {
     use futures::{pin_mut, FutureExt as _, StreamExt as _};
     use thiserror::Error;
 
-    use crate::net::protocol::{PeerInfo, RecvError};
+    use crate::net::{
+        protocol::{PeerInfo, RecvError},
+        replication,
+    };
 
     #[derive(Clone, Debug)]
     pub enum Endpoint {
@@ -149,6 +156,51 @@ pub mod upstream {
         }
     }
 
+    /// Triggered after a request-pull from `peer` was served successfully.
+    #[derive(Clone, Debug)]
+    pub struct RequestPull {
+        /// The peer who made the request
+        pub peer: PeerId,
+        /// The URN which was replicated from `peer`
+        pub urn: Urn,
+        /// The refs which were updated as a result of the request-pull
+        pub success: request_pull::Success,
+    }
+
+    impl From<RequestPull> for Upstream {
+        fn from(rp: RequestPull) -> Self {
+            Self::RequestPull(rp)
+        }
+    }
+
+    /// Triggered after `urn` was replicated from `peer` successfully, whether
+    /// due to gossip, a request-pull, a graft, or an explicit request.
+    #[derive(Clone, Debug)]
+    pub struct Replicated {
+        /// The peer replicated from
+        pub peer: PeerId,
+        /// The URN which was replicated
+        pub urn: Urn,
+        /// The refs which were updated as a result of the replication
+        pub updated: Vec<replication::Updated>,
+    }
+
+    impl Replicated {
+        pub fn new(peer: PeerId, urn: Urn, success: &replication::Success) -> Self {
+            Self {
+                peer,
+                urn,
+                updated: success.updated_refs().to_vec(),
+            }
+        }
+    }
+
+    impl From<Replicated> for Upstream {
+        fn from(r: Replicated) -> Self {
+            Self::Replicated(r)
+        }
+    }
+
     #[derive(Clone, Debug)]
     #[non_exhaustive]
     pub enum Caches {
@@ -213,6 +265,15 @@ pub mod upstream {
             }
         }
 
+        pub fn replicated_from(peer: PeerId, urn: Urn) -> impl Fn(&Upstream) -> bool {
+            move |event| match event {
+                Upstream::Replicated(replicated) => {
+                    replicated.peer == peer && replicated.urn == urn
+                },
+                _ => false,
+            }
+        }
+
         /// Wait for cache `Rebuilt` events where the new length matches the
         /// predicate.
         pub fn urn_cache_len<P>(cmp: P) -> impl Fn(&Upstream) -> bool
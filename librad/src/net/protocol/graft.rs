@@ -0,0 +1,325 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Mutual synchronisation of two connected peers, aka "grafting".
+//!
+//! This is a pull-based take on [RFC 701][rfc]: instead of pushing packfiles,
+//! the two peers tell each other what they have, and each side fetches from
+//! the other what it is missing.
+//!
+//! 1. The initiator sends [`Request::Filter`], containing an [`Xor`] filter of
+//!    the URNs it has.
+//! 2. The receiver responds with [`Response::Offer`], containing its own
+//!    filter, and the [`Tip`]s of the URNs which are (probably) in the
+//!    initiator's filter.
+//! 3. The initiator sends [`Request::Tips`], containing its [`Tip`]s of the
+//!    URNs which are (probably) in the receiver's filter.
+//! 4. Both sides now fetch from the other end all URNs they track for which at
+//!    least one tip of a tracked peer is missing locally.
+//! 5. Once it is done fetching, the receiver sends [`Response::Done`],
+//!    containing a per-URN [`Report`] of what it fetched.
+//!
+//! [rfc]: https://github.com/radicle-dev/radicle-link/blob/master/docs%2Frfc%2F0701-mutual-synchronisation.adoc
+
+use std::collections::BTreeSet;
+
+use futures::future::FutureExt as _;
+use link_async::Spawner;
+use thiserror::Error;
+
+use crate::{
+    git::{
+        identities,
+        storage::{self, PoolError, ReadOnlyStorage as _},
+        tracking,
+        types::{Namespace, Reference},
+        Urn,
+    },
+    identities::{
+        xor::{self, Xor},
+        SomeUrn,
+    },
+    net::{connection::RemotePeer as _, quic, replication},
+};
+
+use super::{event::upstream, interrogation, TinCans};
+
+mod rpc;
+pub use rpc::{Error, Offer, Outcome, Ref, Report, Request, Response, Synced, Tip};
+
+/// Maximum number of [`Tip`]s sent in a single message.
+///
+/// Tips in excess of this limit are not advertised, and thus not synchronised
+/// in the respective session.
+pub const MAX_TIPS: usize = 1024;
+
+/// Buffer size for writing and reading graft messages.
+///
+/// Both [`Request::Filter`] and [`Response::Offer`] contain an [`Xor`] filter,
+/// and the latter additionally up to [`MAX_TIPS`] tips of roughly 128 bytes
+/// each.
+pub const FRAMED_BUFSIZ: usize = interrogation::FRAMED_BUFSIZ + MAX_TIPS * 128;
+
+/// The outcome of a graft session, as seen by the initiator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grafted {
+    /// What we fetched from the remote peer.
+    pub fetched: Report,
+    /// What the remote peer fetched from us.
+    pub remote: Report,
+}
+
+/// State for serving graft requests.
+#[derive(Clone)]
+pub struct State<S> {
+    storage: S,
+    repl: replication::Replication,
+}
+
+impl<S> State<S> {
+    pub fn new(storage: S, repl: replication::Replication) -> Self {
+        Self { storage, repl }
+    }
+}
+
+pub mod error {
+    use super::*;
+
+    #[derive(Debug, Error)]
+    #[non_exhaustive]
+    pub enum Tips {
+        #[error(transparent)]
+        Identities(#[from] identities::Error),
+        #[error(transparent)]
+        Filter(#[from] xor::BuildError<identities::Error>),
+        #[error(transparent)]
+        Store(#[from] storage::read::Error),
+        #[error(transparent)]
+        Tracked(#[from] tracking::error::TrackedPeers),
+    }
+
+    #[derive(Debug, Error)]
+    #[non_exhaustive]
+    pub enum Wants {
+        #[error(transparent)]
+        Store(#[from] storage::read::Error),
+        #[error(transparent)]
+        IsTracked(#[from] tracking::error::IsTracked),
+        #[error(transparent)]
+        DefaultOnly(#[from] tracking::error::DefaultOnly),
+    }
+
+    #[derive(Debug, Error)]
+    #[non_exhaustive]
+    pub enum Fetch {
+        #[error("could not get handle to storage")]
+        Pool(#[from] PoolError),
+        #[error(transparent)]
+        Tips(#[from] Tips),
+        #[error(transparent)]
+        Wants(#[from] Wants),
+    }
+
+    pub fn decode_failed() -> Error {
+        Error {
+            message: "failed to decode request".into(),
+        }
+    }
+
+    pub fn unexpected_request() -> Error {
+        Error {
+            message: "unexpected request".into(),
+        }
+    }
+
+    pub fn internal_error() -> Error {
+        Error {
+            message: "internal error".into(),
+        }
+    }
+}
+
+/// Compute the [`Tip`]s of all URNs in `storage` which are (probably) in
+/// `filter`.
+///
+/// For each URN, the tip of the local `rad/signed_refs` is included, as well as
+/// the tips of the `rad/signed_refs` of all tracked peers. At most
+/// [`MAX_TIPS`] tips are returned.
+pub fn tips(storage: &storage::Storage, filter: &Xor) -> Result<Vec<Tip>, error::Tips> {
+    let local_id = *storage.peer_id();
+    let mut tips = Vec::new();
+    for urn in identities::any::list_urns(storage)? {
+        let urn = urn?;
+        if !filter.contains(&SomeUrn::from(urn.clone())) {
+            continue;
+        }
+
+        let remotes = tracking::tracked_peers(storage, Some(&urn))?;
+        for peer in std::iter::once(Ok(local_id)).chain(remotes) {
+            let peer = peer?;
+            let remote = (peer != local_id).then(|| peer);
+            let sigrefs = Reference::rad_signed_refs(Namespace::from(&urn), remote);
+            if let Some(oid) = storage.reference(&sigrefs)?.and_then(|r| r.target()) {
+                if tips.len() == MAX_TIPS {
+                    tracing::warn!("exceeded maximum number of tips, truncating");
+                    return Ok(tips);
+                }
+                tips.push(Tip {
+                    urn: urn.clone(),
+                    peer,
+                    oid: oid.into(),
+                })
+            }
+        }
+    }
+
+    Ok(tips)
+}
+
+/// Determine the URNs we want to fetch, given the remote end's `tips`.
+///
+/// A URN is wanted if at least one of the `tips` belongs to a peer we track for
+/// the URN, and the tip is not present in `storage`. Tips of our own
+/// `rad/signed_refs` are ignored.
+pub fn wants(storage: &storage::Storage, tips: &[Tip]) -> Result<BTreeSet<Urn>, error::Wants> {
+    let local_id = *storage.peer_id();
+    let mut wants = BTreeSet::new();
+    for Tip { urn, peer, oid } in tips {
+        if *peer == local_id || wants.contains(urn) {
+            continue;
+        }
+        let tracked = tracking::is_tracked(storage, urn, Some(*peer))?
+            || tracking::default_only(storage, urn)?;
+        if tracked && !storage.has_object(oid)? {
+            wants.insert(urn.clone());
+        }
+    }
+
+    Ok(wants)
+}
+
+/// Fetch the URNs we [`wants`] from the other end of `conn`.
+///
+/// The returned [`Report`] contains an entry for each distinct URN of `tips`.
+/// Failing to fetch a particular URN does not abort the remaining fetches, but
+/// is reported as [`Outcome::Failed`].
+///
+/// If `events` is given, an [`upstream::Replicated`] event is emitted for each
+/// URN replicated successfully.
+pub(in crate::net) async fn fetch<S>(
+    spawner: &Spawner,
+    repl: &replication::Replication,
+    storage: &S,
+    conn: &quic::Connection,
+    tips: Vec<Tip>,
+    events: Option<&TinCans>,
+) -> Result<Report, error::Fetch>
+where
+    S: storage::Pooled<storage::Storage> + Send + Sync,
+{
+    let urns = tips
+        .iter()
+        .map(|tip| tip.urn.clone())
+        .collect::<BTreeSet<_>>();
+    let wanted = {
+        let store = storage.get().await?;
+        spawner.blocking(move || wants(&store, &tips)).await?
+    };
+
+    let mut report = Report::default();
+    for urn in urns {
+        let outcome = if wanted.contains(&urn) {
+            let store = storage.get().await?;
+            match repl
+                .replicate(spawner, store, conn.clone(), urn.clone(), None)
+                .await
+            {
+                Ok(success) => {
+                    if let Some(phone) = events {
+                        phone.emit(upstream::Replicated::new(
+                            conn.remote_peer_id(),
+                            urn.clone(),
+                            &success,
+                        ));
+                    }
+                    let store = storage.get().await?;
+                    spawner
+                        .blocking(move || updated(&store, &success))
+                        .map(|refs| refs.map(Outcome::Replicated))
+                        .await
+                        .unwrap_or_else(|e| Outcome::Failed(e.to_string()))
+                },
+                Err(e) => {
+                    tracing::warn!(urn = %urn, err = ?e, "graft replication error");
+                    Outcome::Failed(e.to_string())
+                },
+            }
+        } else {
+            Outcome::UpToDate
+        };
+        report.synced.push(Synced { urn, outcome });
+    }
+
+    Ok(report)
+}
+
+fn updated(
+    storage: &storage::Storage,
+    success: &replication::Success,
+) -> Result<Vec<Ref>, storage::read::Error> {
+    use link_replication::Updated;
+
+    success
+        .updated_refs()
+        .iter()
+        .filter_map(|up| match up {
+            Updated::Direct { name, target, .. } => Some(Ok(Ref {
+                name: name.clone(),
+                oid: (*target).into(),
+            })),
+            Updated::Symbolic { name, target } => {
+                Some(storage.reference_oid(target).map(|oid| Ref {
+                    name: name.clone(),
+                    oid,
+                }))
+            },
+            Updated::Prune { .. } => None,
+        })
+        .collect()
+}
+
+impl<S> State<S>
+where
+    S: storage::Pooled<storage::Storage> + Send + Sync + 'static,
+{
+    /// Make an [`Offer`] in response to the initiator's `filter`.
+    pub(in crate::net::protocol) async fn offer(
+        &self,
+        spawner: &Spawner,
+        urns: Xor,
+        filter: Xor,
+    ) -> Result<Offer, error::Fetch> {
+        let storage = self.storage.get().await?;
+        let tips = spawner.blocking(move || tips(&storage, &filter)).await?;
+        Ok(Offer { urns, tips })
+    }
+
+    /// Fetch from the initiator, given its `tips`.
+    ///
+    /// An [`upstream::Replicated`] event is emitted via `phone` for each URN
+    /// replicated successfully.
+    pub(in crate::net::protocol) async fn fetch(
+        &self,
+        spawner: &Spawner,
+        phone: &TinCans,
+        conn: &quic::Connection,
+        tips: Vec<Tip>,
+    ) -> Result<Report, error::Fetch> {
+        fetch(spawner, &self.repl, &self.storage, conn, tips, Some(phone)).await
+    }
+}
+
+/// The URNs in `storage`, as an [`Xor`] filter.
+pub(in crate::net) fn urns(storage: &storage::Storage) -> Result<Xor, error::Tips> {
+    Ok(identities::any::xor_filter(storage)?.0)
+}
@@ -0,0 +1,144 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt;
+
+use git_ref_format::RefString;
+use minicbor::{Decode, Encode};
+
+use crate::{
+    identities::{git::Urn, xor::Xor},
+    PeerId,
+};
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub enum Request {
+    /// Open a graft session, sending the initiator's URNs.
+    #[n(0)]
+    #[cbor(array)]
+    Filter(#[n(0)] Xor),
+
+    /// The initiator's [`Tip`]s for the URNs it shares with the receiver, sent
+    /// in reply to [`Response::Offer`].
+    #[n(1)]
+    #[cbor(array)]
+    Tips(#[n(0)] Vec<Tip>),
+}
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub enum Response {
+    /// The receiver's URNs, and its [`Tip`]s for the URNs it shares with the
+    /// initiator.
+    #[n(0)]
+    #[cbor(array)]
+    Offer(#[n(0)] Offer),
+
+    /// The receiver is done fetching from the initiator.
+    #[n(1)]
+    #[cbor(array)]
+    Done(#[n(0)] Report),
+
+    #[n(2)]
+    #[cbor(array)]
+    Error(#[n(0)] Error),
+}
+
+impl From<Offer> for Response {
+    fn from(offer: Offer) -> Self {
+        Self::Offer(offer)
+    }
+}
+
+impl From<Report> for Response {
+    fn from(report: Report) -> Self {
+        Self::Done(report)
+    }
+}
+
+impl From<Error> for Response {
+    fn from(error: Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+#[cbor(array)]
+pub struct Offer {
+    #[n(0)]
+    pub urns: Xor,
+    #[n(1)]
+    pub tips: Vec<Tip>,
+}
+
+/// The tip of the `rad/signed_refs` of `peer` within `urn`, as seen by the
+/// sender.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Tip {
+    #[n(0)]
+    pub urn: Urn,
+    #[n(1)]
+    pub peer: PeerId,
+    #[n(2)]
+    pub oid: git_ext::Oid,
+}
+
+/// The per-URN outcome of one side of a graft session.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Report {
+    #[n(0)]
+    pub synced: Vec<Synced>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Synced {
+    #[n(0)]
+    pub urn: Urn,
+    #[n(1)]
+    pub outcome: Outcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum Outcome {
+    /// All of the other side's tips were already present, or belonged to peers
+    /// which are not tracked.
+    #[n(0)]
+    #[cbor(array)]
+    UpToDate,
+
+    /// The URN was replicated, and the given refs were updated.
+    #[n(1)]
+    #[cbor(array)]
+    Replicated(#[n(0)] Vec<Ref>),
+
+    /// Replicating the URN failed.
+    #[n(2)]
+    #[cbor(array)]
+    Failed(#[n(0)] String),
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Ref {
+    #[n(0)]
+    pub name: RefString,
+    #[n(1)]
+    pub oid: git_ext::Oid,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Encode, Decode)]
+#[cbor(array)]
+pub struct Error {
+    #[n(0)]
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
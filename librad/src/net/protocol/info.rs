@@ -3,7 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, fmt};
 
 use data::BoundedVec;
 use minicbor::{Decode, Encode};
@@ -11,11 +11,103 @@ use typenum::U16;
 
 use crate::PeerId;
 
-#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Encode, Decode)]
-#[repr(u8)]
+/// Optional protocol features a peer supports.
+///
+/// Capabilities are encoded as their [`Capability::code`], so that unknown
+/// capabilities advertised by newer peers can be decoded as
+/// [`Capability::Unknown`] instead of failing.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[non_exhaustive]
 pub enum Capability {
-    #[n(0)]
-    Reserved = 0,
+    Reserved,
+
+    /// Answers [`crate::net::protocol::request_pull`] requests.
+    RequestPull,
+
+    /// Answers [`crate::net::protocol::graft`] requests, ie. mutual
+    /// synchronisation.
+    Graft,
+
+    /// Serves fetches subject to the collaborative object filters of tracking
+    /// entries, ie. only the `cobs` refs of selected types and objects are
+    /// advertised to a fetching peer.
+    ///
+    /// Reserved: filters are only applied when fetching, so this is not
+    /// [`Capability::supported`] yet.
+    CobFilter,
+
+    /// Limits the size of the packs it sends and receives, and aborts a fetch
+    /// exceeding the limit instead of stalling.
+    ///
+    /// Reserved: limits are only enforced when fetching, so this is not
+    /// [`Capability::supported`] yet.
+    PackLimit,
+
+    /// Catch-all for unknown capabilities (forwards-compatibility).
+    ///
+    /// This is for decoding, **do not** construct this variant.
+    Unknown(u8),
+}
+
+impl Capability {
+    /// The capabilities supported by this implementation, as advertised in
+    /// our [`PeerAdvertisement`].
+    pub fn supported() -> BTreeSet<Self> {
+        [Self::RequestPull, Self::Graft].iter().copied().collect()
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Reserved => 0,
+            Self::RequestPull => 1,
+            Self::Graft => 2,
+            Self::CobFilter => 3,
+            Self::PackLimit => 4,
+            Self::Unknown(n) => *n,
+        }
+    }
+}
+
+impl From<u8> for Capability {
+    fn from(n: u8) -> Self {
+        match n {
+            0 => Self::Reserved,
+            1 => Self::RequestPull,
+            2 => Self::Graft,
+            3 => Self::CobFilter,
+            4 => Self::PackLimit,
+            x => Self::Unknown(x),
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reserved => f.write_str("reserved"),
+            Self::RequestPull => f.write_str("request-pull"),
+            Self::Graft => f.write_str("graft"),
+            Self::CobFilter => f.write_str("cob-filter"),
+            Self::PackLimit => f.write_str("pack-limit"),
+            Self::Unknown(n) => write!(f, "unknown({})", n),
+        }
+    }
+}
+
+impl minicbor::Encode for Capability {
+    fn encode<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.u8(self.code())?;
+        Ok(())
+    }
+}
+
+impl<'b> minicbor::Decode<'b> for Capability {
+    fn decode(d: &mut minicbor::Decoder<'b>) -> Result<Self, minicbor::decode::Error> {
+        d.u8().map(Self::from)
+    }
 }
 
 pub type PeerInfo<Addr> = GenericPeerInfo<Addr, PeerAdvertisement<Addr>>;
@@ -99,21 +191,96 @@ impl<Addr> GenericPeerInfo<Addr, PeerAdvertisement<Addr>> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Decode, Encode)]
-#[cbor(array)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PeerAdvertisement<Addr> {
-    #[n(0)]
     pub listen_addrs: BoundedVec<U16, Addr>,
-
-    #[n(2)]
-    pub capabilities: BTreeSet<Capability>,
+    /// The advertised capabilities, or `None` if the peer predates them.
+    pub capabilities: Option<BTreeSet<Capability>>,
 }
 
 impl<Addr> PeerAdvertisement<Addr> {
     pub fn new(listen_addr: Addr) -> Self {
         Self {
             listen_addrs: BoundedVec::singleton(listen_addr),
-            capabilities: BTreeSet::default(),
+            capabilities: Some(BTreeSet::default()),
+        }
+    }
+
+    /// Whether the peer supports `capability`.
+    ///
+    /// Peers which don't advertise any capabilities predate them, but do
+    /// answer [`Capability::RequestPull`] requests.
+    pub fn supports(&self, capability: &Capability) -> bool {
+        match &self.capabilities {
+            Some(capabilities) => capabilities.contains(capability),
+            None => *capability == Capability::RequestPull,
+        }
+    }
+}
+
+// Peers prior to the introduction of capabilities other than
+// `Capability::Reserved` derived the encoding of `Capability` as an enum, and
+// so fail to decode a `PeerAdvertisement` which advertises any other
+// capability at index 2 -- breaking membership with them.
+//
+// To remain compatible, index 2 is always encoded as an empty set, and the
+// capabilities are encoded at index 3, which older peers ignore. Index 1 is
+// unused. An advertisement without capabilities is encoded as older peers
+// would encode it, ie. without index 3.
+
+const ADVERTISEMENT_LEN: u64 = 4;
+const LEGACY_ADVERTISEMENT_LEN: u64 = 3;
+
+impl<Addr> minicbor::Encode for PeerAdvertisement<Addr>
+where
+    Addr: minicbor::Encode,
+{
+    fn encode<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        match &self.capabilities {
+            None => {
+                e.array(LEGACY_ADVERTISEMENT_LEN)?
+                    .encode(&self.listen_addrs)?
+                    .null()?
+                    .array(0)?;
+            },
+            Some(capabilities) => {
+                e.array(ADVERTISEMENT_LEN)?
+                    .encode(&self.listen_addrs)?
+                    .null()?
+                    .array(0)?
+                    .encode(capabilities)?;
+            },
+        }
+        Ok(())
+    }
+}
+
+impl<'b, Addr> minicbor::Decode<'b> for PeerAdvertisement<Addr>
+where
+    Addr: minicbor::Decode<'b>,
+{
+    fn decode(d: &mut minicbor::Decoder<'b>) -> Result<Self, minicbor::decode::Error> {
+        use minicbor::decode::Error;
+
+        let len = d
+            .array()?
+            .ok_or(Error::Message("expected definite length array"))?;
+        let mut listen_addrs = None;
+        let mut capabilities = None;
+        for i in 0..len {
+            match i {
+                0 => listen_addrs = Some(d.decode()?),
+                3 => capabilities = Some(d.decode()?),
+                _ => d.skip()?,
+            }
         }
+
+        Ok(Self {
+            listen_addrs: listen_addrs.ok_or(Error::MissingValue(0, "listen_addrs"))?,
+            capabilities,
+        })
     }
 }
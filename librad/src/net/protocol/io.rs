@@ -9,7 +9,7 @@ use data::BoundedVec;
 
 use super::{
     gossip,
-    info::{PartialPeerInfo, PeerAdvertisement},
+    info::{Capability, PartialPeerInfo, PeerAdvertisement},
     membership,
     Endpoint,
     ProtocolStorage,
@@ -85,7 +85,7 @@ pub(super) fn peer_advertisement(
         listen_addrs.extend_fill(endpoint.listen_addrs());
         PeerAdvertisement {
             listen_addrs,
-            capabilities: Default::default(),
+            capabilities: Some(Capability::supported()),
         }
     }
 }
@@ -6,6 +6,9 @@
 mod git;
 pub(in crate::net::protocol) use git::git;
 
+pub(in crate::net::protocol) mod graft;
+pub(in crate::net::protocol) use graft::graft;
+
 mod gossip;
 pub(in crate::net::protocol) use gossip::gossip;
 
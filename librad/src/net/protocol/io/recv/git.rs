@@ -12,8 +12,10 @@ use thiserror::Error;
 use tracing::{error, info};
 
 use crate::{
+    git::Urn,
     net::{
-        connection::Duplex,
+        bandwidth::{self, Bandwidth},
+        connection::{Duplex, RemotePeer},
         upgrade::{self, Upgraded},
     },
     paths::Paths,
@@ -24,33 +26,57 @@ enum Error {
     #[error("upload-pack exited with {0}")]
     UploadPack(ExitStatus),
 
+    #[error(transparent)]
+    Bandwidth(#[from] bandwidth::Exceeded),
+
     #[error(transparent)]
     Io(#[from] io::Error),
 }
 
-pub(in crate::net::protocol) async fn git<T>(paths: &Paths, stream: Upgraded<upgrade::Git, T>)
-where
-    T: Duplex,
+pub(in crate::net::protocol) async fn git<T>(
+    paths: &Paths,
+    bandwidth: &Bandwidth,
+    stream: Upgraded<upgrade::Git, T>,
+) where
+    T: Duplex + RemotePeer,
     T::Read: AsyncRead + Unpin,
     T::Write: AsyncWrite + Unpin,
 {
-    if let Err(e) = serve(paths, stream).await {
+    if let Err(e) = serve(paths, bandwidth, stream).await {
         error!(err = ?e, "upload-pack error");
     }
 }
 
-async fn serve<T>(paths: &Paths, stream: Upgraded<upgrade::Git, T>) -> Result<(), Error>
+async fn serve<T>(
+    paths: &Paths,
+    bandwidth: &Bandwidth,
+    stream: Upgraded<upgrade::Git, T>,
+) -> Result<(), Error>
 where
-    T: Duplex,
+    T: Duplex + RemotePeer,
     T::Read: AsyncRead + Unpin,
     T::Write: AsyncWrite + Unpin,
 {
+    let account = bandwidth.account(bandwidth::Direction::Upload, stream.remote_peer_id(), None);
+    account.check()?;
+
     let (recv, send) = stream.into_stream().split();
     let git_dir = paths.git_dir();
 
-    let (Header { path, host, extra }, run) = upload_pack(git_dir, recv, send).await?;
+    let (Header { path, host, extra }, run) =
+        upload_pack(git_dir, recv, account.metered(send)).await?;
     info!(%path, ?host, ?extra, "upload-pack");
 
+    // Legacy clients send a full URN, others only its id
+    let urn = path
+        .parse::<Urn>()
+        .ok()
+        .or_else(|| Urn::try_from_id(&path).ok());
+    if let Some(urn) = urn {
+        account.set_urn(urn);
+        account.check()?;
+    }
+
     let status = run.await?;
     // XXX: #![feature(exit_status_error)] ?
     // https://github.com/rust-lang/rust/issues/84908
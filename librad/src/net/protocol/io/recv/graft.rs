@@ -0,0 +1,167 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Receiving end of a graft session, see [`crate::net::protocol::graft`].
+
+use std::net::SocketAddr;
+
+use futures::{
+    future,
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt as _, BufReader, BufWriter, IntoSink},
+    SinkExt as _,
+    StreamExt as _,
+};
+use futures_codec::FramedRead;
+
+use crate::{
+    git::Urn,
+    net::{
+        connection::{Duplex, RemotePeer as _},
+        peer::event::downstream::Gossip,
+        protocol::{
+            self,
+            control,
+            gossip,
+            graft::{self, error, Outcome, Ref, Report, Request, Response, Synced},
+            io::codec,
+            State,
+        },
+        quic,
+        upgrade::{self, Upgraded},
+    },
+    PeerId,
+};
+
+pub(in crate::net::protocol) async fn graft<S, G>(
+    state: State<S, G>,
+    stream: Upgraded<upgrade::Graft, quic::BidiStream>,
+) where
+    S: protocol::ProtocolStorage<SocketAddr, Update = gossip::Payload> + 'static,
+    G: protocol::RequestPullGuard,
+{
+    let remote_peer = stream.remote_peer_id();
+    let conn = stream.connection().clone();
+    let (recv, send) = stream.into_stream().split();
+    let recv = BufReader::with_capacity(graft::FRAMED_BUFSIZ, recv);
+    let send = BufWriter::with_capacity(graft::FRAMED_BUFSIZ, send);
+    let mut sink = send.into_sink();
+    let mut recv = FramedRead::new(recv, codec::Codec::<Request>::new());
+
+    let resp = match handle_session(&state, &conn, &mut recv, &mut sink).await {
+        Ok(report) => {
+            gossip(&state, remote_peer, &report).await;
+            Response::from(report)
+        },
+        Err(None) => {
+            tracing::debug!("graft stream closed by initiator");
+            return;
+        },
+        Err(Some(err)) => Response::from(err),
+    };
+    respond(&mut sink, &resp).await
+}
+
+/// Drive the session up to the point where the [`Report`] is to be sent.
+///
+/// An `Err(None)` indicates that the initiator went away prematurely.
+async fn handle_session<S, G, R, W>(
+    state: &State<S, G>,
+    conn: &quic::Connection,
+    recv: &mut FramedRead<R, codec::Codec<Request>>,
+    sink: &mut IntoSink<W, Vec<u8>>,
+) -> Result<Report, Option<graft::Error>>
+where
+    S: protocol::ProtocolStorage<SocketAddr, Update = gossip::Payload> + 'static,
+    G: protocol::RequestPullGuard,
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let filter = match next(recv).await? {
+        Request::Filter(filter) => filter,
+        Request::Tips(_) => return Err(Some(error::unexpected_request())),
+    };
+
+    let urns = state.caches.urns.get().clone();
+    let offer = state
+        .graft
+        .offer(&state.spawner, urns, filter)
+        .await
+        .map_err(|e| {
+            tracing::error!(err = ?e, "error computing graft offer");
+            Some(error::internal_error())
+        })?;
+    respond(sink, &offer.into()).await;
+
+    let tips = match next(recv).await? {
+        Request::Tips(tips) => tips,
+        Request::Filter(_) => return Err(Some(error::unexpected_request())),
+    };
+
+    state
+        .graft
+        .fetch(&state.spawner, &state.phone, conn, tips)
+        .await
+        .map_err(|e| {
+            tracing::error!(err = ?e, "error fetching graft tips");
+            Some(error::internal_error())
+        })
+}
+
+async fn next<R>(
+    recv: &mut FramedRead<R, codec::Codec<Request>>,
+) -> Result<Request, Option<graft::Error>>
+where
+    R: AsyncRead + Unpin,
+{
+    match recv.next().await {
+        None => Err(None),
+        Some(Err(e)) => {
+            tracing::warn!(err = ?e, "graft recv error");
+            Err(Some(error::decode_failed()))
+        },
+        Some(Ok(req)) => Ok(req),
+    }
+}
+
+async fn respond<W>(sink: &mut IntoSink<W, Vec<u8>>, resp: &Response)
+where
+    W: AsyncWrite + Unpin,
+{
+    match minicbor::to_vec(resp) {
+        Err(e) => tracing::error!(err = ?e, "error encoding graft response"),
+        Ok(resp) => {
+            if let Err(e) = sink.send(resp).await {
+                tracing::warn!(err = ?e, "graft send error")
+            }
+        },
+    }
+}
+
+/// Announce the refs we fetched to everyone but the initiator.
+async fn gossip<S, G>(state: &State<S, G>, exclude: PeerId, report: &Report)
+where
+    S: protocol::ProtocolStorage<SocketAddr, Update = gossip::Payload> + 'static,
+    G: protocol::RequestPullGuard,
+{
+    let announce = |urn: &Urn, Ref { oid, .. }: &Ref| {
+        control::gossip(
+            state,
+            Gossip::Announce(gossip::Payload {
+                urn: urn.clone(),
+                rev: Some((*oid).into()),
+                origin: None,
+            }),
+            Some(exclude),
+        )
+    };
+    future::join_all(
+        report
+            .synced
+            .iter()
+            .flat_map(|Synced { urn, outcome }| match outcome {
+                Outcome::Replicated(refs) => refs.iter().map(|r| announce(urn, r)).collect(),
+                Outcome::UpToDate | Outcome::Failed(_) => vec![],
+            }),
+    )
+    .await;
+}
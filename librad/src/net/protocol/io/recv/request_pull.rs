@@ -20,7 +20,7 @@ use crate::{
     git::Urn,
     net::{
         connection::{Duplex, RemotePeer as _},
-        peer::event::downstream::Gossip,
+        peer::event::{downstream::Gossip, upstream},
         protocol::{
             self,
             control,
@@ -133,12 +133,17 @@ where
     report.progress(progress::replicating(&urn)).await;
     match state
         .request_pull
-        .replicate(&state.spawner, urn.clone(), conn)
+        .replicate(&state.spawner, &state.phone, urn.clone(), conn)
         .await
     {
         Ok(success) => {
             let tips = success.refs.iter().map(|Ref { oid, .. }| oid).copied();
             gossip(&state, peer, &urn, tips).await;
+            state.phone.emit(upstream::RequestPull {
+                peer,
+                urn,
+                success: success.clone(),
+            });
             success.into()
         },
         Err(err) => error::replication_error(err).into(),
@@ -90,11 +90,12 @@ mod incoming {
                 stream.close(CloseReason::InvalidUpgrade)
             },
 
-            Ok(Git(up)) => recv::git(&state.config.paths, up).await,
+            Ok(Git(up)) => recv::git(&state.config.paths, &state.bandwidth, up).await,
             Ok(Gossip(up)) => recv::gossip(state, up).await,
             Ok(Membership(up)) => recv::membership(state, up).await,
             Ok(Interrogation(up)) => recv::interrogation(state, up).await,
             Ok(RequestPull(up)) => recv::request_pull(state, up).await,
+            Ok(Graft(up)) => recv::graft(state, up).await,
         }
     }
 
@@ -114,6 +115,7 @@ mod incoming {
             Ok(Git(up)) => deny_uni(up.into_stream(), "git"),
             Ok(Interrogation(up)) => deny_uni(up.into_stream(), "interrogation"),
             Ok(RequestPull(up)) => deny_uni(up.into_stream(), "request-pull"),
+            Ok(Graft(up)) => deny_uni(up.into_stream(), "graft"),
 
             Ok(Gossip(up)) => recv::gossip(state, up).await,
             Ok(Membership(up)) => recv::membership(state, up).await,
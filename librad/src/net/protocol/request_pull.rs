@@ -6,8 +6,12 @@ use thiserror::Error;
 
 use crate::{
     git::{storage, storage::PoolError, Urn},
-    net::{quic, replication},
-    paths::Paths,
+    net::{
+        connection::RemotePeer as _,
+        protocol::{event::upstream, TinCans},
+        quic,
+        replication,
+    },
     PeerId,
 };
 
@@ -40,15 +44,15 @@ pub trait Guard {
 #[derive(Clone)]
 pub struct State<S, G> {
     storage: S,
-    paths: Paths,
+    repl: replication::Replication,
     guard: G,
 }
 
 impl<S, G: Guard> State<S, G> {
-    pub fn new(storage: S, paths: Paths, guard: G) -> Self {
+    pub fn new(storage: S, repl: replication::Replication, guard: G) -> Self {
         Self {
             storage,
-            paths,
+            repl,
             guard,
         }
     }
@@ -67,8 +71,6 @@ pub(in crate::net::protocol) mod error {
         Replication(#[from] replication::error::Replicate),
         #[error("internal error: could not get handle to storage")]
         Pool(#[from] PoolError),
-        #[error("internal error: could not intialise storage")]
-        Init(#[from] replication::error::Init),
         #[error("internal error: failed to look up symbolic-ref target")]
         Read(#[from] storage::read::Error),
     }
@@ -103,24 +105,32 @@ where
     S: storage::Pooled<storage::Storage> + Send + Sync + 'static,
 {
     /// Run replication and convert the updated tips into [`Ref`]s.
+    ///
+    /// An [`upstream::Replicated`] event is emitted via `phone` if the
+    /// replication succeeds.
     pub(in crate::net::protocol) async fn replicate(
         &self,
         spawner: &Spawner,
+        phone: &TinCans,
         urn: Urn,
         conn: quic::Connection,
     ) -> Result<Success, error::Replicate> {
         use crate::git::storage::ReadOnlyStorage as _;
         use link_replication::Updated;
 
-        let repl = replication::Replication::new(&self.paths, replication::Config::default())?;
         let storage = self.storage.get().await?;
-        let succ = repl.replicate(spawner, storage, conn, urn, None).await?;
+        let remote_peer = conn.remote_peer_id();
+        let succ = self
+            .repl
+            .replicate(spawner, storage, conn, urn.clone(), None)
+            .await?;
+        phone.emit(upstream::Replicated::new(remote_peer, urn, &succ));
 
         let storage = self.storage.get().await?;
         succ.updated_refs()
             .iter()
             .try_fold(Success::default(), |mut success, up| match up {
-                Updated::Direct { name, target } => {
+                Updated::Direct { name, target, .. } => {
                     success.refs.push(Ref {
                         name: name.clone(),
                         oid: (*target).into(),
@@ -135,7 +145,7 @@ where
                     });
                     Ok(success)
                 },
-                Updated::Prune { name } => {
+                Updated::Prune { name, .. } => {
                     success.pruned.push(name.clone());
                     Ok(success)
                 },
@@ -5,14 +5,14 @@ use std::{net::SocketAddr, sync::Arc};
 
 use crypto::Signer;
 
-use futures::TryFutureExt;
-
 use link_async::Spawner;
 
 use crate::{
     git::{self, identities::local::LocalIdentity, Urn},
     net::{
-        quic::ConnectPeer,
+        bandwidth::Bandwidth,
+        protocol::{event::upstream, graft::Grafted, Capability, TinCans},
+        quic::{self, ConnectPeer, Ingress},
         replication::{self, Replication},
     },
     paths::Paths,
@@ -23,6 +23,7 @@ pub mod config;
 pub use config::Config;
 pub mod error;
 
+mod graft;
 mod interrogation;
 pub use interrogation::Interrogation;
 mod request_pull;
@@ -39,6 +40,7 @@ pub struct Client<Signer, Endpoint: Clone + Send + Sync> {
     endpoint: Endpoint,
     repl: Replication,
     user_store: git::storage::Pool<git::storage::Storage>,
+    events: Option<TinCans>,
 }
 
 impl<S, E: Clone + Send + Sync> Client<S, E>
@@ -59,8 +61,28 @@ where
             endpoint,
             repl,
             user_store,
+            events: None,
         })
     }
+
+    /// Emit an [`upstream::Replicated`] event via `phone` after each
+    /// successful [`Client::replicate`], and for each URN replicated by
+    /// [`Client::graft`].
+    pub(crate) fn with_events(self, phone: TinCans) -> Self {
+        Self {
+            events: Some(phone),
+            ..self
+        }
+    }
+
+    /// Account for the bytes fetched by [`Client::replicate`] and
+    /// [`Client::graft`] in `bandwidth`.
+    pub(crate) fn with_bandwidth(self, bandwidth: Bandwidth) -> Self {
+        Self {
+            repl: self.repl.with_bandwidth(bandwidth),
+            ..self
+        }
+    }
 }
 
 impl<S, E> Client<S, E>
@@ -92,10 +114,14 @@ where
             .connection()
             .clone();
         let store = self.user_store.get().await?;
-        self.repl
-            .replicate(&self.spawner, store, conn, urn, whoami)
-            .err_into()
-            .await
+        let success = self
+            .repl
+            .replicate(&self.spawner, store, conn, urn.clone(), whoami)
+            .await?;
+        if let Some(phone) = &self.events {
+            phone.emit(upstream::Replicated::new(remote_peer, urn, &success));
+        }
+        Ok(success)
     }
 
     pub async fn request_pull(
@@ -114,10 +140,42 @@ where
             crate::net::quic::Ingress::Remote(conn) => (conn, None),
             crate::net::quic::Ingress::Local { conn, streams } => (conn, Some(streams)),
         };
+        require(remote_peer, &conn, Capability::RequestPull).await?;
 
         RequestPull::new(conn, incoming, urn, self.paths.clone()).await
     }
 
+    /// Mutually synchronise with the remote peer, see
+    /// [`crate::net::protocol::graft`].
+    pub async fn graft(
+        &self,
+        with: impl Into<(PeerId, Vec<SocketAddr>)>,
+    ) -> Result<Grafted, error::Graft> {
+        let (remote_peer, addrs) = with.into();
+
+        let ingress = self
+            .endpoint
+            .connect(remote_peer, addrs)
+            .await
+            .ok_or(error::NoConnection(remote_peer))?;
+        let (conn, incoming) = match ingress {
+            Ingress::Remote(conn) => (conn, None),
+            Ingress::Local { conn, streams } => (conn, Some(streams)),
+        };
+        require(remote_peer, &conn, Capability::Graft).await?;
+
+        graft::graft(
+            &self.spawner,
+            &self.repl,
+            &self.user_store,
+            self.paths.clone(),
+            conn,
+            incoming,
+            self.events.as_ref(),
+        )
+        .await
+    }
+
     pub async fn interrogate(
         &self,
         from: impl Into<(PeerId, Vec<SocketAddr>)>,
@@ -146,3 +204,26 @@ where
         Ok(self.spawner.blocking(move || blocking(&storage)).await)
     }
 }
+
+/// Ensure `peer` advertises `capability` before a sub-protocol stream is opened
+/// on `conn`.
+///
+/// Peers which don't know about a sub-protocol would otherwise just drop the
+/// stream, leaving us waiting for a response until it is closed.
+async fn require(
+    peer: PeerId,
+    conn: &quic::Connection,
+    capability: Capability,
+) -> Result<(), error::Capability> {
+    let ad = Interrogation {
+        peer,
+        conn: conn.clone(),
+    }
+    .peer_advertisement()
+    .await?;
+    if ad.supports(&capability) {
+        Ok(())
+    } else {
+        Err(error::Capability::Unsupported { peer, capability })
+    }
+}
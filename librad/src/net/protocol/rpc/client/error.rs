@@ -6,7 +6,7 @@ use thiserror::Error;
 use crate::{
     git::storage,
     net::{
-        protocol::{self, interrogation},
+        protocol::{self, graft, interrogation},
         quic,
         replication,
     },
@@ -42,12 +42,28 @@ impl From<protocol::error::Rpc<quic::BidiStream>> for Interrogation {
     }
 }
 
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Capability {
+    #[error("{peer} does not support {capability}")]
+    Unsupported {
+        peer: PeerId,
+        capability: protocol::Capability,
+    },
+
+    #[error("failed to obtain the advertisement of the remote peer")]
+    Interrogation(#[from] Interrogation),
+}
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum RequestPull {
     #[error("request-pull replication cancelled")]
     Cancelled,
 
+    #[error(transparent)]
+    Capability(#[from] Capability),
+
     #[error(transparent)]
     Incoming(#[from] Incoming),
 
@@ -64,6 +80,46 @@ impl From<protocol::error::Rpc<quic::BidiStream>> for RequestPull {
     }
 }
 
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Graft {
+    #[error("no response from {0}")]
+    NoResponse(PeerId),
+
+    #[error("error response: {0}")]
+    ErrorResponse(graft::Error),
+
+    #[error("invalid response")]
+    InvalidResponse,
+
+    #[error(transparent)]
+    Capability(#[from] Capability),
+
+    #[error(transparent)]
+    Fetch(#[from] graft::error::Fetch),
+
+    #[error(transparent)]
+    Tips(#[from] graft::error::Tips),
+
+    #[error(transparent)]
+    Incoming(#[from] Incoming),
+
+    #[error(transparent)]
+    NoConnection(#[from] NoConnection),
+
+    #[error("failed to borrow storage from pool")]
+    Pool(#[from] storage::PoolError),
+
+    #[error(transparent)]
+    Rpc(#[from] Box<protocol::error::Rpc<quic::BidiStream>>),
+}
+
+impl From<protocol::error::Rpc<quic::BidiStream>> for Graft {
+    fn from(e: protocol::error::Rpc<quic::BidiStream>) -> Self {
+        Self::Rpc(Box::new(e))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Replicate {
     #[error(transparent)]
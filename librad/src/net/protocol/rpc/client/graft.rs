@@ -0,0 +1,122 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::sync::Arc;
+
+use futures::{
+    future::{self, Either},
+    io::BufReader,
+    FutureExt as _,
+    SinkExt as _,
+    TryFutureExt as _,
+    TryStreamExt as _,
+};
+use futures_codec::Framed;
+use link_async::Spawner;
+
+use crate::{
+    git::storage::{self, Pool},
+    net::{
+        codec::CborCodec,
+        connection::RemotePeer as _,
+        protocol::{
+            error::Rpc,
+            graft::{self, Grafted, Request, Response},
+            TinCans,
+        },
+        quic,
+        replication::Replication,
+        upgrade,
+    },
+    paths::Paths,
+};
+
+use super::{error, streams};
+
+type RpcError = Rpc<quic::BidiStream>;
+
+/// Run a graft session as the initiator, see [`crate::net::protocol::graft`].
+///
+/// If `incoming` is given, git requests made by the remote peer on `conn` are
+/// served while the session is in progress. If `events` is given, an
+/// [`crate::net::protocol::event::upstream::Replicated`] event is emitted for
+/// each URN we replicated.
+pub(super) async fn graft(
+    spawner: &Spawner,
+    repl: &Replication,
+    store: &Pool<storage::Storage>,
+    paths: Arc<Paths>,
+    conn: quic::Connection,
+    incoming: Option<quic::BoxedIncomingStreams<'static>>,
+    events: Option<&TinCans>,
+) -> Result<Grafted, error::Graft> {
+    let serve = match incoming {
+        Some(incoming) => streams::git(paths, incoming).boxed(),
+        None => future::pending().boxed(),
+    };
+    let session = session(spawner, repl, store, conn, events).boxed();
+
+    match future::select(session, serve).await {
+        Either::Left((res, _)) => res,
+        Either::Right((Err(e), _)) => Err(e.into()),
+        Either::Right((Ok(()), session)) => session.await,
+    }
+}
+
+async fn session(
+    spawner: &Spawner,
+    repl: &Replication,
+    store: &Pool<storage::Storage>,
+    conn: quic::Connection,
+    events: Option<&TinCans>,
+) -> Result<Grafted, error::Graft> {
+    let remote_peer = conn.remote_peer_id();
+
+    let urns = {
+        let storage = store.get().await?;
+        spawner.blocking(move || graft::urns(&storage)).await?
+    };
+
+    let stream = conn.open_bidi().await.map_err(RpcError::from)?;
+    let upgraded = upgrade::upgrade(stream, upgrade::Graft)
+        .await
+        .map_err(RpcError::from)?;
+    let buf = BufReader::with_capacity(graft::FRAMED_BUFSIZ, upgraded.into_stream());
+    let mut framing = Framed::new(buf, CborCodec::<Request, Response>::new());
+
+    framing
+        .send(Request::Filter(urns))
+        .await
+        .map_err(RpcError::from)?;
+    let offer = match framing.try_next().await.map_err(RpcError::from)? {
+        None => return Err(error::Graft::NoResponse(remote_peer)),
+        Some(Response::Offer(offer)) => offer,
+        Some(Response::Error(e)) => return Err(error::Graft::ErrorResponse(e)),
+        Some(Response::Done(_)) => return Err(error::Graft::InvalidResponse),
+    };
+
+    let tips = {
+        let storage = store.get().await?;
+        let filter = offer.urns;
+        spawner
+            .blocking(move || graft::tips(&storage, &filter))
+            .await?
+    };
+    framing
+        .send(Request::Tips(tips))
+        .await
+        .map_err(RpcError::from)?;
+
+    let fetched = graft::fetch(spawner, repl, store, &conn, offer.tips, events).err_into();
+    let remote = async {
+        match framing.try_next().await.map_err(RpcError::from)? {
+            None => Err(error::Graft::NoResponse(remote_peer)),
+            Some(Response::Done(report)) => Ok(report),
+            Some(Response::Error(e)) => Err(error::Graft::ErrorResponse(e)),
+            Some(Response::Offer(_)) => Err(error::Graft::InvalidResponse),
+        }
+    };
+    let (fetched, remote) = future::try_join(fetched, remote).await?;
+
+    Ok(Grafted { fetched, remote })
+}
@@ -68,6 +68,7 @@ mod incoming {
             Ok(Membership(up)) => deny_bidi(up.into_stream(), "membership"),
             Ok(Interrogation(up)) => deny_bidi(up.into_stream(), "interrogation"),
             Ok(RequestPull(up)) => deny_bidi(up.into_stream(), "request-pull"),
+            Ok(Graft(up)) => deny_bidi(up.into_stream(), "graft"),
         }
     }
 
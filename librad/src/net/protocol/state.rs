@@ -15,17 +15,22 @@ use super::{
     cache,
     event,
     gossip,
+    graft,
     membership,
     request_pull,
     tick,
     Endpoint,
+    PeerInfo,
     ProtocolStorage,
     RequestPullGuard,
     TinCans,
 };
 use crate::{
     git::storage::{self, PoolError, PooledRef},
-    net::quic,
+    net::{
+        bandwidth::{self, Bandwidth},
+        quic,
+    },
     paths::Paths,
     rate_limit::{self, Direct, Keyed, RateLimiter},
     PeerId,
@@ -46,11 +51,13 @@ pub(super) struct State<S, G> {
     pub membership: membership::Hpv<Pcg64Mcg, SocketAddr>,
     pub gossip: broadcast::State<Storage<S>, ()>,
     pub request_pull: request_pull::State<Storage<S>, G>,
+    pub graft: graft::State<Storage<S>>,
     pub phone: TinCans,
     pub config: StateConfig,
     pub caches: cache::Caches,
     pub spawner: Arc<Spawner>,
     pub limits: RateLimits,
+    pub bandwidth: Bandwidth,
 }
 
 impl<S, G> State<S, G> {
@@ -132,6 +139,13 @@ pub struct Quota {
     pub membership: rate_limit::Quota,
     /// See [`StorageQuota`].
     pub storage: StorageQuota,
+    /// Bytes transferred via `git` streams, see [`bandwidth`].
+    ///
+    /// When a peer or URN exceeds its quota, serving or fetching from the peer
+    /// fails until the quota has replenished.
+    ///
+    /// Default: unlimited
+    pub bandwidth: bandwidth::Quota,
 }
 
 impl Default for Quota {
@@ -140,6 +154,7 @@ impl Default for Quota {
             gossip: GossipQuota::default(),
             membership: rate_limit::Quota::per_second(nonzero!(1u32)).allow_burst(nonzero!(10u32)),
             storage: StorageQuota::default(),
+            bandwidth: bandwidth::Quota::default(),
         }
     }
 }
@@ -244,6 +259,10 @@ where
     async fn ask(&self, want: Self::Update) -> bool {
         self.inner.ask(want).await
     }
+
+    fn providers(&self, want: &Self::Update) -> Vec<PeerInfo<A>> {
+        self.inner.providers(want)
+    }
 }
 
 impl<S> broadcast::RateLimited for Storage<S> {
@@ -16,12 +16,16 @@ use crate::{
         storage::{read::ReadOnlyStorage as _, Storage},
     },
     identities::git::Urn,
-    net::{connection::RemotePeer as _, quic},
+    net::{
+        bandwidth::{self, Bandwidth},
+        connection::RemotePeer as _,
+        quic,
+    },
     paths::Paths,
     PeerId,
 };
 
-pub use link_replication::FetchLimit;
+pub use link_replication::{FetchLimit, Update, Updated};
 
 mod context;
 use context::Context;
@@ -43,6 +47,9 @@ pub mod error {
         #[error("timeout waiting for replication slot")]
         Timeout(#[from] link_async::Elapsed),
 
+        #[error(transparent)]
+        Bandwidth(#[from] crate::net::bandwidth::Exceeded),
+
         #[error(transparent)]
         Replicate(#[from] link_replication::Error),
     }
@@ -73,6 +80,7 @@ pub struct Replication {
     slots: Arc<Semaphore>,
     odb: link_replication::io::Odb,
     rdb: link_git::refs::db::Refdb,
+    bandwidth: Bandwidth,
 }
 
 impl Replication {
@@ -86,9 +94,22 @@ impl Replication {
             slots,
             odb,
             rdb,
+            bandwidth: Bandwidth::default(),
         })
     }
 
+    /// Account for the bytes fetched in `bandwidth`, and fail replication if
+    /// its download quota is exceeded.
+    pub fn with_bandwidth(self, bandwidth: Bandwidth) -> Self {
+        Self { bandwidth, ..self }
+    }
+
+    /// Re-scan the packs of the monorepo, eg. after
+    /// [`crate::git::storage::maintenance::run`].
+    pub fn reload(&self) -> Result<(), link_replication::Error> {
+        self.odb.reload()
+    }
+
     pub async fn replicate<S>(
         &self,
         spawner: &Spawner,
@@ -100,6 +121,13 @@ impl Replication {
     where
         S: AsRef<Storage> + Send + 'static,
     {
+        let account = self.bandwidth.account(
+            bandwidth::Direction::Download,
+            conn.remote_peer_id(),
+            Some(urn.clone()),
+        );
+        account.check()?;
+
         let slot = timeout(self.config.wait_slot, self.slots.acquire_arc()).await?;
         let limit = self.config.limit;
         let odb = self.odb.clone();
@@ -117,7 +145,7 @@ impl Replication {
                 let refdb = link_replication::io::Refdb::new(info, odb.clone(), rdb.clone(), &urn)?;
                 let net = link_replication::io::Network::new(
                     refdb.clone(),
-                    conn,
+                    context::Connection::new(conn, account),
                     store.path(),
                     urn.clone(),
                 );
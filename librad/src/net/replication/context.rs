@@ -5,7 +5,7 @@
 
 use std::{
     borrow::Cow,
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     convert::TryFrom,
     ops::Deref,
     path::Path,
@@ -49,6 +49,7 @@ use crate::{
         self,
         git::{
             ContentId,
+            Genesis,
             Person,
             Project,
             Revision,
@@ -57,7 +58,7 @@ use crate::{
             VerifiedProject,
         },
     },
-    net::{self, quic, upgrade},
+    net::{self, bandwidth, quic, upgrade},
     PeerId,
 };
 
@@ -115,7 +116,7 @@ pub mod error {
     }
 }
 
-type Network = io::Network<Urn, io::Refdb<io::Odb>, io::Odb, quic::Connection>;
+type Network = io::Network<Urn, io::Refdb<io::Odb>, io::Odb, Connection>;
 
 /// Context for a replication v3 run.
 ///
@@ -148,15 +149,17 @@ impl<'a> Context<'a> {
             },
 
             SomeIdentity::Project(p) => {
-                let verified = self.store.read_only().identities::<Project>().verify(
-                    *p.content_id,
-                    |urn| {
+                let verified = self
+                    .store
+                    .read_only()
+                    .identities::<Project>()
+                    .with_genesis(Genesis::Single)
+                    .verify(*p.content_id, |urn| {
                         let urn = Urn(urn);
                         resolve(&urn)
                             .map(|oid| git_ext::Oid::from(oid.as_ref().to_owned()).into())
                             .ok_or(error::Verification::MissingDelegate(urn.0))
-                    },
-                )?;
+                    })?;
                 Ok(SomeVerifiedIdentity::Project(verified))
             },
 
@@ -169,10 +172,19 @@ pub struct SomeUnverifiedIdentity(SomeIdentity);
 
 impl AnyIdentity for SomeUnverifiedIdentity {
     type Oid = ContentId;
+    type Urn = Urn;
 
     fn content_id(&self) -> Self::Oid {
         self.0.content_id()
     }
+
+    fn revision(&self) -> Self::Oid {
+        self.0.revision()
+    }
+
+    fn urn(&self) -> Self::Urn {
+        self.0.urn().into()
+    }
 }
 
 #[derive(Debug)]
@@ -246,6 +258,27 @@ impl VerifiedIdentity for SomeVerifiedIdentity {
             BTreeSet::new()
         }
     }
+
+    fn revoked_ids(&self) -> BTreeMap<PeerId, u64> {
+        let revoked = |doc_revoked: &BTreeMap<_, identities::git::Revocation>| {
+            doc_revoked
+                .iter()
+                .map(|(key, revocation)| (PeerId::from(*key), revocation.effective))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Self::Person(p) => revoked(&p.doc.revoked).into_iter().collect(),
+            Self::Project(p) => revoked(&p.doc.revoked)
+                .into_iter()
+                .chain(
+                    p.delegations()
+                        .into_iter()
+                        .indirect()
+                        .flat_map(|person| revoked(&person.doc.revoked)),
+                )
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -311,6 +344,18 @@ impl Identities for Context<'_> {
         Ok(id.map(SomeUnverifiedIdentity))
     }
 
+    fn load<H>(&self, head: H) -> Result<Self::UnverifiedIdentity, Self::LookupError>
+    where
+        H: AsRef<oid>,
+    {
+        let id = self
+            .store
+            .read_only()
+            .identities::<Void>()
+            .some_identity(*git_ext::Oid::from(head.as_ref().to_owned()))?;
+        Ok(SomeUnverifiedIdentity(id))
+    }
+
     fn verify<H, F, T>(
         &self,
         head: H,
@@ -445,7 +490,14 @@ pub struct Tracked<'a>(
 );
 
 impl<'a> Iterator for Tracked<'a> {
-    type Item = Result<(PeerId, link_replication::DataPolicy), tracking::error::Tracked>;
+    type Item = Result<
+        (
+            PeerId,
+            link_replication::DataPolicy,
+            link_replication::CobPolicy,
+        ),
+        tracking::error::Tracked,
+    >;
 
     fn next(&mut self) -> Option<Self::Item> {
         use link_replication::DataPolicy::*;
@@ -454,7 +506,8 @@ impl<'a> Iterator for Tracked<'a> {
             match self.0.next()? {
                 Ok(tracking::Tracked::Default { .. }) => continue,
                 Ok(tracking::Tracked::Peer { peer, config, .. }) => {
-                    break Some(Ok((peer, if config.data { Allow } else { Deny })))
+                    let data = if config.data { Allow } else { Deny };
+                    break Some(Ok((peer, data, cob_policy(&config))));
                 },
                 Err(e) => break Some(Err(e)),
             }
@@ -462,6 +515,35 @@ impl<'a> Iterator for Tracked<'a> {
     }
 }
 
+fn cob_policy(config: &tracking::Config) -> link_replication::CobPolicy {
+    use tracking::config::{cobs::Policy, Pattern, TypeName};
+
+    let mut policy = link_replication::CobPolicy {
+        wildcard: None,
+        types: BTreeMap::new(),
+    };
+    for (typename, filter) in config.cobs.iter() {
+        let filter = link_replication::CobFilter {
+            policy: match filter.policy {
+                Policy::Allow => link_replication::DataPolicy::Allow,
+                Policy::Deny => link_replication::DataPolicy::Deny,
+            },
+            objects: match &filter.pattern {
+                Pattern::Wildcard => None,
+                Pattern::Objects(objs) => Some(objs.iter().map(|id| id.0.to_string()).collect()),
+            },
+        };
+        match typename {
+            TypeName::Wildcard => policy.wildcard = Some(filter),
+            TypeName::Type(ty) => {
+                policy.types.insert(ty.0.to_string(), filter);
+            },
+        }
+    }
+
+    policy
+}
+
 #[allow(clippy::type_complexity)]
 impl<'a> Tracking for Context<'a> {
     type Urn = Urn;
@@ -635,18 +717,31 @@ impl Net for Context<'_> {
     }
 }
 
+/// A [`quic::Connection`] whose `git` streams are metered as downloads.
+pub(super) struct Connection {
+    conn: quic::Connection,
+    account: bandwidth::Account,
+}
+
+impl Connection {
+    pub fn new(conn: quic::Connection, account: bandwidth::Account) -> Self {
+        Self { conn, account }
+    }
+}
+
 #[async_trait]
-impl io::Connection for quic::Connection {
-    type Read = quic::RecvStream;
+impl io::Connection for Connection {
+    type Read = bandwidth::Metered<quic::RecvStream>;
     type Write = quic::SendStream;
     type Error = error::Connection;
 
     async fn open_stream(&self) -> Result<(Self::Read, Self::Write), Self::Error> {
         use net::connection::Duplex as _;
 
-        let bi = self.open_bidi().await?;
+        let bi = self.conn.open_bidi().await?;
         let up = upgrade::upgrade(bi, upgrade::Git).await?;
-        Ok(up.into_stream().split())
+        let (recv, send) = up.into_stream().split();
+        Ok((self.account.metered(recv), send))
     }
 }
 
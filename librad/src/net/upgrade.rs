@@ -51,6 +51,9 @@ pub struct Interrogation;
 #[derive(Debug)]
 pub struct RequestPull;
 
+#[derive(Debug)]
+pub struct Graft;
+
 /// Signal the (sub-) protocol about to be sent over a given QUIC stream.
 ///
 /// This is only valid as the first message sent by the initiator of a fresh
@@ -91,6 +94,10 @@ pub enum UpgradeRequest {
     Git = 1,
     Membership = 2,
     Interrogation = 3,
+    /// Mutual synchronisation, see [RFC 701][rfc].
+    ///
+    /// [rfc]: https://github.com/radicle-dev/radicle-link/blob/master/docs%2Frfc%2F0701-mutual-synchronisation.adoc
+    Graft = 4,
     /// `RequestPull` is a temporary stream and shall be deprecated in the
     /// future, see [RFC 702][rfc].
     ///
@@ -128,6 +135,12 @@ impl From<RequestPull> for UpgradeRequest {
     }
 }
 
+impl From<Graft> for UpgradeRequest {
+    fn from(_graft: Graft) -> Self {
+        UpgradeRequest::Graft
+    }
+}
+
 impl minicbor::Encode for UpgradeRequest {
     fn encode<W: minicbor::encode::Write>(
         &self,
@@ -154,6 +167,7 @@ impl<'de> minicbor::Decode<'de> for UpgradeRequest {
                 1 => Ok(Self::Git),
                 2 => Ok(Self::Membership),
                 3 => Ok(Self::Interrogation),
+                4 => Ok(Self::Graft),
                 200 => Ok(Self::RequestPull),
                 n => Err(minicbor::decode::Error::UnknownVariant(n as u32)),
             },
@@ -267,6 +281,7 @@ pub enum SomeUpgraded<S> {
     Membership(Upgraded<Membership, S>),
     Interrogation(Upgraded<Interrogation, S>),
     RequestPull(Upgraded<RequestPull, S>),
+    Graft(Upgraded<Graft, S>),
 }
 
 impl<S> SomeUpgraded<S> {
@@ -280,6 +295,7 @@ impl<S> SomeUpgraded<S> {
             Self::Membership(up) => SomeUpgraded::Membership(up.map(f)),
             Self::Interrogation(up) => SomeUpgraded::Interrogation(up.map(f)),
             Self::RequestPull(up) => SomeUpgraded::RequestPull(up.map(f)),
+            Self::Graft(up) => SomeUpgraded::Graft(up.map(f)),
         }
     }
 }
@@ -329,6 +345,7 @@ where
                     SomeUpgraded::Interrogation(Upgraded::new(incoming))
                 },
                 UpgradeRequest::RequestPull => SomeUpgraded::RequestPull(Upgraded::new(incoming)),
+                UpgradeRequest::Graft => SomeUpgraded::Graft(Upgraded::new(incoming)),
             };
 
             Ok(upgrade)
@@ -27,6 +27,8 @@ pub struct Paths {
     socket_dir: PathBuf,
     seeds_file: PathBuf,
     hooks_dir: PathBuf,
+    providers_file: PathBuf,
+    address_book_file: PathBuf,
 }
 
 impl Paths {
@@ -48,6 +50,8 @@ impl Paths {
             socket_dir: socket_dir()?,
             seeds_file: config_dir.join("seeds"),
             hooks_dir: data_dir.join("hooks"),
+            providers_file: cache_dir.join("providers"),
+            address_book_file: data_dir.join("addresses"),
         }
         .init()
     }
@@ -64,6 +68,8 @@ impl Paths {
             socket_dir: socket_dir()?,
             seeds_file: root.join("seeds"),
             hooks_dir: root.join("hooks"),
+            providers_file: root.join("providers"),
+            address_book_file: root.join("addresses"),
         }
         .init()
     }
@@ -99,6 +105,8 @@ impl Paths {
             hooks_dir,
             socket_dir: _,
             seeds_file: _,
+            providers_file: _,
+            address_book_file: _,
         } = self;
 
         vec![
@@ -129,6 +137,18 @@ impl Paths {
     pub fn seeds_file(&self) -> &Path {
         &self.seeds_file
     }
+
+    /// The file the provider cache is persisted to, see
+    /// [`crate::net::protocol::cache::providers`].
+    pub fn providers_file(&self) -> &Path {
+        &self.providers_file
+    }
+
+    /// The file the address book is persisted to, see
+    /// [`crate::net::discovery::book`].
+    pub fn address_book_file(&self) -> &Path {
+        &self.address_book_file
+    }
 }
 
 /// Returns [`ProjectDirs`] for this specific project (`radicle`).
@@ -7,7 +7,7 @@ use std::{
     cmp::max,
     hash::Hash,
     mem,
-    num::NonZeroUsize,
+    num::{NonZeroU32, NonZeroUsize},
     sync::{Arc, Weak},
     thread::{self, Thread},
     time::Instant,
@@ -15,6 +15,7 @@ use std::{
 
 pub use governor::{
     clock::{Clock, DefaultClock},
+    NegativeMultiDecision,
     NotUntil,
     Quota,
 };
@@ -108,4 +109,13 @@ where
         self.maint.as_ref().unwrap().unpark();
         self.inner.check_key(k)
     }
+
+    pub fn check_key_n(
+        &self,
+        k: &T,
+        n: NonZeroU32,
+    ) -> Result<(), NegativeMultiDecision<NotUntil<<DefaultClock as Clock>::Instant>>> {
+        self.maint.as_ref().unwrap().unpark();
+        self.inner.check_key_n(k, n)
+    }
 }
@@ -31,7 +31,7 @@ pub fn blank_peer_info<A: Ord + Clone>(peer_id: PeerId) -> PartialPeerInfo<A> {
         peer_id,
         advertised_info: Some(PeerAdvertisement {
             listen_addrs: iter::empty().into(),
-            capabilities: BTreeSet::new(),
+            capabilities: Some(BTreeSet::new()),
         }),
         seen_addrs: iter::empty().into(),
     }
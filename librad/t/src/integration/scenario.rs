@@ -6,8 +6,11 @@
 mod collaboration;
 mod collaborative_objects;
 mod default_branch_head;
+mod issues;
 mod menage;
 mod passive_replication;
+mod patches;
+mod proposals;
 mod prune;
 mod tracked_references;
 mod tracking_policy;
@@ -10,6 +10,7 @@ use it_helpers::{fixed::TestProject, testnet};
 use lazy_static::lazy_static;
 use librad::{
     collaborative_objects::{
+        op_log,
         CollaborativeObject,
         EntryContents,
         History,
@@ -19,6 +20,7 @@ use librad::{
     },
     git::{
         identities,
+        storage::ReadOnlyStorage as _,
         tracking,
         types::{Namespace, Reference},
     },
@@ -50,6 +52,19 @@ lazy_static! {
         }
     });
     static ref TYPENAME: TypeName = FromStr::from_str("xyz.radicle.testobject").unwrap();
+    static ref OP_SCHEMA: serde_json::Value = serde_json::json!({
+        "$vocabulary": {
+            "https://alexjg.github.io/automerge-jsonschema/spec": true,
+        },
+        "type": "object",
+        "properties": {
+            "emoji": {
+                "type": "string",
+            }
+        },
+        "required": ["emoji"]
+    });
+    static ref OP_TYPENAME: TypeName = FromStr::from_str("xyz.radicle.testoplog").unwrap();
     static ref KEY_ONE: SecretKey = SecretKey::from_seed([
         100, 107, 14, 43, 237, 25, 113, 215, 236, 197, 160, 60, 169, 174, 81, 58, 143, 74, 42, 201,
         122, 252, 143, 21, 82, 225, 111, 252, 12, 186, 4, 154
@@ -371,6 +386,205 @@ fn collab_object_crud() {
     })
 }
 
+#[test]
+fn op_log_crud() {
+    logging::init();
+
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let cache_path = cache_dir.path().to_path_buf();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let object_id = {
+            let urn = proj.project.urn();
+            let owner = proj.owner.urn();
+            peer1
+                .using_storage(move |storage| {
+                    let whoami = identities::local::load(storage, owner).unwrap().unwrap();
+                    let collabs = storage.collaborative_objects(Some(cache_path));
+                    let object = collabs
+                        .create(
+                            &whoami,
+                            &urn,
+                            NewObjectSpec {
+                                history: EntryContents::op_log(&[reaction("+1")]),
+                                message: None,
+                                schema_json: OP_SCHEMA.clone(),
+                                typename: OP_TYPENAME.clone(),
+                            },
+                        )
+                        .unwrap();
+                    assert!(collabs
+                        .update(
+                            &whoami,
+                            &urn,
+                            UpdateObjectSpec {
+                                object_id: *object.id(),
+                                typename: OP_TYPENAME.clone(),
+                                message: None,
+                                changes: EntryContents::op_log(&[serde_json::json!({})]),
+                            },
+                        )
+                        .is_err());
+                    assert!(collabs
+                        .update(
+                            &whoami,
+                            &urn,
+                            UpdateObjectSpec {
+                                object_id: *object.id(),
+                                typename: OP_TYPENAME.clone(),
+                                message: None,
+                                changes: EntryContents::Automerge(vec![]),
+                            },
+                        )
+                        .is_err());
+                    collabs
+                        .update(
+                            &whoami,
+                            &urn,
+                            UpdateObjectSpec {
+                                object_id: *object.id(),
+                                typename: OP_TYPENAME.clone(),
+                                message: None,
+                                changes: EntryContents::op_log(&[
+                                    reaction("heart"),
+                                    reaction("+1"),
+                                ]),
+                            },
+                        )
+                        .unwrap();
+                    *object.id()
+                })
+                .await
+                .unwrap()
+        };
+
+        proj.pull(peer1, peer2).await.unwrap();
+
+        let object = {
+            let urn = proj.project.urn();
+            peer2
+                .using_storage(move |storage| {
+                    storage
+                        .collaborative_objects(None)
+                        .retrieve(&urn, &OP_TYPENAME, &object_id)
+                        .unwrap()
+                        .unwrap()
+                })
+                .await
+                .unwrap()
+        };
+        let reactions = op_log::fold(object.history(), Vec::new(), |mut acc, _, op| {
+            acc.push(op["emoji"].as_str().unwrap().to_owned());
+            acc
+        });
+        assert_eq!(reactions, vec!["+1", "heart", "+1"]);
+    })
+}
+
+/// The cob filter of a tracking entry applies to delegates, too: a denied
+/// object is neither fetched nor expected to be present by validation.
+#[test]
+fn delegate_cob_filter() {
+    logging::init();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+        proj.pull(peer1, peer2).await.unwrap();
+        peer2
+            .using_storage({
+                let peer1_id = peer1.peer_id();
+                let urn = proj.project.urn();
+                move |storage| -> anyhow::Result<()> {
+                    assert!(tracking::track(
+                        storage,
+                        &urn,
+                        Some(peer1_id),
+                        tracking::Config {
+                            cobs: tracking::config::cobs::Cobs::deny_all(),
+                            ..tracking::Config::default()
+                        },
+                        tracking::policy::Track::Any,
+                    )?
+                    .is_ok());
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let object_id = {
+            let urn = proj.project.urn();
+            let owner = proj.owner.urn();
+            peer1
+                .using_storage(move |storage| {
+                    let whoami = identities::local::load(storage, owner).unwrap().unwrap();
+                    let object = storage
+                        .collaborative_objects(None)
+                        .create(
+                            &whoami,
+                            &urn,
+                            NewObjectSpec {
+                                history: EntryContents::op_log(&[reaction("+1")]),
+                                message: None,
+                                schema_json: OP_SCHEMA.clone(),
+                                typename: OP_TYPENAME.clone(),
+                            },
+                        )
+                        .unwrap();
+                    *object.id()
+                })
+                .await
+                .unwrap()
+        };
+
+        let success = proj.pull(peer1, peer2).await.unwrap();
+        assert!(
+            success.validation_errors().is_empty(),
+            "unexpected validation errors: {:?}",
+            success.validation_errors()
+        );
+
+        let has = peer2
+            .using_storage({
+                let urn = proj.project.urn();
+                let cob = Reference::rad_collaborative_object(
+                    Namespace::from(&urn),
+                    peer1.peer_id(),
+                    OP_TYPENAME.clone(),
+                    object_id,
+                );
+                move |storage| storage.has_ref(&cob)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!has, "denied collaborative object was fetched");
+    })
+}
+
+fn reaction(emoji: &str) -> serde_json::Value {
+    serde_json::json!({ "emoji": emoji })
+}
+
 fn init_history() -> EntryContents {
     let mut backend = automerge::Backend::new();
     let mut frontend = automerge::Frontend::new();
@@ -430,6 +644,9 @@ fn evaluate_history(history: &History) -> (automerge::Frontend, automerge::Backe
                 backend.apply_changes(vec![change]).unwrap();
                 std::ops::ControlFlow::Continue(backend)
             },
+            librad::collaborative_objects::EntryContents::OpLog(_) => {
+                unreachable!("test objects use Automerge histories")
+            },
         },
     );
     let mut frontend = automerge::Frontend::new();
@@ -0,0 +1,209 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Index as _;
+
+use it_helpers::{fixed::TestProject, testnet, tmp};
+use librad::{
+    collaborative_objects::{
+        issue::{error, Issues, NewIssue, State, TYPENAME},
+        EntryContents,
+        NewObjectSpec,
+    },
+    git::identities,
+    SecretKey,
+};
+use test_helpers::logging;
+
+fn config() -> testnet::Config {
+    testnet::Config {
+        num_peers: nonzero!(2usize),
+        min_connected: 2,
+        bootstrap: testnet::Bootstrap::from_env(),
+    }
+}
+
+#[test]
+fn issue_lifecycle() {
+    logging::init();
+
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let cache_path = cache_dir.path().to_path_buf();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (id, issue) = {
+            let urn = proj.project.urn();
+            let owner = proj.owner.urn();
+            peer1
+                .using_storage(move |storage| {
+                    let whoami = identities::local::load(storage, owner.clone())
+                        .unwrap()
+                        .unwrap();
+                    let issues = Issues::new(storage.collaborative_objects(Some(cache_path)));
+                    let (id, _) = issues
+                        .create(
+                            &whoami,
+                            &urn,
+                            NewIssue {
+                                title: "Crashes on startup".into(),
+                                description: "Every time".into(),
+                                labels: vec!["bug".into(), "bug".into()],
+                                assignees: vec![],
+                            },
+                        )
+                        .unwrap();
+                    issues
+                        .label(&whoami, &urn, &id, vec!["bug".into(), "p1".into()])
+                        .unwrap();
+                    issues
+                        .unlabel(&whoami, &urn, &id, vec!["bug".into()])
+                        .unwrap();
+                    issues.assign(&whoami, &urn, &id, vec![owner]).unwrap();
+                    let thread = issues
+                        .comment(&whoami, &urn, &id, "Can't reproduce".into(), None)
+                        .unwrap()
+                        .comments[0]
+                        .id;
+                    issues
+                        .comment(
+                            &whoami,
+                            &urn,
+                            &id,
+                            "Try a clean profile".into(),
+                            Some(thread),
+                        )
+                        .unwrap();
+                    assert!(issues
+                        .comment(
+                            &whoami,
+                            &urn,
+                            &id,
+                            "Dangling".into(),
+                            Some("a7b3c1d2-1111-4222-8333-444455556666".parse().unwrap())
+                        )
+                        .is_err());
+                    let issue = issues.close(&whoami, &urn, &id).unwrap();
+                    (id, issue)
+                })
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(issue.state, State::Closed);
+        assert_eq!(issue.labels, vec!["p1".to_owned()]);
+        assert_eq!(issue.assignees, vec![proj.owner.urn()]);
+        let thread = issue.comments[0].id;
+        assert_eq!(
+            issue
+                .threads()
+                .map(|comment| comment.id)
+                .collect::<Vec<_>>(),
+            vec![thread]
+        );
+        assert_eq!(
+            issue
+                .replies(thread)
+                .map(|reply| reply.body.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Try a clean profile"]
+        );
+
+        proj.pull(peer1, peer2).await.unwrap();
+
+        let replicated = {
+            let urn = proj.project.urn();
+            peer2
+                .using_storage(move |storage| {
+                    Issues::new(storage.collaborative_objects(None))
+                        .list(&urn)
+                        .unwrap()
+                })
+                .await
+                .unwrap()
+        };
+        assert_eq!(replicated, vec![(id, issue)]);
+    })
+}
+
+/// Objects of the issue type which don't use the issue schema are not issues.
+#[test]
+fn foreign_schema() {
+    logging::init();
+
+    let storage = tmp::storage(SecretKey::new());
+    let storage = &*storage;
+    let proj = TestProject::create(storage).unwrap();
+    let urn = proj.project.urn();
+    let whoami = identities::local::load(storage, proj.owner.urn())
+        .unwrap()
+        .unwrap();
+
+    let (id, issue) = Issues::new(storage.collaborative_objects(None))
+        .create(
+            &whoami,
+            &urn,
+            NewIssue {
+                title: "Crashes on startup".into(),
+                description: "Every time".into(),
+                labels: vec![],
+                assignees: vec![],
+            },
+        )
+        .unwrap();
+    let foreign = storage
+        .collaborative_objects(None)
+        .create(
+            &whoami,
+            &urn,
+            NewObjectSpec {
+                schema_json: serde_json::json!({
+                    "$vocabulary": {
+                        "https://alexjg.github.io/automerge-jsonschema/spec": true,
+                    },
+                    "type": "object",
+                }),
+                history: init_history(),
+                typename: TYPENAME.clone(),
+                message: None,
+            },
+        )
+        .unwrap();
+
+    let issues = Issues::new(storage.collaborative_objects(None));
+    assert_eq!(issues.list(&urn).unwrap(), vec![(id, issue)]);
+    assert_matches!(
+        issues.get(&urn, foreign.id()),
+        Err(error::Retrieve::SchemaMismatch(mismatch)) if mismatch == *foreign.id()
+    );
+}
+
+fn init_history() -> EntryContents {
+    let mut backend = automerge::Backend::new();
+    let mut frontend = automerge::Frontend::new();
+    let (_, change) = frontend
+        .change::<_, _, automerge::InvalidChangeRequest>(None, |d| {
+            d.add_change(automerge::LocalChange::set(
+                automerge::Path::root().key("title"),
+                automerge::Value::Primitive(automerge::Primitive::Str("Not an issue".into())),
+            ))?;
+            Ok(())
+        })
+        .unwrap();
+    backend.apply_local_change(change.unwrap()).unwrap();
+    let bytes = backend
+        .get_changes(&[])
+        .iter()
+        .flat_map(|c| c.raw_bytes().to_vec())
+        .collect();
+    EntryContents::Automerge(bytes)
+}
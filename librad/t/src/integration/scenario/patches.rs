@@ -0,0 +1,129 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Index as _;
+
+use it_helpers::{fixed::TestProject, testnet};
+use librad::{
+    collaborative_objects::patch::{Anchor, NewPatch, Patches, State, Verdict},
+    git::identities,
+    git_ext::Oid,
+};
+use test_helpers::logging;
+
+fn config() -> testnet::Config {
+    testnet::Config {
+        num_peers: nonzero!(2usize),
+        min_connected: 2,
+        bootstrap: testnet::Bootstrap::from_env(),
+    }
+}
+
+fn commit(content: &[u8]) -> Oid {
+    git2::Oid::hash_object(git2::ObjectType::Commit, content)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn patch_lifecycle() {
+    logging::init();
+
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let cache_path = cache_dir.path().to_path_buf();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (id, patch) = {
+            let urn = proj.project.urn();
+            let owner = proj.owner.urn();
+            peer1
+                .using_storage(move |storage| {
+                    let whoami = identities::local::load(storage, owner).unwrap().unwrap();
+                    let patches = Patches::new(storage.collaborative_objects(Some(cache_path)));
+                    let (id, _) = patches
+                        .create(
+                            &whoami,
+                            &urn,
+                            NewPatch {
+                                title: "Make it faster".into(),
+                                description: "By doing less".into(),
+                                target: "master".into(),
+                                commit: commit(b"first"),
+                                base: None,
+                            },
+                        )
+                        .unwrap();
+                    patches
+                        .revise(
+                            &whoami,
+                            &urn,
+                            &id,
+                            commit(b"second"),
+                            None,
+                            "Addressed review".into(),
+                        )
+                        .unwrap();
+                    patches
+                        .comment(
+                            &whoami,
+                            &urn,
+                            &id,
+                            "Is this needed?".into(),
+                            Some(Anchor {
+                                revision: commit(b"second"),
+                                path: "src/lib.rs".into(),
+                                line: 42,
+                            }),
+                        )
+                        .unwrap();
+                    patches
+                        .review(&whoami, &urn, &id, None, Verdict::Accept, None)
+                        .unwrap();
+                    let patch = patches.merge(&whoami, &urn, &id, None).unwrap();
+                    (id, patch)
+                })
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(patch.state, State::Merged);
+        assert_eq!(patch.revisions.len(), 2);
+        assert_eq!(
+            patch.merged.as_ref().map(|merge| merge.revision),
+            Some(commit(b"second"))
+        );
+        assert_eq!(
+            patch
+                .reviews_of(&commit(b"second"))
+                .map(|review| review.verdict)
+                .collect::<Vec<_>>(),
+            vec![Verdict::Accept]
+        );
+        assert_eq!(patch.comments[0].anchor.as_ref().map(|a| a.line), Some(42));
+
+        proj.pull(peer1, peer2).await.unwrap();
+
+        let replicated = {
+            let urn = proj.project.urn();
+            peer2
+                .using_storage(move |storage| {
+                    Patches::new(storage.collaborative_objects(None))
+                        .list(&urn)
+                        .unwrap()
+                })
+                .await
+                .unwrap()
+        };
+        assert_eq!(replicated, vec![(id, patch)]);
+    })
+}
@@ -0,0 +1,228 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Index as _;
+
+use it_helpers::{
+    fixed::{TestPerson, TestProject},
+    testnet,
+};
+use librad::{
+    git::{
+        identities::{self, project::proposals},
+        refs::Refs,
+        storage::ReadOnlyStorage as _,
+        types::{Force, Namespace, Reference},
+    },
+    identities::payload::{self, ProjectPayload},
+    PeerId,
+};
+use test_helpers::logging;
+
+fn config() -> testnet::Config {
+    testnet::Config {
+        num_peers: nonzero!(2usize),
+        min_connected: 2,
+        bootstrap: testnet::Bootstrap::from_env(),
+    }
+}
+
+/// `peer1` proposes a revision of a project maintained by `peer1` and `peer2`.
+/// The proposal is replicated to `peer2`, which signs it, thereby reaching
+/// quorum. The signed proposal is replicated back to `peer1`, which adopts it.
+#[test]
+fn sign_replicated_proposal() {
+    logging::init();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+        let person = peer2
+            .using_storage(TestPerson::create)
+            .await
+            .unwrap()
+            .unwrap();
+        proj.maintainers(peer1)
+            .add(&person, peer2)
+            .setup()
+            .await
+            .unwrap();
+
+        let proposal = peer1
+            .using_storage({
+                let urn = proj.project.urn();
+                move |storage| {
+                    proposals::propose(
+                        storage,
+                        &urn,
+                        None,
+                        ProjectPayload::new(payload::Project {
+                            description: Some("pea two pea, proposed".into()),
+                            ..TestProject::default_payload()
+                        }),
+                        None,
+                    )
+                }
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        proj.pull(peer1, peer2).await.unwrap();
+        let status = peer2
+            .using_storage({
+                let urn = proj.project.urn();
+                move |storage| -> anyhow::Result<Vec<proposals::Proposal>> {
+                    proposals::sign(storage, &urn, proposal.revision)?;
+                    Ok(proposals::status(storage, &urn)?)
+                }
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            status
+                .iter()
+                .any(|p| p.revision == proposal.revision && p.adopted),
+            "peer 2 did not adopt the proposal: {:?}",
+            status
+        );
+
+        proj.pull(peer2, peer1).await.unwrap();
+        let status = peer1
+            .using_storage({
+                let urn = proj.project.urn();
+                move |storage| proposals::status(storage, &urn)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        let signed = status
+            .iter()
+            .find(|p| p.revision == proposal.revision)
+            .expect("peer 1 is missing the proposal");
+        assert!(signed.signers.contains(peer1.peer_id().as_public_key()));
+        assert!(signed.signers.contains(peer2.peer_id().as_public_key()));
+
+        let merged = peer1
+            .using_storage({
+                let urn = proj.project.urn();
+                let peer_id = peer2.peer_id();
+                move |storage| identities::project::merge(storage, &urn, peer_id)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.revision, proposal.revision);
+    })
+}
+
+/// `peer2` only accepts proposals of `peer1` which are revisions of the
+/// project, and forgets them once `peer1` no longer has them.
+#[test]
+fn replicated_proposals_are_checked() {
+    logging::init();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let peer1 = net.peers().index(0);
+        let peer2 = net.peers().index(1);
+
+        let proj = peer1
+            .using_storage(TestProject::create)
+            .await
+            .unwrap()
+            .unwrap();
+        let person = peer2
+            .using_storage(TestPerson::create)
+            .await
+            .unwrap()
+            .unwrap();
+        proj.maintainers(peer1)
+            .add(&person, peer2)
+            .setup()
+            .await
+            .unwrap();
+        let urn = proj.project.urn();
+
+        let proposal = peer1
+            .using_storage({
+                let urn = urn.clone();
+                let bogus = proj.owner.clone();
+                move |storage| -> anyhow::Result<_> {
+                    let proposal = proposals::propose(
+                        storage,
+                        &urn,
+                        None,
+                        ProjectPayload::new(payload::Project {
+                            description: Some("pea two pea, proposed".into()),
+                            ..TestProject::default_payload()
+                        }),
+                        None,
+                    )?;
+                    // A revision of another identity, posing as a proposal
+                    let repo = git2::Repository::open_bare(storage.path())?;
+                    Reference::rad_id_proposal(
+                        Namespace::from(&urn),
+                        None::<PeerId>,
+                        bogus.revision,
+                    )
+                    .create(&repo, *bogus.content_id, Force::True, "bogus")?;
+                    Refs::update(storage, &urn)?;
+                    Ok(proposal)
+                }
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        proj.pull(peer1, peer2).await.unwrap();
+        let status = peer2
+            .using_storage({
+                let urn = urn.clone();
+                move |storage| proposals::status(storage, &urn)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            status.iter().map(|p| p.revision).collect::<Vec<_>>(),
+            vec![proposal.revision]
+        );
+
+        peer1
+            .using_storage({
+                let urn = urn.clone();
+                move |storage| -> anyhow::Result<()> {
+                    let proposals =
+                        Reference::rad_id_proposals(Namespace::from(&urn), None::<PeerId>);
+                    let references = storage
+                        .references(&proposals)?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for mut reference in references {
+                        reference.delete()?;
+                    }
+                    Refs::update(storage, &urn)?;
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        proj.pull(peer1, peer2).await.unwrap();
+        let status = peer2
+            .using_storage(move |storage| proposals::status(storage, &urn))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(status.is_empty(), "stale proposals: {:?}", status);
+    })
+}
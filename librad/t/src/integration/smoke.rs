@@ -6,6 +6,7 @@
 mod clone;
 mod fetch_limit;
 mod gossip;
+mod graft;
 mod interrogation;
 mod regression;
 mod request_pull;
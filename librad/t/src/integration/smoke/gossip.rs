@@ -231,7 +231,7 @@ fn ask_and_clone() {
         let project_urn = proj.project.urn();
 
         let provider = peer2
-            .providers(project_urn.clone(), Duration::from_secs(5))
+            .providers(project_urn.clone(), Duration::from_secs(5), false)
             .next()
             .await;
         assert_eq!(
@@ -240,6 +240,17 @@ fn ask_and_clone() {
             "Expected to have obtained peer1 as provider, but got nothing instead"
         );
 
+        // peer1 is now in peer2's provider cache
+        let provider = peer2
+            .providers(project_urn.clone(), Duration::from_secs(0), true)
+            .next()
+            .await;
+        assert_eq!(
+            Some(peer1.peer_id()),
+            provider.map(|info| info.peer_id),
+            "Expected to have obtained peer1 from the provider cache"
+        );
+
         async fn has_urn<P, S, G>(peer: &P, urn: Urn) -> bool
         where
             P: Deref<Target = Peer<S, G>>,
@@ -0,0 +1,102 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{ops::Index as _, time::Duration};
+
+use it_helpers::{fixed::TestPerson, testnet};
+use librad::net::protocol::{
+    event::{self, upstream::predicate},
+    graft::{Outcome, Synced},
+};
+use test_helpers::logging;
+
+fn config() -> testnet::Config {
+    testnet::Config {
+        num_peers: nonzero!(2usize),
+        min_connected: 2,
+        bootstrap: testnet::Bootstrap::from_env(),
+    }
+}
+
+/// `requester` has a stale copy of the `responder`'s identity, and gets the
+/// update by grafting.
+#[test]
+fn fetches_missing_tips() {
+    logging::init();
+
+    let net = testnet::run(config()).unwrap();
+    net.enter(async {
+        let responder = net.peers().index(0);
+        let requester = net.peers().index(1);
+
+        let person = responder
+            .using_storage(TestPerson::create)
+            .await
+            .unwrap()
+            .unwrap();
+        person.pull(responder, requester).await.unwrap();
+
+        let events = responder.subscribe();
+        let person = responder
+            .using_storage(move |storage| person.update(storage))
+            .await
+            .unwrap()
+            .unwrap();
+        let urn = person.owner.urn();
+
+        // Make sure responder had a chance to refresh its caches
+        if responder.stats().await.caches.urns.elements < 1 {
+            futures::pin_mut!(events);
+            event::upstream::expect(
+                events,
+                predicate::urn_cache_len(|len| len >= 1),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        }
+
+        let replicated = requester.subscribe();
+        let grafted = requester
+            .client()
+            .unwrap()
+            .graft((responder.peer_id(), responder.listen_addrs().to_vec()))
+            .await
+            .unwrap();
+
+        let synced = grafted
+            .fetched
+            .synced
+            .iter()
+            .find(|synced| synced.urn == urn)
+            .expect("requester did not sync the identity");
+        assert!(
+            matches!(
+                synced,
+                Synced {
+                    outcome: Outcome::Replicated(_),
+                    ..
+                }
+            ),
+            "unexpected outcome: {:?}",
+            synced.outcome
+        );
+        // The responder already has everything the requester has
+        let remote = grafted
+            .remote
+            .synced
+            .iter()
+            .find(|synced| synced.urn == urn)
+            .expect("responder did not consider the identity");
+        assert_eq!(remote.outcome, Outcome::UpToDate);
+
+        futures::pin_mut!(replicated);
+        event::upstream::expect(
+            replicated,
+            predicate::replicated_from(responder.peer_id(), urn),
+            Duration::from_secs(1),
+        )
+        .await
+        .expect("requester did not emit a replicated event");
+    })
+}
@@ -11,6 +11,7 @@ use librad::{
     identities::SomeUrn,
     net::protocol::{
         event::{self, upstream::predicate},
+        Capability,
         PeerAdvertisement,
     },
 };
@@ -70,7 +71,7 @@ fn responds() {
             PeerAdvertisement {
                 listen_addrs: BoundedVec::try_from_length(responder.listen_addrs().to_vec())
                     .unwrap(),
-                capabilities: Default::default(),
+                capabilities: Some(Capability::supported()),
             },
             interrogation.peer_advertisement().await.unwrap()
         );
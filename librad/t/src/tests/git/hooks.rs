@@ -0,0 +1,70 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryFrom as _;
+
+use git_ref_format::RefString;
+use librad::{
+    git::{hooks, Urn},
+    net::replication::Updated,
+};
+use radicle_git_ext as ext;
+
+fn oid(s: &str) -> ext::Oid {
+    ext::Oid::from(git2::Oid::hash_object(git2::ObjectType::Blob, s.as_bytes()).unwrap())
+}
+
+fn urn(path: Option<&str>) -> Urn {
+    Urn::new(oid("project")).with_path(path.map(|p| ext::RefLike::try_from(p).unwrap()))
+}
+
+fn name(path: &str) -> RefString {
+    RefString::try_from(format!(
+        "refs/namespaces/{}/{}",
+        urn(None).encode_id(),
+        path
+    ))
+    .unwrap()
+}
+
+#[test]
+fn data_notifications() {
+    let zero = ext::Oid::from(git2::Oid::zero());
+    let updated = vec![
+        Updated::Direct {
+            name: name("refs/rad/id"),
+            target: oid("id").into(),
+            prev: None,
+        },
+        Updated::Direct {
+            name: name("refs/remotes/peer/heads/main"),
+            target: oid("new").into(),
+            prev: Some(oid("old").into()),
+        },
+        Updated::Symbolic {
+            name: name("refs/rad/ids/x"),
+            target: RefString::try_from("refs/namespaces/x/refs/rad/id").unwrap(),
+        },
+        Updated::Prune {
+            name: name("refs/remotes/peer/heads/gone"),
+            prev: Some(oid("gone").into()),
+        },
+    ];
+
+    let data = hooks::data(&updated)
+        .map(|d| (d.urn, d.old, d.new))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        data,
+        vec![
+            (urn(None), zero, oid("id")),
+            (urn(Some("refs/rad/id")), zero, oid("id")),
+            (
+                urn(Some("refs/remotes/peer/heads/main")),
+                oid("old"),
+                oid("new")
+            ),
+            (urn(Some("refs/remotes/peer/heads/gone")), oid("gone"), zero),
+        ]
+    );
+}
@@ -0,0 +1,73 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::time::Duration;
+
+use librad::{
+    git::storage::{gc, Storage},
+    paths::Paths,
+    SecretKey,
+};
+
+struct Blobs {
+    referenced: git2::Oid,
+    unreferenced: git2::Oid,
+}
+
+fn setup(paths: &Paths) -> (Storage, git2::Repository, Blobs) {
+    let storage = Storage::open(paths, SecretKey::new()).unwrap();
+    let repo = git2::Repository::open_bare(paths.git_dir()).unwrap();
+    let referenced = repo.blob(b"referenced").unwrap();
+    let unreferenced = repo.blob(b"unreferenced").unwrap();
+    repo.reference(
+        "refs/namespaces/hnrkyghsrokxzxpy9pww69xr11dr9q7edbxfo/refs/tags/keep",
+        referenced,
+        false,
+        "keep",
+    )
+    .unwrap();
+
+    (
+        storage,
+        repo,
+        Blobs {
+            referenced,
+            unreferenced,
+        },
+    )
+}
+
+#[test]
+fn prunes_unreachable() {
+    let tmp = tempfile::tempdir().unwrap();
+    let paths = Paths::from_root(&tmp).unwrap();
+    let (storage, repo, blobs) = setup(&paths);
+
+    let stats = gc::collect(
+        &storage,
+        &gc::Options {
+            expire: Duration::ZERO,
+            aggressive: false,
+        },
+    )
+    .unwrap();
+
+    let odb = repo.odb().unwrap();
+    assert!(odb.exists(blobs.referenced));
+    assert!(!odb.exists(blobs.unreferenced));
+    assert_eq!(stats.after.loose, 0);
+    assert!(stats.after.packs > 0);
+}
+
+#[test]
+fn keeps_recent_unreachable() {
+    let tmp = tempfile::tempdir().unwrap();
+    let paths = Paths::from_root(&tmp).unwrap();
+    let (storage, repo, blobs) = setup(&paths);
+
+    gc::collect(&storage, &gc::Options::default()).unwrap();
+
+    let odb = repo.odb().unwrap();
+    assert!(odb.exists(blobs.referenced));
+    assert!(odb.exists(blobs.unreferenced));
+}
@@ -0,0 +1,81 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+
+use librad::{
+    git::storage::{maintenance, Storage},
+    paths::Paths,
+    SecretKey,
+};
+
+const NAMESPACE: &str = "refs/namespaces/hnrkyghsrokxzxpy9pww69xr11dr9q7edbxfo";
+
+#[test]
+fn packs_loose_objects() {
+    let tmp = tempfile::tempdir().unwrap();
+    let paths = Paths::from_root(&tmp).unwrap();
+    let storage = Storage::open(&paths, SecretKey::new()).unwrap();
+    let repo = git2::Repository::open_bare(paths.git_dir()).unwrap();
+    let blob = repo.blob(b"maintained").unwrap();
+    let unreferenced = repo.blob(b"unreferenced").unwrap();
+    repo.reference(
+        &format!("{}/refs/tags/keep", NAMESPACE),
+        blob,
+        false,
+        "keep",
+    )
+    .unwrap();
+
+    let loose = |oid: git2::Oid| {
+        let hex = oid.to_string();
+        paths
+            .git_dir()
+            .join("objects")
+            .join(&hex[..2])
+            .join(&hex[2..])
+    };
+    let mtime = fs::metadata(loose(unreferenced))
+        .unwrap()
+        .modified()
+        .unwrap();
+
+    let stats = maintenance::run(&storage, &maintenance::Options::default()).unwrap();
+
+    assert!(stats.before.loose > 0);
+    assert_eq!(stats.after.packs, 1);
+    let odb = repo.odb().unwrap();
+    assert!(odb.exists(blob));
+    assert!(odb.exists(unreferenced));
+
+    // Only reachable objects are packed, unreachable ones keep their mtime
+    assert!(!loose(blob).exists());
+    assert_eq!(
+        fs::metadata(loose(unreferenced))
+            .unwrap()
+            .modified()
+            .unwrap(),
+        mtime
+    );
+}
+
+#[test]
+fn configures_delta_islands() {
+    let tmp = tempfile::tempdir().unwrap();
+    let paths = Paths::from_root(&tmp).unwrap();
+    let storage = Storage::open(&paths, SecretKey::new()).unwrap();
+
+    // Idempotent
+    maintenance::configure(&storage).unwrap();
+    maintenance::configure(&storage).unwrap();
+
+    let config = git2::Config::open(&storage.config_path()).unwrap();
+    let mut islands = Vec::new();
+    config
+        .multivar("pack.island", None)
+        .unwrap()
+        .for_each(|entry| islands.push(entry.value().unwrap().to_owned()))
+        .unwrap();
+    assert_eq!(islands, vec!["^refs/namespaces/([^/]+)/".to_owned()]);
+    assert!(config.get_bool("repack.useDeltaIslands").unwrap());
+}
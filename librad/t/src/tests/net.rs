@@ -3,7 +3,9 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
+mod bandwidth;
 mod codec;
+mod discovery;
 mod peer;
 mod protocol;
 mod tls;
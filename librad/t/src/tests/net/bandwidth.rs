@@ -0,0 +1,154 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{io, num::NonZeroU64, time::Duration};
+
+use futures::{executor::block_on, io::AsyncWriteExt as _};
+use librad::{
+    git::Urn,
+    git_ext::Oid,
+    net::bandwidth::{Bandwidth, Direction, Exceeded, Limit, Limits, Quota, Scope, MAX_ENTRIES},
+    PeerId,
+    SecretKey,
+};
+
+fn peer() -> PeerId {
+    PeerId::from(SecretKey::new())
+}
+
+fn urn(name: &str) -> Urn {
+    Urn::new(Oid::from(
+        git2::Oid::hash_object(git2::ObjectType::Blob, name.as_bytes()).unwrap(),
+    ))
+}
+
+fn limit(kib: u64) -> Option<Limit> {
+    Some(Limit {
+        bytes: NonZeroU64::new(kib * 1024).unwrap(),
+        per: Duration::from_secs(3600),
+    })
+}
+
+fn exceeded(e: io::Error) -> Exceeded {
+    *e.into_inner().unwrap().downcast::<Exceeded>().unwrap()
+}
+
+#[test]
+fn usage() {
+    let bandwidth = Bandwidth::default();
+    let peer = peer();
+    let urn = urn("usage");
+
+    let account = bandwidth.account(Direction::Upload, peer, None);
+    let mut sink = account.metered(Vec::new());
+    block_on(sink.write_all(&[0; 1000])).unwrap();
+    account.set_urn(urn.clone());
+    block_on(sink.write_all(&[0; 2000])).unwrap();
+
+    let stats = bandwidth.stats();
+    assert_eq!(stats.total.upload, 3000);
+    assert_eq!(stats.total.download, 0);
+    assert_eq!(stats.peers[&peer].upload, 3000);
+    assert_eq!(stats.urns[&urn].upload, 2000);
+}
+
+#[test]
+fn peer_limit() {
+    let bandwidth = Bandwidth::new(Quota {
+        upload: Limits {
+            per_peer: limit(4),
+            per_urn: None,
+        },
+        ..Default::default()
+    });
+    let peer = peer();
+
+    let account = bandwidth.account(Direction::Upload, peer, Some(urn("peer_limit")));
+    let mut sink = account.metered(Vec::new());
+    block_on(sink.write_all(&[0; 4096])).unwrap();
+    let err = exceeded(block_on(sink.write_all(&[0; 1024])).unwrap_err());
+    assert_eq!(err.direction, Direction::Upload);
+    assert_eq!(err.scope, Scope::Peer(peer));
+
+    // Exhausted for all URNs of the same peer, but not for other peers
+    assert!(bandwidth
+        .account(Direction::Upload, peer, Some(urn("other")))
+        .check()
+        .is_err());
+    assert!(bandwidth
+        .account(Direction::Upload, self::peer(), None)
+        .check()
+        .is_ok());
+    // Downloads are limited separately
+    assert!(bandwidth
+        .account(Direction::Download, peer, None)
+        .check()
+        .is_ok());
+}
+
+#[test]
+fn urn_limit() {
+    let bandwidth = Bandwidth::new(Quota {
+        download: Limits {
+            per_peer: None,
+            per_urn: limit(2),
+        },
+        ..Default::default()
+    });
+    let urn = urn("urn_limit");
+
+    let account = bandwidth.account(Direction::Download, peer(), Some(urn.clone()));
+    let mut sink = account.metered(Vec::new());
+    block_on(sink.write_all(&[0; 2048])).unwrap();
+
+    // Exhausted across peers
+    let err = bandwidth
+        .account(Direction::Download, peer(), Some(urn.clone()))
+        .check()
+        .unwrap_err();
+    assert_eq!(err.scope, Scope::Urn(urn));
+}
+
+#[test]
+fn usage_is_bounded() {
+    let bandwidth = Bandwidth::default();
+    for _ in 0..MAX_ENTRIES + 10 {
+        let account = bandwidth.account(Direction::Download, peer(), None);
+        block_on(account.metered(Vec::new()).write_all(&[0; 10])).unwrap();
+    }
+    let recent = peer();
+    let account = bandwidth.account(Direction::Download, recent, None);
+    block_on(account.metered(Vec::new()).write_all(&[0; 10])).unwrap();
+
+    let stats = bandwidth.stats();
+    assert_eq!(stats.total.download, 10 * (MAX_ENTRIES as u64 + 11));
+    assert_eq!(stats.peers.len(), MAX_ENTRIES);
+    assert!(stats.peers.contains_key(&recent));
+}
+
+#[test]
+fn exceeding_one_budget_charges_neither() {
+    let bandwidth = Bandwidth::new(Quota {
+        upload: Limits {
+            per_peer: limit(4),
+            per_urn: limit(2),
+        },
+        ..Default::default()
+    });
+    let peer = peer();
+    let exhausted = urn("exhausted");
+
+    let account = bandwidth.account(Direction::Upload, peer, Some(exhausted.clone()));
+    block_on(account.metered(Vec::new()).write_all(&[0; 2048])).unwrap();
+    for _ in 0..3 {
+        let err = bandwidth
+            .account(Direction::Upload, peer, Some(exhausted.clone()))
+            .check()
+            .unwrap_err();
+        assert_eq!(err.scope, Scope::Urn(exhausted.clone()));
+    }
+
+    // The failed checks didn't eat into the remaining budget of the peer
+    let account = bandwidth.account(Direction::Upload, peer, Some(urn("other")));
+    block_on(account.metered(Vec::new()).write_all(&[0; 2048])).unwrap();
+}
@@ -0,0 +1,180 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{
+    net::SocketAddr,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use futures::{executor::block_on, future, StreamExt as _};
+use librad::{
+    data::BoundedVec,
+    net::{
+        discovery::{book, mdns, AddressBook, Discovery as _, Mdns},
+        protocol::{membership::Transition, PartialPeerInfo},
+        Network,
+    },
+    PeerId,
+    SecretKey,
+};
+use tempfile::tempdir;
+
+fn peer() -> PeerId {
+    PeerId::from(SecretKey::new())
+}
+
+fn info(peer_id: PeerId, port: u16) -> PartialPeerInfo<SocketAddr> {
+    PartialPeerInfo {
+        peer_id,
+        advertised_info: None,
+        seen_addrs: BoundedVec::singleton(SocketAddr::from(([127, 0, 0, 1], port))),
+    }
+}
+
+#[test]
+fn promotions_rank_first() {
+    let tmp = tempdir().unwrap();
+    let mut book = AddressBook::new(tmp.path().join("addresses"), book::Config::default());
+    let (a, b) = (peer(), peer());
+    book.transition(&Transition::Promoted(info(a, 1)));
+    book.transition(&Transition::Promoted(info(b, 2)));
+    book.transition(&Transition::Promoted(info(b, 3)));
+    book.transition(&Transition::Evicted(info(peer(), 4)));
+
+    let best = book.best(2);
+    assert_eq!(
+        best.iter().map(|e| e.peer_id).collect::<Vec<_>>(),
+        vec![b, a]
+    );
+    assert_eq!(best[0].successes, 2);
+    assert_eq!(
+        best[0].addrs,
+        vec![
+            SocketAddr::from(([127, 0, 0, 1], 3)),
+            SocketAddr::from(([127, 0, 0, 1], 2))
+        ]
+    );
+}
+
+#[test]
+fn evicts_least_successful() {
+    let tmp = tempdir().unwrap();
+    let mut book = AddressBook::new(
+        tmp.path().join("addresses"),
+        book::Config {
+            capacity: 2,
+            ..Default::default()
+        },
+    );
+    let (a, b, c) = (peer(), peer(), peer());
+    book.seen(info(a, 1), true);
+    book.seen(info(b, 2), true);
+    book.seen(info(b, 2), true);
+    // The newly seen peer is kept, even though it was never promoted
+    book.seen(info(c, 3), false);
+
+    assert_eq!(book.len(), 2);
+    assert!(book.get(&a).is_none());
+    assert!(book.get(&c).is_some());
+}
+
+#[test]
+fn persist_and_load() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("addresses");
+    let a = peer();
+    {
+        let mut book = AddressBook::new(&path, book::Config::default());
+        book.seen(info(a, 1), true);
+        book.persist().unwrap();
+    }
+
+    let book = AddressBook::load(&path, book::Config::default()).unwrap();
+    assert_eq!(book.len(), 1);
+    assert_eq!(book.get(&a).map(|e| e.successes), Some(1));
+}
+
+#[test]
+fn load_forgets_aged_entries() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("addresses");
+    {
+        let mut book = AddressBook::new(&path, book::Config::default());
+        book.seen(info(peer(), 1), true);
+        book.persist().unwrap();
+    }
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    let book = AddressBook::load(
+        &path,
+        book::Config {
+            max_age: std::time::Duration::from_secs(0),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(book.is_empty());
+}
+
+#[test]
+fn mdns_service_roundtrip() {
+    let local = peer();
+    let addr = SocketAddr::from(([192, 168, 1, 2], 8776));
+    let service = mdns::service(&local, &Network::Main, &[addr]).unwrap();
+
+    assert_eq!(
+        mdns::resolve(&service, &Network::Main),
+        Some((local, vec![addr]))
+    );
+    assert_eq!(
+        mdns::resolve(&service, &"lab".parse::<Network>().unwrap()),
+        None
+    );
+}
+
+/// Announces peers of the main and another network via mDNS, and checks that
+/// only the former is discovered, and not the discovering peer itself.
+///
+/// Requires multicast on the host's network interfaces, run with `cargo test
+/// -- --ignored mdns_loopback`.
+#[test]
+#[ignore]
+fn mdns_loopback() {
+    let lab = "lab".parse::<Network>().unwrap();
+    let addr = SocketAddr::from(([0, 0, 0, 0], 8776));
+
+    let (main, other, local) = (peer(), peer(), peer());
+    let announced = Mdns::new(main, Network::Main).unwrap();
+    announced.announce(&[addr]).unwrap();
+    let elsewhere = Mdns::new(other, lab).unwrap();
+    elsewhere.announce(&[addr]).unwrap();
+    let discovering = Mdns::new(local, Network::Main).unwrap();
+    discovering.announce(&[addr]).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        block_on(discovering.discover().for_each(|(peer, _)| {
+            tx.send(peer).ok();
+            future::ready(())
+        }))
+    });
+
+    let mut found = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while !found.contains(&main) {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        found.push(rx.recv_timeout(timeout).expect("peer not discovered"));
+    }
+    // Give the other announcements a chance to show up
+    while let Ok(peer) = rx.recv_timeout(Duration::from_secs(2)) {
+        found.push(peer);
+    }
+
+    assert!(
+        found.iter().all(|peer| peer == &main),
+        "discovered unexpected peers: {:?}",
+        found
+    );
+}
@@ -4,4 +4,6 @@
 // Linking Exception. For full terms see the included LICENSE file.
 
 mod broadcast;
+mod cache;
 mod gossip;
+mod info;
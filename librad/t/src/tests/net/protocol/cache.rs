@@ -0,0 +1,96 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use librad::{
+    git::Urn,
+    git_ext,
+    net::protocol::cache::providers::{Providers, K},
+    reflike,
+    PeerId,
+    SecretKey,
+};
+use tempfile::tempdir;
+
+fn urn(n: u8) -> Urn {
+    Urn::new(git_ext::Oid::from(git2::Oid::from_bytes(&[n; 20]).unwrap()))
+}
+
+fn peer() -> PeerId {
+    PeerId::from(SecretKey::new())
+}
+
+#[test]
+fn most_recent_provider_first() {
+    let cache = Providers::new(1);
+    let (a, b) = (peer(), peer());
+    cache.insert(&urn(0), a, vec![]);
+    cache.insert(&urn(0), b, vec![]);
+    cache.insert(&urn(0), a, vec![]);
+
+    let providers = cache
+        .get(&urn(0))
+        .into_iter()
+        .map(|p| p.peer_id)
+        .collect::<Vec<_>>();
+    assert_eq!(providers, vec![a, b])
+}
+
+#[test]
+fn bucket_evicts_least_recently_seen() {
+    let cache = Providers::new(1);
+    let first = peer();
+    cache.insert(&urn(0), first, vec![]);
+    for _ in 0..K {
+        cache.insert(&urn(0), peer(), vec![]);
+    }
+
+    let providers = cache.get(&urn(0));
+    assert_eq!(providers.len(), K);
+    assert!(providers.iter().all(|p| p.peer_id != first))
+}
+
+#[test]
+fn ignores_urn_path() {
+    let cache = Providers::new(1);
+    let provider = peer();
+    cache.insert(
+        &urn(0).with_path(reflike!("refs/heads/main")),
+        provider,
+        vec![],
+    );
+
+    assert_eq!(
+        cache.get(&urn(0)).first().map(|p| p.peer_id),
+        Some(provider)
+    )
+}
+
+#[test]
+fn bounded_number_of_urns() {
+    let cache = Providers::new(2);
+    for n in 0..3 {
+        cache.insert(&urn(n), peer(), vec![]);
+    }
+
+    let stats = cache.stats();
+    assert_eq!(stats.urns, 2);
+    assert_eq!(stats.providers, 2)
+}
+
+#[test]
+fn persists_on_drop() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("providers");
+    let provider = peer();
+    let addr = "127.0.0.1:8776".parse().unwrap();
+    {
+        let cache = Providers::load(&path, 1).unwrap();
+        cache.insert(&urn(0), provider, vec![addr]);
+    }
+
+    let cache = Providers::load(&path, 1).unwrap();
+    let providers = cache.get(&urn(0));
+    assert_eq!(providers.len(), 1);
+    assert_eq!(providers[0].peer_id, provider);
+    assert_eq!(providers[0].addrs, vec![addr])
+}
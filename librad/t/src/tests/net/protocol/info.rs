@@ -0,0 +1,150 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::{collections::BTreeSet, net::SocketAddr};
+
+use librad::{
+    data::BoundedVec,
+    net::protocol::{Capability, PeerAdvertisement},
+};
+use test_helpers::roundtrip;
+
+/// The encoding of `PeerAdvertisement` before any capabilities were defined.
+#[derive(Clone, Debug, PartialEq, minicbor::Encode, minicbor::Decode)]
+#[cbor(array)]
+struct AdvertisementV1 {
+    #[n(0)]
+    listen_addrs: Vec<SocketAddr>,
+    #[n(2)]
+    capabilities: BTreeSet<CapabilityV1>,
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, minicbor::Encode, minicbor::Decode)]
+enum CapabilityV1 {
+    #[n(0)]
+    Reserved,
+}
+
+fn listen_addrs() -> Vec<SocketAddr> {
+    vec![
+        "127.0.0.1:8776".parse().unwrap(),
+        "[::1]:8776".parse().unwrap(),
+    ]
+}
+
+fn advertisement() -> PeerAdvertisement<SocketAddr> {
+    PeerAdvertisement {
+        listen_addrs: BoundedVec::try_from_length(listen_addrs()).unwrap(),
+        capabilities: Some(Capability::supported()),
+    }
+}
+
+#[test]
+fn roundtrip_capability() {
+    let reserved = [Capability::CobFilter, Capability::PackLimit];
+    for capability in Capability::supported().into_iter().chain(reserved) {
+        roundtrip::cbor(capability)
+    }
+}
+
+#[test]
+fn reserved_capabilities_are_not_supported() {
+    let supported = Capability::supported();
+    assert!(!supported.contains(&Capability::CobFilter));
+    assert!(!supported.contains(&Capability::PackLimit));
+}
+
+#[test]
+fn roundtrip_advertisement() {
+    roundtrip::cbor(advertisement());
+    roundtrip::cbor(PeerAdvertisement {
+        capabilities: Some(BTreeSet::new()),
+        ..advertisement()
+    });
+    roundtrip::cbor(PeerAdvertisement {
+        capabilities: None,
+        ..advertisement()
+    })
+}
+
+#[test]
+fn unknown_capabilities() {
+    let ad = PeerAdvertisement {
+        capabilities: Some(
+            vec![Capability::Graft, Capability::from(42)]
+                .into_iter()
+                .collect(),
+        ),
+        ..advertisement()
+    };
+    let decoded: PeerAdvertisement<SocketAddr> =
+        minicbor::decode(&minicbor::to_vec(&ad).unwrap()).unwrap();
+
+    assert_eq!(decoded, ad);
+    assert!(decoded.supports(&Capability::Graft));
+    assert!(decoded.supports(&Capability::Unknown(42)));
+    assert!(!decoded.supports(&Capability::RequestPull));
+}
+
+#[test]
+fn no_capabilities() {
+    let ad = PeerAdvertisement {
+        capabilities: Some(BTreeSet::new()),
+        ..advertisement()
+    };
+    assert!(!ad.supports(&Capability::RequestPull));
+    assert!(!ad.supports(&Capability::Graft));
+}
+
+#[test]
+fn backwards_compat_v1() {
+    let v2 = advertisement();
+    let v1: AdvertisementV1 = minicbor::decode(&minicbor::to_vec(&v2).unwrap()).unwrap();
+
+    assert_eq!(
+        v1,
+        AdvertisementV1 {
+            listen_addrs: listen_addrs(),
+            capabilities: BTreeSet::new(),
+        }
+    )
+}
+
+#[test]
+fn forwards_compat_v2() {
+    let v1 = AdvertisementV1 {
+        listen_addrs: listen_addrs(),
+        capabilities: vec![CapabilityV1::Reserved].into_iter().collect(),
+    };
+    let v2: PeerAdvertisement<SocketAddr> =
+        minicbor::decode(&minicbor::to_vec(&v1).unwrap()).unwrap();
+
+    assert_eq!(
+        v2,
+        PeerAdvertisement {
+            listen_addrs: BoundedVec::try_from_length(listen_addrs()).unwrap(),
+            capabilities: None,
+        }
+    )
+}
+
+/// Peers predating capabilities never encode index 3, but answer request-pull.
+#[test]
+fn legacy_advertisement() {
+    let v1 = AdvertisementV1 {
+        listen_addrs: listen_addrs(),
+        capabilities: BTreeSet::new(),
+    };
+    let v2: PeerAdvertisement<SocketAddr> =
+        minicbor::decode(&minicbor::to_vec(&v1).unwrap()).unwrap();
+
+    assert_eq!(v2.capabilities, None);
+    assert!(v2.supports(&Capability::RequestPull));
+    assert!(!v2.supports(&Capability::Graft));
+
+    // And we encode it the same way
+    assert_eq!(
+        minicbor::to_vec(&v2).unwrap(),
+        minicbor::to_vec(&v1).unwrap()
+    );
+}
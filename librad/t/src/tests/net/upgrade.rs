@@ -11,6 +11,7 @@ use librad::{
         Error,
         Git,
         Gossip,
+        Graft,
         Interrogation,
         Membership,
         RequestPull,
@@ -84,6 +85,11 @@ async fn upgrade_request_pull() {
     )
 }
 
+#[tokio::test]
+async fn upgrade_graft() {
+    assert_matches!(test_upgrade(Graft).await, Ok(SomeUpgraded::Graft(_)))
+}
+
 #[test]
 fn roundtrip_upgrade_request() {
     roundtrip::cbor(UpgradeRequest::Gossip);
@@ -91,4 +97,5 @@ fn roundtrip_upgrade_request() {
     roundtrip::cbor(UpgradeRequest::Membership);
     roundtrip::cbor(UpgradeRequest::Interrogation);
     roundtrip::cbor(UpgradeRequest::RequestPull);
+    roundtrip::cbor(UpgradeRequest::Graft);
 }
@@ -63,7 +63,7 @@ pub trait Index {
         cache: &mut impl DecodeEntry,
     ) -> Result<Option<Object<'a>>, error::Lookup<E>>
     where
-        F: FnOnce(&pack::Info) -> Result<Arc<pack::Data>, E>;
+        F: Fn(&pack::Info) -> Result<Arc<pack::Data>, E>;
 }
 
 /// An [`Index`] which can be shared between threads.
@@ -80,7 +80,8 @@ pub trait Index {
 /// * orders indices found in `GIT_DIR/objects/pack` by modification time, and
 ///   queries the more recent ones first
 /// * attempts to rescan `GIT_DIR/objects/pack` when an object id was _not_
-///   found (assuming that this is due to a compaction)
+///   found, or the pack containing it could not be loaded (assuming that this
+///   is due to a compaction)
 ///
 /// Unless a reload occurs, lookups are lock-free and mostly wait-free. Writes
 /// ([`Shared::push`], [`Shared::reload`]) are guarded by a [`Mutex`].
@@ -198,18 +199,27 @@ where
         cache: &mut impl DecodeEntry,
     ) -> Result<Option<Object<'a>>, error::Lookup<E>>
     where
-        F: FnOnce(&pack::Info) -> Result<Arc<pack::Data>, E>,
+        F: Fn(&pack::Info) -> Result<Arc<pack::Data>, E>,
     {
         for i in 0..2 {
-            for idx in self.indices.load().iter() {
-                if let Some(ofs) = idx.ofs(&id) {
+            let found = self
+                .indices
+                .load()
+                .iter()
+                .find_map(|idx| idx.ofs(&id).map(|ofs| (Arc::clone(idx), ofs)));
+            match found {
+                Some((idx, ofs)) => {
                     self.stats.record_hit();
-                    return load_obj(ofs, idx, pack_cache, buf, cache).map(Some);
-                }
-            }
-
-            if i == 0 {
-                self.reload()?;
+                    match pack_cache(&idx.info) {
+                        Ok(data) => return load_obj(ofs, &idx, &data, buf, cache).map(Some),
+                        // The pack may have been removed by a repack since we
+                        // last scanned the packs directory
+                        Err(_) if i == 0 => self.reload()?,
+                        Err(e) => return Err(error::Lookup::Lookup(e)),
+                    }
+                },
+                None if i == 0 => self.reload()?,
+                None => break,
             }
         }
 
@@ -218,17 +228,13 @@ where
     }
 }
 
-fn load_obj<'a, F, E>(
+fn load_obj<'a, E>(
     ofs: u64,
     idx: &pack::Index,
-    pack_cache: F,
+    data: &pack::Data,
     buf: &'a mut Vec<u8>,
     cache: &mut impl DecodeEntry,
-) -> Result<Object<'a>, error::Lookup<E>>
-where
-    F: FnOnce(&pack::Info) -> Result<Arc<pack::Data>, E>,
-{
-    let data = pack_cache(&idx.info).map_err(error::Lookup::Lookup)?;
+) -> Result<Object<'a>, error::Lookup<E>> {
     let pack = data.file();
     let entry = pack.entry(ofs);
     let obj = pack
@@ -300,7 +306,7 @@ where
         cache: &mut impl DecodeEntry,
     ) -> Result<Option<Object<'a>>, error::Lookup<E>>
     where
-        F: FnOnce(&pack::Info) -> Result<Arc<pack::Data>, E>,
+        F: Fn(&pack::Info) -> Result<Arc<pack::Data>, E>,
     {
         self.lookup(pack_cache, id, buf, cache)
     }
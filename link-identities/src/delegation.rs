@@ -30,6 +30,17 @@ pub trait Delegations: sealed::Sealed {
     /// Nb.: "threshold" means that there must be `quorum_threshold() + 1` votes
     /// to form a quorum.
     fn quorum_threshold(&self) -> usize;
+
+    /// The maximum number of [`Delegations::eligible`] votes, ie. the number
+    /// of distinct delegations.
+    fn voters(&self) -> usize;
+
+    /// Whether a quorum can be formed at all, ie. the
+    /// [`Delegations::quorum_threshold`] is less than the number of
+    /// [`Delegations::voters`].
+    fn valid_threshold(&self) -> bool {
+        self.quorum_threshold() < self.voters()
+    }
 }
 
 //// Forwarding impls for `Doc` and `Identity`
@@ -44,8 +55,24 @@ where
         self.delegations.eligible(votes)
     }
 
+    /// If the [`generic::Doc`] specifies a `threshold`, it overrides the
+    /// default threshold of the delegations.
     fn quorum_threshold(&self) -> usize {
-        self.delegations.quorum_threshold()
+        match self.threshold {
+            Some(threshold) => threshold.saturating_sub(1),
+            None => self.delegations.quorum_threshold(),
+        }
+    }
+
+    fn voters(&self) -> usize {
+        self.delegations.voters()
+    }
+
+    fn valid_threshold(&self) -> bool {
+        match self.threshold {
+            Some(threshold) => threshold > 0 && threshold <= self.voters(),
+            None => self.delegations.valid_threshold(),
+        }
     }
 }
 
@@ -62,6 +89,14 @@ where
     fn quorum_threshold(&self) -> usize {
         self.doc.quorum_threshold()
     }
+
+    fn voters(&self) -> usize {
+        self.doc.voters()
+    }
+
+    fn valid_threshold(&self) -> bool {
+        self.doc.valid_threshold()
+    }
 }
 
 /// "Existentialised" delegations.
@@ -87,6 +122,13 @@ impl<T, R: Ord, C: Ord> Delegations for SomeDelegations<T, R, C> {
             SomeDelegations::Indirect(indirect) => indirect.quorum_threshold(),
         }
     }
+
+    fn voters(&self) -> usize {
+        match self {
+            SomeDelegations::Direct(direct) => direct.voters(),
+            SomeDelegations::Indirect(indirect) => indirect.voters(),
+        }
+    }
 }
 
 impl<T, R: Ord, C: Ord> sealed::Sealed for SomeDelegations<T, R, C> {}
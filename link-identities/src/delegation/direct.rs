@@ -67,7 +67,11 @@ impl Delegations for Direct {
     }
 
     fn quorum_threshold(&self) -> usize {
-        self.0.len() / 2
+        self.voters() / 2
+    }
+
+    fn voters(&self) -> usize {
+        self.0.len()
     }
 }
 
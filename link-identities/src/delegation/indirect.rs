@@ -294,6 +294,10 @@ impl<T, R, C> Delegations for Indirect<T, R, C> {
     }
 
     fn quorum_threshold(&self) -> usize {
+        self.voters() / 2
+    }
+
+    fn voters(&self) -> usize {
         let direct = self
             .delegations
             .iter()
@@ -301,7 +305,7 @@ impl<T, R, C> Delegations for Indirect<T, R, C> {
             .count();
         let indirect = self.identities.len();
 
-        (direct + indirect) / 2
+        direct + indirect
     }
 }
 
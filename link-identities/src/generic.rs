@@ -6,11 +6,13 @@
 #![allow(clippy::type_complexity)]
 
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display},
     marker::PhantomData,
     ops::Deref,
 };
 
+use crypto::PublicKey;
 use serde::ser::SerializeStruct;
 
 use super::{delegation::Delegations, payload::Payload, sealed, sign::Signatures, urn::Urn};
@@ -28,6 +30,31 @@ pub struct Doc<T, D, Revision> {
     pub replaces: Option<Revision>,
     pub payload: T,
     pub delegations: D,
+    /// The number of votes required to form a quorum, overriding the default
+    /// majority of the `delegations`.
+    ///
+    /// Must be at least `1`, and at most the number of delegations. Omitted
+    /// from the serialised form if `None`, so documents which don't override
+    /// the threshold retain their revision.
+    #[serde(default)]
+    pub threshold: Option<usize>,
+    /// Keys which were removed from the `delegations` because they are no
+    /// longer trusted.
+    ///
+    /// A revoked key can not be delegated to again. Omitted from the serialised
+    /// form if empty.
+    #[serde(default)]
+    pub revoked: BTreeMap<PublicKey, Revocation>,
+}
+
+/// The record of a key revocation, see [`Doc::revoked`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Revocation {
+    /// Why the key was revoked, eg. "lost device".
+    pub reason: String,
+    /// The time, in seconds since the Unix epoch, from which on anything
+    /// signed by the key is no longer to be trusted.
+    pub effective: u64,
 }
 
 impl<T, D, Revision> serde::Serialize for Doc<T, D, Revision>
@@ -40,11 +67,21 @@ where
     where
         S: serde::Serializer,
     {
-        let mut doc = serializer.serialize_struct("Doc", 4)?;
+        let len = 4 + self.threshold.is_some() as usize + !self.revoked.is_empty() as usize;
+        let mut doc = serializer.serialize_struct("Doc", len)?;
         doc.serialize_field("version", &0)?;
         doc.serialize_field("replaces", &self.replaces)?;
         doc.serialize_field("payload", &self.payload)?;
         doc.serialize_field("delegations", &self.delegations)?;
+        match self.threshold {
+            Some(threshold) => doc.serialize_field("threshold", &threshold)?,
+            None => doc.skip_field("threshold")?,
+        }
+        if self.revoked.is_empty() {
+            doc.skip_field("revoked")?
+        } else {
+            doc.serialize_field("revoked", &self.revoked)?
+        }
         doc.end()
     }
 }
@@ -63,6 +100,8 @@ impl<T, D, R> Doc<T, D, R> {
             replaces: self.replaces,
             payload: f(self.payload),
             delegations: g(self.delegations),
+            threshold: self.threshold,
+            revoked: self.revoked,
         }
     }
 
@@ -95,6 +134,8 @@ impl<T, D, R> Doc<T, D, R> {
             replaces: doc.replaces,
             payload: doc.payload?,
             delegations: doc.delegations,
+            threshold: doc.threshold,
+            revoked: doc.revoked,
         })
     }
 
@@ -111,6 +152,8 @@ impl<T, D, R> Doc<T, D, R> {
             replaces: doc.replaces,
             payload: doc.payload,
             delegations: doc.delegations?,
+            threshold: doc.threshold,
+            revoked: doc.revoked,
         })
     }
 }
@@ -231,6 +274,18 @@ impl<T, D, R> Replaces for Doc<T, D, R> {
     }
 }
 
+/// Ad-hoc trait which allows us to keep the `T` parameter of [`Identity`]
+/// polymorphic for verification.
+pub trait Revocations: sealed::Sealed {
+    fn revoked(&self) -> &BTreeMap<PublicKey, Revocation>;
+}
+
+impl<T, D, R> Revocations for Doc<T, D, R> {
+    fn revoked(&self) -> &BTreeMap<PublicKey, Revocation> {
+        &self.revoked
+    }
+}
+
 /// Untrusted, well-formed input.
 #[derive(Clone, Copy, Debug)]
 pub struct Untrusted;
@@ -248,6 +303,22 @@ pub struct Quorum;
 #[derive(Clone, Copy, Debug)]
 pub struct Verified;
 
+/// How to verify the [`Quorum`] of the root (ie. initial) revision of an
+/// identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Genesis {
+    /// The root revision must be signed by a quorum of its delegations, like
+    /// any other revision.
+    Quorum,
+    /// The root revision is valid if signed by any single one of its
+    /// delegations, ie. its creator.
+    ///
+    /// This allows to initialise an identity with multiple delegations
+    /// without having to co-ordinate signatures first. Subsequent revisions
+    /// still need to be signed by a quorum of the root's delegations.
+    Single,
+}
+
 /// An identity `T` under verification.
 ///
 /// The verification status (ie. which predicates where successfully applied to
@@ -347,7 +418,7 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Untrusted> {
         parent: Option<&Verifying<Identity<T, R, C>, Verified>>,
     ) -> Result<Verifying<Identity<T, R, C>, Verified>, error::Verify<R, C>>
     where
-        T: Delegations + Replaces<Revision = R>,
+        T: Delegations + Replaces<Revision = R> + Revocations,
         T::Error: std::error::Error + Send + Sync + 'static,
 
         R: Clone + Debug + Display + PartialEq + AsRef<[u8]>,
@@ -362,8 +433,10 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Signed> {
     ///
     /// # Errors
     ///
-    /// If the number of signatures does not reach the
-    /// [`Delegations::quorum_threshold`].
+    /// * the [`Delegations::quorum_threshold`] is out of range, see
+    ///   [`Delegations::valid_threshold`]
+    /// * the number of signatures does not reach the
+    ///   [`Delegations::quorum_threshold`]
     pub fn quorum(self) -> Result<Verifying<Identity<T, R, C>, Quorum>, error::Verify<R, C>>
     where
         T: Delegations,
@@ -372,6 +445,10 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Signed> {
         R: Debug + Display,
         C: Debug + Display,
     {
+        if !self.doc.valid_threshold() {
+            return Err(error::Verify::Threshold);
+        }
+
         let eligible = self
             .doc
             .eligible(self.signatures.keys().collect())
@@ -384,6 +461,51 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Signed> {
             Err(error::Verify::Quorum)
         }
     }
+
+    /// Attempt to transition a [`Signed`] [`Identity`] to the [`Quorum`]
+    /// state, subject to the [`Genesis`] mode.
+    ///
+    /// If `self` is the root revision (ie. `replaces` is `None`) and `genesis`
+    /// is [`Genesis::Single`], a single eligible signature suffices.
+    /// Otherwise, this is the same as [`Self::quorum`].
+    ///
+    /// # Errors
+    ///
+    /// * the [`Delegations::quorum_threshold`] is out of range, see
+    ///   [`Delegations::valid_threshold`]
+    /// * the number of signatures does not reach the required threshold
+    pub fn quorum_with(
+        self,
+        genesis: Genesis,
+    ) -> Result<Verifying<Identity<T, R, C>, Quorum>, error::Verify<R, C>>
+    where
+        T: Delegations + Replaces<Revision = R>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+
+        R: Debug + Display,
+        C: Debug + Display,
+    {
+        match genesis {
+            Genesis::Single if self.doc.replaces().is_none() => {
+                if !self.doc.valid_threshold() {
+                    return Err(error::Verify::Threshold);
+                }
+
+                let eligible = self
+                    .doc
+                    .eligible(self.signatures.keys().collect())
+                    .map_err(error::Verify::eligibility)?
+                    .len();
+
+                if eligible > 0 {
+                    Ok(self.coerce())
+                } else {
+                    Err(error::Verify::Quorum)
+                }
+            },
+            _ => self.quorum(),
+        }
+    }
 }
 
 impl<T, R, C> Verifying<Identity<T, R, C>, Quorum> {
@@ -406,12 +528,14 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Quorum> {
     ///   `parent.eligible(self.signatures.keys()).len() >
     ///   parent.doc.quorum_threshold()`
     /// * `parent.eligible(self.signatures.keys())` returns an error
+    /// * a revocation of the `parent` is not carried over to `self`
+    /// * a revoked key is delegated to by `self`
     pub fn verified(
         self,
         parent: Option<&Verifying<Identity<T, R, C>, Verified>>,
     ) -> Result<Verifying<Identity<T, R, C>, Verified>, error::Verify<R, C>>
     where
-        T: Delegations + Replaces<Revision = R>,
+        T: Delegations + Replaces<Revision = R> + Revocations,
         T::Error: std::error::Error + Send + Sync + 'static,
 
         R: Clone + Debug + Display + PartialEq + AsRef<[u8]>,
@@ -429,7 +553,10 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Quorum> {
             )),
             (Some(replaces), None) => Err(error::Verify::MissingParent(replaces.to_owned())),
 
-            (None, None) => Ok(self.coerce()),
+            (None, None) => {
+                self.check_revocations(None)?;
+                Ok(self.coerce())
+            },
 
             (Some(replaces), Some(parent)) => {
                 if replaces != &parent.revision {
@@ -438,6 +565,7 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Quorum> {
                         actual: parent.revision.to_owned(),
                     })
                 } else {
+                    self.check_revocations(Some(&parent.doc))?;
                     let votes = parent
                         .doc
                         .eligible(self.signatures.keys().collect())
@@ -453,6 +581,37 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Quorum> {
             },
         }
     }
+
+    /// Ensure that all revocations of the `parent` document are carried over
+    /// unchanged, and that none of the revoked keys is delegated to again.
+    fn check_revocations(&self, parent: Option<&T>) -> Result<(), error::Verify<R, C>>
+    where
+        T: Delegations + Revocations,
+        T::Error: std::error::Error + Send + Sync + 'static,
+
+        R: Debug + Display,
+        C: Debug + Display,
+    {
+        let revoked = self.doc.revoked();
+        if let Some(parent) = parent {
+            for (key, revocation) in parent.revoked() {
+                if revoked.get(key) != Some(revocation) {
+                    return Err(error::Verify::RevocationDropped(*key));
+                }
+            }
+        }
+        for key in revoked.keys() {
+            let delegated = self
+                .doc
+                .eligible(Some(key).into_iter().collect())
+                .map_err(error::Verify::eligibility)?;
+            if !delegated.is_empty() {
+                return Err(error::Verify::Revoked(*key));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// The result of running [`Verifying::verify`].
@@ -484,7 +643,7 @@ impl<T, R, C> Verifying<Identity<T, R, C>, Verified> {
         mut progeny: impl Iterator<Item = Result<Verifying<Identity<T, R, C>, Untrusted>, E>>,
     ) -> Result<Folded<T, R, C>, error::Verify<R, C>>
     where
-        T: Delegations + Replaces<Revision = R>,
+        T: Delegations + Replaces<Revision = R> + Revocations,
         T::Error: std::error::Error + Send + Sync + 'static,
 
         R: Clone + Debug + Display + PartialEq + AsRef<[u8]>,
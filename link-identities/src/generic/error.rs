@@ -5,6 +5,7 @@
 
 use std::fmt::{Debug, Display};
 
+use crypto::PublicKey;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,6 +27,15 @@ where
     #[error("quorum on parent not reached")]
     ParentQuorum,
 
+    #[error("quorum threshold out of range")]
+    Threshold,
+
+    #[error("the revocation of {0} was not carried over from the parent")]
+    RevocationDropped(PublicKey),
+
+    #[error("the key {0} was revoked and can not be delegated to")]
+    Revoked(PublicKey),
+
     #[error("expected parent {expected}, found {actual}")]
     ParentMismatch {
         expected: Revision,
@@ -47,6 +57,9 @@ where
     #[error("empty history")]
     EmptyHistory,
 
+    #[error("history is not linear: {0} has more than one parent")]
+    NonLinear(ContentId),
+
     #[error("non-eligible delegation")]
     Eligibility(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
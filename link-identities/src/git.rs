@@ -3,7 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{convert::TryFrom, fmt::Debug, marker::PhantomData};
+use std::{collections::BTreeMap, convert::TryFrom, fmt::Debug, marker::PhantomData};
 
 use canonical::Cjson;
 use crypto::{PublicKey, Signer};
@@ -23,7 +23,7 @@ use crate::{
 pub mod error;
 pub mod iter;
 
-pub use generic::Verifying;
+pub use generic::{Genesis, Revocation, Verifying};
 
 mod load;
 pub mod sign;
@@ -90,6 +90,13 @@ impl SomeIdentity {
             Self::Person(person) => person.content_id,
         }
     }
+
+    pub fn revision(&self) -> Revision {
+        match self {
+            Self::Project(project) => project.revision,
+            Self::Person(person) => person.revision,
+        }
+    }
 }
 
 pub type SignedPerson = SignedIdentity<PersonDoc>;
@@ -102,16 +109,35 @@ pub type VerificationError = generic::error::Verify<Revision, ContentId>;
 
 pub type IndirectDelegation = delegation::Indirect<PersonPayload, Revision, ContentId>;
 
+/// The shape of identity histories accepted by [`Identities`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Histories may contain merge commits, ie. concurrent updates are
+    /// reconciled by following the first parent.
+    Any,
+    /// Histories must be linear. Verification rejects merge commits, and
+    /// concurrent updates are reported as forks instead of being merged.
+    Linear,
+}
+
 #[derive(Clone)]
 pub struct Identities<'a, T> {
     repo: &'a git2::Repository,
+    genesis: Genesis,
+    history: HistoryMode,
     _marker: PhantomData<T>,
 }
 
+/// Verification of the root revision of an identity defaults to
+/// [`Genesis::Quorum`], and histories default to [`HistoryMode::Any`]. Use
+/// [`Identities::with_genesis`] to accept root revisions signed only by their
+/// creator.
 impl<'a, T: 'a> From<&'a git2::Repository> for Identities<'a, T> {
     fn from(repo: &'a git2::Repository) -> Self {
         Self {
             repo,
+            genesis: Genesis::Quorum,
+            history: HistoryMode::Any,
             _marker: PhantomData,
         }
     }
@@ -120,10 +146,23 @@ impl<'a, T: 'a> From<&'a git2::Repository> for Identities<'a, T> {
 impl<'a, T: 'a> From<&Identities<'a, T>> for Identities<'a, T> {
     fn from(other: &Identities<'a, T>) -> Self {
         Identities::from(other.repo)
+            .with_genesis(other.genesis)
+            .with_history(other.history)
     }
 }
 
 impl<'a, T: 'a> Identities<'a, T> {
+    /// Set the [`Genesis`] mode used to verify the root revision of
+    /// identities.
+    pub fn with_genesis(self, genesis: Genesis) -> Self {
+        Self { genesis, ..self }
+    }
+
+    /// Set the [`HistoryMode`] of identities.
+    pub fn with_history(self, history: HistoryMode) -> Self {
+        Self { history, ..self }
+    }
+
     /// Convenience to specialise `T` to [`Person`].
     pub fn as_person(&self) -> Identities<'_, Person> {
         self.coerce()
@@ -147,6 +186,8 @@ impl<'a, T: 'a> Identities<'a, T> {
     pub fn coerce<U>(&self) -> Identities<'_, U> {
         Identities {
             repo: self.repo,
+            genesis: self.genesis,
+            history: self.history,
             _marker: PhantomData,
         }
     }
@@ -190,7 +231,7 @@ impl<'a, T: 'a> Identities<'a, T> {
         head: git2::Oid,
     ) -> Result<VerifiedIdentity<Doc>, VerificationError>
     where
-        Doc: Delegations + generic::Replaces<Revision = Revision>,
+        Doc: Delegations + generic::Replaces<Revision = Revision> + generic::Revocations,
         <Doc as Delegations>::Error: std::error::Error + Send + Sync + 'static,
 
         Identity<Doc>: TryFrom<ByOid<'a>, Error = error::Load>,
@@ -203,11 +244,15 @@ impl<'a, T: 'a> Identities<'a, T> {
         head: git2::Oid,
     ) -> Result<generic::Folded<Doc, Revision, ContentId>, VerificationError>
     where
-        Doc: Delegations + generic::Replaces<Revision = Revision>,
+        Doc: Delegations + generic::Replaces<Revision = Revision> + generic::Revocations,
         <Doc as Delegations>::Error: std::error::Error + Send + Sync + 'static,
 
         Identity<Doc>: TryFrom<ByOid<'a>, Error = error::Load>,
     {
+        if self.history == HistoryMode::Linear {
+            self.ensure_linear(head)?;
+        }
+
         let mut progeny = Iter::<'_, Identity<Doc>>::new(self.repo, head)
             .map_err(generic::error::Verify::history)?;
 
@@ -217,7 +262,7 @@ impl<'a, T: 'a> Identities<'a, T> {
             .ok_or(generic::error::Verify::EmptyHistory)?
             .map_err(generic::error::Verify::history)?
             .signed()?
-            .quorum()?
+            .quorum_with(self.genesis)?
             .verified(None)?;
 
         root.verify(progeny)
@@ -229,6 +274,27 @@ impl<'a, T: 'a> Identities<'a, T> {
         (self.repo, oid)
     }
 
+    fn ensure_linear(&self, head: git2::Oid) -> Result<(), VerificationError> {
+        let mut revwalk = self
+            .repo
+            .revwalk()
+            .map_err(generic::error::Verify::history)?;
+        revwalk
+            .push(head)
+            .map_err(generic::error::Verify::history)?;
+
+        for oid in revwalk {
+            let commit = oid
+                .and_then(|oid| self.repo.find_commit(oid))
+                .map_err(generic::error::Verify::history)?;
+            if commit.parent_count() > 1 {
+                return Err(generic::error::Verify::NonLinear(commit.id().into()));
+            }
+        }
+
+        Ok(())
+    }
+
     fn is_in_ancestry_path(&self, commit: git2::Oid, tree: git2::Oid) -> Result<bool, git2::Error> {
         {
             let commit = self.repo.find_commit(commit)?;
@@ -292,9 +358,9 @@ where
 
     /// Apply `theirs` to `ours`, and sign the result.
     ///
-    /// This is like a merge of `theirs` into `ours` -- the resulting commit
-    /// will have both `content_id`s as parents. The merge is subject to the
-    /// following rules:
+    /// This is like a merge of `theirs` into `ours` -- unless `theirs` already
+    /// builds on `ours`, the resulting commit will have both `content_id`s as
+    /// parents. The merge is subject to the following rules:
     ///
     /// 1. `ours` must already be signed by `signer` (otherwise it wouldn't be
     ///    "ours", isn't it?)
@@ -309,10 +375,15 @@ where
     ///    signed by the union of both sets of signatures.
     /// 6. If `theirs` replaces `ours` (ie. `ours.revision ==
     ///    theirs.doc.replaces`), their revision is signed, and becomes the
-    ///    revision of the result. Note that the result has only one
-    ///    signature (by us).
+    ///    revision of the result. Note that the result has only one signature
+    ///    (by us).
     /// 7. Otherwise, there is no apparent relation between `ours` and `theirs`,
     ///    so an error is returned.
+    ///
+    /// If the [`HistoryMode`] is [`HistoryMode::Linear`], no merge commits are
+    /// created: in cases 5. and 6., `theirs` must be a descendant of `ours`,
+    /// and becomes the only parent of the result. Otherwise, the histories
+    /// have forked, and [`error::Merge::Fork`] is returned.
     pub fn update_from<S>(
         &self,
         ours: SignedIdentity<T>,
@@ -359,6 +430,19 @@ where
             }
         }?;
 
+        // A linear history can only be extended if `theirs` builds on `ours`.
+        // If it does, the result stays linear in any mode.
+        let linear = self.history == HistoryMode::Linear;
+        let builds_on_ours = self
+            .repo
+            .graph_descendant_of(*theirs.content_id, *ours.content_id)?;
+        if linear && matches!(action, Action::SlowFwd | Action::SuccRev) && !builds_on_ours {
+            return Err(error::Merge::Fork {
+                ours: error::Tip::of(&ours),
+                theirs: error::Tip::of(&theirs),
+            });
+        }
+
         match action {
             Action::Uptodate => Ok(ours),
             Action::FastFwd => Ok(theirs),
@@ -370,7 +454,7 @@ where
                     &format!("Updated signatures from {}", theirs.content_id),
                     &signatures,
                     ours.revision,
-                    &[&ours],
+                    if linear { &[&theirs] } else { &[&ours] },
                 )?;
 
                 Ok(Identity {
@@ -394,7 +478,11 @@ where
                     ),
                     &signatures,
                     theirs.revision,
-                    &[&ours, &theirs],
+                    if builds_on_ours {
+                        &[&theirs]
+                    } else {
+                        &[&ours, &theirs]
+                    },
                 )?;
 
                 Ok(Identity {
@@ -406,6 +494,69 @@ where
         }
     }
 
+    /// Merge the signatures of several copies of a proposed revision, and add
+    /// our own.
+    ///
+    /// All `proposals` must be of the same root and revision. The proposal
+    /// carrying the most signatures is used as the parent of the result, so
+    /// the history of the proposal stays linear. If neither the other
+    /// proposals nor our signature contribute anything new, that proposal is
+    /// returned unchanged.
+    pub fn merge_signatures<S>(
+        &self,
+        proposals: Vec<SignedIdentity<T>>,
+        signer: &S,
+    ) -> Result<Identity<T>, error::Merge>
+    where
+        S: Signer,
+    {
+        let mut proposals = proposals
+            .into_iter()
+            .map(|proposal| proposal.into_inner())
+            .collect::<Vec<_>>();
+        proposals.sort_by(|a, b| {
+            b.signatures
+                .len()
+                .cmp(&a.signatures.len())
+                .then(a.content_id.cmp(&b.content_id))
+        });
+
+        let mut proposals = proposals.into_iter();
+        let base = proposals.next().ok_or(error::Merge::NoProposals)?;
+
+        let mut signatures = base.signatures.clone();
+        for other in proposals {
+            if other.root != base.root {
+                return Err(error::Merge::RootMismatch);
+            }
+            if other.revision != base.revision {
+                return Err(error::Merge::RevisionMismatch);
+            }
+            signatures.extend(other.signatures);
+        }
+        {
+            let sig = sign(signer, base.revision).map_err(|e| error::Merge::Signer(Box::new(e)))?;
+            signatures.extend(Some(sig))
+        }
+
+        if signatures.len() == base.signatures.len() {
+            return Ok(base);
+        }
+
+        let content_id = self.commit(
+            &format!("Signed proposed revision `{}`", base.revision),
+            &signatures,
+            base.revision,
+            &[&base],
+        )?;
+
+        Ok(Identity {
+            content_id,
+            signatures,
+            ..base
+        })
+    }
+
     //// Helpers ////
 
     fn commit(
@@ -438,6 +589,10 @@ where
 impl<'a, T: 'a> Identities<'a, VerifiedIdentity<T>> {
     /// Return the newer of identities `left` and `right`, or an error if their
     /// histories are unrelated.
+    ///
+    /// If the [`HistoryMode`] is [`HistoryMode::Linear`], one of the two
+    /// identities must be an ancestor of the other, otherwise their histories
+    /// have forked.
     pub fn newer(
         &self,
         left: VerifiedIdentity<T>,
@@ -446,6 +601,16 @@ impl<'a, T: 'a> Identities<'a, VerifiedIdentity<T>> {
     where
         T: Debug,
     {
+        if self.history == HistoryMode::Linear {
+            return if self.is_ancestor_of(&right, &left)? {
+                Ok(left)
+            } else if self.is_ancestor_of(&left, &right)? {
+                Ok(right)
+            } else {
+                Err(error::History::Fork { left, right })
+            };
+        }
+
         if self.is_in_ancestry_path(left.content_id.into(), right.revision.into())? {
             Ok(left)
         } else if self.is_in_ancestry_path(right.content_id.into(), left.revision.into())? {
@@ -454,6 +619,17 @@ impl<'a, T: 'a> Identities<'a, VerifiedIdentity<T>> {
             Err(error::History::Fork { left, right })
         }
     }
+
+    fn is_ancestor_of(
+        &self,
+        ancestor: &VerifiedIdentity<T>,
+        descendant: &VerifiedIdentity<T>,
+    ) -> Result<bool, git2::Error> {
+        Ok(ancestor.content_id == descendant.content_id
+            || self
+                .repo
+                .graph_descendant_of(*descendant.content_id, *ancestor.content_id)?)
+    }
 }
 
 impl<'a> Identities<'a, Person> {
@@ -519,6 +695,8 @@ impl<'a> Identities<'a, Person> {
             replaces: None,
             payload,
             delegations: payload::PersonDelegations::from(delegations),
+            threshold: None,
+            revoked: BTreeMap::new(),
         };
         let root: Revision = self.repo.blob(&Cjson(&doc).canonical_form()?)?.into();
         Ok((doc, root))
@@ -549,15 +727,108 @@ impl<'a> Identities<'a, Person> {
             return Ok(base.into_inner());
         }
 
+        let delegations = delegations.unwrap_or_else(|| base.delegations().clone());
+        check_threshold(base.doc.threshold, delegations.voters())?;
+        check_revoked(&base.doc.revoked, &delegations)?;
         let doc = Doc {
             version: 0,
             replaces: Some(base.revision),
             payload: payload.unwrap_or_else(|| base.payload().clone()),
-            delegations: payload::PersonDelegations::from(
-                delegations.unwrap_or_else(|| base.delegations().clone()),
-            ),
+            delegations: payload::PersonDelegations::from(delegations),
+            threshold: base.doc.threshold,
+            revoked: base.doc.revoked.clone(),
         };
 
+        self.store_update(base, doc, signer)
+    }
+
+    /// Update the quorum `threshold` of an existing [`SignedPerson`].
+    ///
+    /// A `threshold` of `None` reverts to requiring a majority of the
+    /// delegations. If the threshold is unchanged, no new commit is made, and
+    /// the result is the unwrapped [`Person`] of the `base` argument.
+    ///
+    /// Otherwise, the result is a new [`Person`] whose parent is `base`.
+    pub fn update_threshold<S>(
+        &self,
+        base: SignedPerson,
+        threshold: Option<usize>,
+        signer: &S,
+    ) -> Result<Person, error::Store>
+    where
+        S: Signer,
+    {
+        if threshold == base.doc.threshold {
+            return Ok(base.into_inner());
+        }
+
+        check_threshold(threshold, base.delegations().voters())?;
+        let doc = Doc {
+            version: 0,
+            replaces: Some(base.revision),
+            payload: base.payload().clone(),
+            delegations: payload::PersonDelegations::from(base.delegations().clone()),
+            threshold,
+            revoked: base.doc.revoked.clone(),
+        };
+
+        self.store_update(base, doc, signer)
+    }
+
+    /// Revoke the delegated `key` of an existing [`SignedPerson`], optionally
+    /// replacing it with `replacement`.
+    ///
+    /// The `revocation` is recorded in the new revision, and the key can not be
+    /// delegated to again. The result is a new [`Person`] whose parent is
+    /// `base`.
+    pub fn revoke<S>(
+        &self,
+        base: SignedPerson,
+        key: PublicKey,
+        replacement: Option<PublicKey>,
+        revocation: Revocation,
+        signer: &S,
+    ) -> Result<Person, error::Store>
+    where
+        S: Signer,
+    {
+        if !base.delegations().contains(&key) {
+            return Err(error::Store::NotDelegated(key));
+        }
+        let delegations = delegation::Direct::try_from_iter(
+            base.delegations()
+                .iter()
+                .filter(|other| **other != key)
+                .copied()
+                .chain(replacement),
+        )
+        .map_err(|_| error::Store::LastKey(key))?;
+        let mut revoked = base.doc.revoked.clone();
+        revoked.insert(key, revocation);
+
+        check_threshold(base.doc.threshold, delegations.voters())?;
+        check_revoked(&revoked, &delegations)?;
+        let doc = Doc {
+            version: 0,
+            replaces: Some(base.revision),
+            payload: base.payload().clone(),
+            delegations: payload::PersonDelegations::from(delegations),
+            threshold: base.doc.threshold,
+            revoked,
+        };
+
+        self.store_update(base, doc, signer)
+    }
+
+    fn store_update<S>(
+        &self,
+        base: SignedPerson,
+        doc: Doc<PersonPayload, payload::PersonDelegations>,
+        signer: &S,
+    ) -> Result<Person, error::Store>
+    where
+        S: Signer,
+    {
         let revision = {
             let doc_blob = self.repo.blob(&Cjson(&doc).canonical_form()?)?;
             let base_tree = self.repo.find_tree(*base.revision)?;
@@ -628,7 +899,7 @@ impl<'a> Identities<'a, Project> {
 
         Ok(generic::Verifying::from(head)
             .signed()?
-            .quorum()?
+            .quorum_with(self.genesis)?
             .verified(parent.as_ref())?)
     }
 
@@ -688,6 +959,8 @@ impl<'a> Identities<'a, Project> {
             replaces: None,
             payload,
             delegations: payload::ProjectDelegations::from(delegations),
+            threshold: None,
+            revoked: BTreeMap::new(),
         };
         let root: Revision = self.repo.blob(&Cjson(&doc).canonical_form()?)?.into();
         Ok((doc, root))
@@ -720,6 +993,13 @@ impl<'a> Identities<'a, Project> {
 
         // FIXME: reorder stuff to avoid cloning
 
+        check_threshold(
+            base.doc.threshold,
+            delegations
+                .as_ref()
+                .unwrap_or_else(|| base.delegations())
+                .voters(),
+        )?;
         let doc = Doc {
             version: 0,
             replaces: Some(base.revision),
@@ -728,8 +1008,56 @@ impl<'a> Identities<'a, Project> {
                 .clone()
                 .map(payload::ProjectDelegations::from)
                 .unwrap_or_else(|| base.delegations().clone().into()),
+            threshold: base.doc.threshold,
+            revoked: base.doc.revoked.clone(),
         };
 
+        self.store_update(base, doc, delegations, signer)
+    }
+
+    /// Update the quorum `threshold` of an existing [`SignedProject`].
+    ///
+    /// A `threshold` of `None` reverts to requiring a majority of the
+    /// delegations. If the threshold is unchanged, no new commit is made, and
+    /// the result is the unwrapped [`Project`] of the `base` argument.
+    ///
+    /// Otherwise, the result is a new [`Project`] whose parent is `base`.
+    pub fn update_threshold<S>(
+        &self,
+        base: SignedProject,
+        threshold: Option<usize>,
+        signer: &S,
+    ) -> Result<Project, error::Store>
+    where
+        S: Signer,
+    {
+        if threshold == base.doc.threshold {
+            return Ok(base.into_inner());
+        }
+
+        check_threshold(threshold, base.delegations().voters())?;
+        let doc = Doc {
+            version: 0,
+            replaces: Some(base.revision),
+            payload: base.payload().clone(),
+            delegations: base.delegations().clone().into(),
+            threshold,
+            revoked: base.doc.revoked.clone(),
+        };
+
+        self.store_update(base, doc, None, signer)
+    }
+
+    fn store_update<S>(
+        &self,
+        base: SignedProject,
+        doc: Doc<ProjectPayload, payload::ProjectDelegations<Revision>>,
+        delegations: Option<IndirectDelegation>,
+        signer: &S,
+    ) -> Result<Project, error::Store>
+    where
+        S: Signer,
+    {
         let root = base.root;
         let revision = {
             let base_tree = self.repo.find_tree(*base.revision)?;
@@ -853,3 +1181,22 @@ where
     let sig = block_on(signer.sign(rev.as_bytes()))?;
     Ok(Signature::from((signer.public_key().into(), sig.into())))
 }
+
+fn check_revoked(
+    revoked: &BTreeMap<PublicKey, Revocation>,
+    delegations: &delegation::Direct,
+) -> Result<(), error::Store> {
+    match delegations.iter().find(|key| revoked.contains_key(key)) {
+        Some(key) => Err(error::Store::Revoked(*key)),
+        None => Ok(()),
+    }
+}
+
+fn check_threshold(threshold: Option<usize>, voters: usize) -> Result<(), error::Store> {
+    match threshold {
+        Some(threshold) if threshold == 0 || threshold > voters => {
+            Err(error::Store::Threshold { threshold, voters })
+        },
+        _ => Ok(()),
+    }
+}
@@ -3,12 +3,17 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{fmt::Debug, path::PathBuf};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Debug, Display},
+    path::PathBuf,
+};
 
 use canonical::CjsonError;
+use crypto::PublicKey;
 use thiserror::Error;
 
-use super::Urn;
+use super::{Identity, Urn};
 use crate::{
     delegation::indirect::error::FromIter as DelegationsFromIterError,
     generic,
@@ -68,6 +73,18 @@ pub enum Store {
 
     #[error(transparent)]
     Git(#[from] git2::Error),
+
+    #[error("quorum threshold {threshold} out of range for {voters} delegations")]
+    Threshold { threshold: usize, voters: usize },
+
+    #[error("the key {0} is not delegated to")]
+    NotDelegated(PublicKey),
+
+    #[error("the key {0} is the only delegation and can not be revoked without replacement")]
+    LastKey(PublicKey),
+
+    #[error("the key {0} was revoked and can not be delegated to")]
+    Revoked(PublicKey),
 }
 
 #[derive(Debug, Error)]
@@ -85,6 +102,12 @@ pub enum Merge {
     )]
     RevisionMismatch,
 
+    #[error("identity history forked: ours is {ours}, theirs is {theirs}")]
+    Fork { ours: Tip, theirs: Tip },
+
+    #[error("no proposals to merge")]
+    NoProposals,
+
     #[error("failed to produce a signature")]
     Signer(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
@@ -167,3 +190,38 @@ pub enum History<T: Debug> {
     #[error(transparent)]
     Git(#[from] git2::Error),
 }
+
+/// The tip of one side of a forked identity history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tip {
+    pub content_id: ContentId,
+    pub revision: Revision,
+    pub signers: BTreeSet<PublicKey>,
+}
+
+impl Tip {
+    pub fn of<T>(identity: &Identity<T>) -> Self {
+        Self {
+            content_id: identity.content_id,
+            revision: identity.revision,
+            signers: identity.signatures.keys().copied().collect(),
+        }
+    }
+}
+
+impl Display for Tip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at revision {}, signed by [",
+            self.content_id, self.revision
+        )?;
+        for (i, signer) in self.signers.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", signer)?;
+        }
+        f.write_str("]")
+    }
+}
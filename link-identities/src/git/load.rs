@@ -51,6 +51,8 @@ impl<'de> serde::Deserialize<'de> for SomeDoc {
                     replaces: doc.replaces,
                     payload,
                     delegations,
+                    threshold: doc.threshold,
+                    revoked: doc.revoked,
                 }))
             },
 
@@ -60,6 +62,8 @@ impl<'de> serde::Deserialize<'de> for SomeDoc {
                     replaces: doc.replaces,
                     payload,
                     delegations,
+                    threshold: doc.threshold,
+                    revoked: doc.revoked,
                 }))
             },
 
@@ -69,6 +73,8 @@ impl<'de> serde::Deserialize<'de> for SomeDoc {
                     replaces: doc.replaces,
                     payload,
                     delegations: (*delegations).iter().copied().map(Either::Left).collect(),
+                    threshold: doc.threshold,
+                    revoked: doc.revoked,
                 }))
             },
 
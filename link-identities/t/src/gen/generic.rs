@@ -119,6 +119,8 @@ where
             replaces: None,
             payload: Boring,
             delegations,
+            threshold: None,
+            revoked: Default::default(),
         },
         signatures,
     }
@@ -168,6 +170,8 @@ where
                     replaces,
                     payload: Boring,
                     delegations,
+                    threshold: None,
+                    revoked: Default::default(),
                 },
                 signatures,
             },
@@ -315,6 +319,8 @@ where
                 replaces: inner_replaces,
                 payload: Boring,
                 delegations,
+                threshold: None,
+                revoked: Default::default(),
             },
             signatures,
         };
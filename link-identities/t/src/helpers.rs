@@ -6,10 +6,10 @@
 use anyhow::anyhow;
 use either::Either::*;
 use librad::git::{identities, storage::Storage};
-use link_crypto::SecretKey;
+use link_crypto::{PublicKey, SecretKey};
 use link_identities::{
     delegation,
-    git::{error, Person, Urn},
+    git::{error, Genesis, Person, Revocation, Urn},
     payload,
     Identities,
     IndirectDelegation,
@@ -97,6 +97,34 @@ impl<'a> Device<'a> {
         Ok(Self { cur, ..self })
     }
 
+    pub fn update_threshold(self, threshold: Option<usize>) -> anyhow::Result<Self> {
+        let cur =
+            self.git
+                .update_threshold(Verifying::from(self.cur).signed()?, threshold, self.key)?;
+
+        Ok(Self { cur, ..self })
+    }
+
+    pub fn revoke(
+        self,
+        key: PublicKey,
+        replacement: Option<PublicKey>,
+        reason: &str,
+    ) -> anyhow::Result<Self> {
+        let cur = self.git.revoke(
+            Verifying::from(self.cur).signed()?,
+            key,
+            replacement,
+            Revocation {
+                reason: reason.into(),
+                effective: 1_650_000_000,
+            },
+            self.key,
+        )?;
+
+        Ok(Self { cur, ..self })
+    }
+
     pub fn update_from(self, other: &Device<'a>) -> anyhow::Result<Self> {
         let cur = self.git.update_from(
             Verifying::from(self.cur).signed()?,
@@ -148,6 +176,11 @@ pub struct Project<'a> {
 
 impl<'a> Project<'a> {
     pub fn new(dev: Device<'a>) -> anyhow::Result<Self> {
+        let delegations = IndirectDelegation::try_from_iter(Some(Right(dev.cur.clone())))?;
+        Self::new_with(dev, delegations)
+    }
+
+    pub fn new_with(dev: Device<'a>, delegations: IndirectDelegation) -> anyhow::Result<Self> {
         let cur = dev.git.as_project().create(
             payload::Project {
                 name: "haskell-emoji".into(),
@@ -155,7 +188,7 @@ impl<'a> Project<'a> {
                 default_branch: Some("\u{1F32F}".into()),
             }
             .into(),
-            IndirectDelegation::try_from_iter(Some(Right(dev.cur.clone())))?,
+            delegations,
             dev.key,
         )?;
 
@@ -171,6 +204,16 @@ impl<'a> Project<'a> {
         Ok(Self { dev, cur })
     }
 
+    pub fn merge_signatures(dev: Device<'a>, proposals: &[&Project<'a>]) -> anyhow::Result<Self> {
+        let proposals = proposals
+            .iter()
+            .map(|proposal| Verifying::from(proposal.cur.clone()).signed())
+            .collect::<Result<Vec<_>, _>>()?;
+        let cur = dev.git.as_project().merge_signatures(proposals, dev.key)?;
+
+        Ok(Self { dev, cur })
+    }
+
     pub fn change_description(self, descr: &str) -> anyhow::Result<Self> {
         let cur = self.dev.git.as_project().update(
             Verifying::from(self.cur.clone()).signed()?,
@@ -227,6 +270,21 @@ impl<'a> Project<'a> {
             .verify(*self.cur.content_id, lookup)
     }
 
+    pub fn verify_with<F>(
+        &self,
+        genesis: Genesis,
+        lookup: F,
+    ) -> Result<VerifiedProject, error::VerifyProject>
+    where
+        F: Fn(Urn) -> Result<git2::Oid, Void>,
+    {
+        self.dev
+            .git
+            .as_project()
+            .with_genesis(genesis)
+            .verify(*self.cur.content_id, lookup)
+    }
+
     pub fn assert_verifies<F>(&self, lookup: F) -> anyhow::Result<()>
     where
         F: Fn(Urn) -> Result<git2::Oid, Void>,
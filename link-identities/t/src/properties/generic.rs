@@ -7,10 +7,11 @@ use std::collections::BTreeMap;
 
 use librad::identities::{
     delegation::Delegations,
-    generic::{error, Doc, Identity},
+    generic::{error, Doc, Genesis, Identity, Revocation},
     sign::Signatures,
     Verifying,
 };
+use link_crypto::SecretKey;
 use nonempty::NonEmpty;
 use proptest::prelude::*;
 use radicle_std_ext::Void;
@@ -65,6 +66,66 @@ proptest! {
         )
     }
 
+    #[test]
+    fn quorum_with_threshold(
+        (id, threshold) in
+            gen_identity::<Boring>().prop_flat_map(|id| {
+                let voters = id.voters();
+                (Just(id), 1..=voters)
+            })
+    ) {
+        let id = id.map(|doc| Doc {
+            threshold: Some(threshold),
+            ..doc
+        });
+        let signatures: Signatures = BTreeMap::from(id.signatures.clone())
+            .into_iter()
+            .take(threshold)
+            .collect::<BTreeMap<_, _>>()
+            .into();
+
+        assert_matches!(
+            Verifying::from(Identity { signatures, ..id }).quorum(),
+            Ok(_)
+        )
+    }
+
+    #[test]
+    fn quorum_threshold_out_of_range(
+        (id, threshold) in
+            gen_identity::<Boring>().prop_flat_map(|id| {
+                let voters = id.voters();
+                (Just(id), prop_oneof![Just(0), (voters + 1)..=(voters * 2)])
+            })
+    ) {
+        let id = id.map(|doc| Doc {
+            threshold: Some(threshold),
+            ..doc
+        });
+
+        assert_matches!(
+            Verifying::from(id).quorum(),
+            Err(error::Verify::Threshold)
+        )
+    }
+
+    #[test]
+    fn quorum_single_genesis(id in gen_root_identity::<Revision>()) {
+        let signatures: Signatures = BTreeMap::from(id.signatures.clone())
+            .into_iter()
+            .take(1)
+            .collect::<BTreeMap<_, _>>()
+            .into();
+
+        assert_matches!(
+            Verifying::from(Identity { signatures, ..id })
+                .signed()
+                .unwrap()
+                .quorum_with(Genesis::Single),
+            Ok(_)
+        )
+    }
+
     #[test]
     fn verified_root(id in gen_root_identity::<Revision>()) {
         assert_eq!(
@@ -165,6 +226,58 @@ proptest! {
         }
     }
 
+    #[test]
+    fn verified_revocation_dropped(NonEmpty { head, tail } in gen_history(1)) {
+        match tail.as_slice() {
+            [next] => {
+                let revoked = SecretKey::new().public();
+                let parent = Verifying::from(head.map(|doc| Doc {
+                    revoked: Some((
+                        revoked,
+                        Revocation {
+                            reason: "lost device".into(),
+                            effective: 1_650_000_000,
+                        },
+                    ))
+                    .into_iter()
+                    .collect(),
+                    ..doc
+                }))
+                .verified(None)
+                .unwrap();
+
+                assert_matches!(
+                    Verifying::from(next.clone()).verified(Some(&parent)),
+                    Err(error::Verify::RevocationDropped(key)) if key == revoked
+                )
+            },
+
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn verified_revoked_delegation(id in gen_root_identity::<Revision>()) {
+        let delegated = *id.signatures.keys().next().unwrap();
+        let id = id.map(|doc| Doc {
+            revoked: Some((
+                delegated,
+                Revocation {
+                    reason: "stolen".into(),
+                    effective: 1_650_000_000,
+                },
+            ))
+            .into_iter()
+            .collect(),
+            ..doc
+        });
+
+        assert_matches!(
+            Verifying::from(id).verified(None),
+            Err(error::Verify::Revoked(key)) if key == delegated
+        )
+    }
+
     #[test]
     fn verify(history in gen_history(0..10)) {
         let NonEmpty { head, tail } = history;
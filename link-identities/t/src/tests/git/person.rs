@@ -109,3 +109,161 @@ fn revoke_a_trois() -> anyhow::Result<()> {
         desktop.assert_verifies()
     }
 }
+
+#[test]
+fn revoke_lost_device() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let desktop = Device::new(&*DESKTOP, Identities::from(&*repo))?.update(
+            Direct::new(DESKTOP.public())
+                .insert(LAPTOP.public())
+                .insert(PALMTOP.public()),
+        )?;
+        let laptop = Device::create_from(&*LAPTOP, &desktop)?;
+        let desktop = desktop.update_from(&laptop)?;
+        desktop.assert_verifies()?;
+
+        // The laptop got lost, so the remaining devices need to agree
+        let desktop = desktop.revoke(LAPTOP.public(), None, "lost laptop")?;
+        desktop.assert_no_quorum()?;
+        let palmtop = Device::create_from(&*PALMTOP, &desktop)?;
+        palmtop.assert_verifies()?;
+
+        let doc = &palmtop.current().doc;
+        assert_eq!(
+            doc.delegations,
+            Direct::new(DESKTOP.public()).insert(PALMTOP.public())
+        );
+        assert_eq!(
+            doc.revoked.get(&LAPTOP.public()).map(|r| r.reason.as_str()),
+            Some("lost laptop")
+        );
+
+        // The revocation sticks
+        let err = palmtop
+            .clone()
+            .update(
+                Direct::new(DESKTOP.public())
+                    .insert(LAPTOP.public())
+                    .insert(PALMTOP.public()),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<error::Store>(),
+            Some(error::Store::Revoked(key)) if *key == LAPTOP.public()
+        );
+        let palmtop = palmtop.update(Direct::new(PALMTOP.public()))?;
+        assert!(palmtop.current().doc.revoked.contains_key(&LAPTOP.public()));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn revoke_invalid() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let desktop = Device::new(&*DESKTOP, Identities::from(&*repo))?;
+
+        let err = desktop
+            .clone()
+            .revoke(LAPTOP.public(), None, "never had one")
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<error::Store>(),
+            Some(error::Store::NotDelegated(_))
+        );
+
+        let err = desktop
+            .clone()
+            .revoke(DESKTOP.public(), None, "stolen")
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<error::Store>(),
+            Some(error::Store::LastKey(_))
+        );
+
+        // Replacing the only key is fine
+        let desktop = desktop.revoke(DESKTOP.public(), Some(LAPTOP.public()), "stolen")?;
+        assert_eq!(
+            desktop.current().doc.delegations,
+            Direct::new(LAPTOP.public())
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn threshold() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let desktop = Device::new(&*DESKTOP, Identities::from(&*repo))?
+            .update_threshold(Some(1))?
+            .update(
+                Direct::new(DESKTOP.public())
+                    .insert(LAPTOP.public())
+                    .insert(PALMTOP.public()),
+            )?;
+        // A single vote is enough
+        desktop.assert_verifies()?;
+
+        // Laptop can revoke palmtop unilaterally
+        let laptop = Device::create_from(&*LAPTOP, &desktop)?
+            .update(Direct::new(DESKTOP.public()).insert(LAPTOP.public()))?;
+        laptop.assert_verifies()?;
+        let desktop = desktop.update_from(&laptop)?;
+        desktop.assert_verifies()?;
+
+        // Reverting to the default requires a majority, including for the
+        // revision reverting it
+        let laptop = laptop.update_threshold(None)?;
+        laptop.assert_no_quorum()?;
+        let desktop = desktop.update_from(&laptop)?;
+        desktop.assert_verifies()?;
+
+        let laptop = laptop
+            .update_from(&desktop)?
+            .update(Direct::new(LAPTOP.public()))?;
+        assert_matches!(
+            laptop.verify(),
+            Err(error::VerifyPerson::Verification(
+                VerificationError::ParentQuorum
+            ))
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn threshold_out_of_range() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let desktop = Device::new(&*DESKTOP, Identities::from(&*repo))?;
+        for threshold in [0, 2] {
+            let err = desktop
+                .clone()
+                .update_threshold(Some(threshold))
+                .unwrap_err();
+            assert_matches!(
+                err.downcast_ref::<error::Store>(),
+                Some(error::Store::Threshold { voters: 1, .. })
+            );
+        }
+
+        // Revoking a key must not render the threshold unreachable
+        let desktop = desktop
+            .update_threshold(Some(1))?
+            .update(Direct::new(DESKTOP.public()).insert(LAPTOP.public()))?;
+        let desktop = desktop.update_threshold(Some(2))?;
+        // Setting the same threshold again is a no-op
+        assert_eq!(
+            desktop.clone().update_threshold(Some(2))?.current().content_id,
+            desktop.current().content_id
+        );
+        assert!(desktop.update(Direct::new(DESKTOP.public())).is_err());
+
+        Ok(())
+    }
+}
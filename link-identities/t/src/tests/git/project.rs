@@ -12,7 +12,7 @@ use librad::{
     identities::{
         self,
         delegation::Direct,
-        git::{error, VerificationError},
+        git::{error, Genesis, HistoryMode, VerificationError},
         payload,
         Identities,
         IndirectDelegation,
@@ -57,6 +57,92 @@ fn create() -> anyhow::Result<()> {
     }
 }
 
+#[test]
+fn create_multisig() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let cheyenne = Device::new(&*CHEYENNE_DESKTOP, Identities::from(&*repo))?;
+        let dylan = Device::new(&*DYLAN, Identities::from(&*repo))?;
+
+        let heads = current_heads_from(vec![&cheyenne, &dylan]);
+
+        let delegations = IndirectDelegation::try_from_iter(vec![
+            Right(cheyenne.current().clone()),
+            Right(dylan.current().clone()),
+        ])?;
+        let project = Project::new_with(cheyenne, delegations)?;
+        project.assert_no_quorum()?;
+        // By default, the root revision requires a quorum
+        assert_matches!(
+            project.verify(lookup(&heads)),
+            Err(error::VerifyProject::Verification(
+                VerificationError::Quorum
+            ))
+        );
+        // Unless we opt in to the creator's signature being sufficient
+        project.verify_with(Genesis::Single, lookup(&heads))?;
+
+        // Subsequent revisions require a quorum
+        let project = project.change_description("multisig")?;
+        project.assert_no_quorum()?;
+        let project = Project::create_from(dylan, &project)?;
+        project.verify_with(Genesis::Single, lookup(&heads))?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn merge_signatures() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let cheyenne = Device::new(&*CHEYENNE_DESKTOP, Identities::from(&*repo))?;
+        let dylan = Device::new(&*DYLAN, Identities::from(&*repo))?;
+        let palmer = Device::new(&*CHEYENNE_PALMTOP, Identities::from(&*repo))?;
+
+        let heads = current_heads_from(vec![&cheyenne, &dylan, &palmer]);
+
+        let delegations = IndirectDelegation::try_from_iter(vec![
+            Right(cheyenne.current().clone()),
+            Right(dylan.current().clone()),
+            Right(palmer.current().clone()),
+        ])?;
+        let project = Project::new_with(cheyenne.clone(), delegations)?;
+        project.assert_verifies(lookup(&heads))?;
+
+        let proposal = project.clone().change_description("proposed")?;
+        proposal.assert_no_quorum()?;
+
+        // Either co-signature reaches quorum
+        let by_dylan = Project::merge_signatures(dylan.clone(), &[&proposal])?;
+        let by_palmer = Project::merge_signatures(palmer, &[&proposal])?;
+        assert_eq!(by_dylan.current().signatures.len(), 2);
+        assert_eq!(by_palmer.current().signatures.len(), 2);
+        by_dylan.assert_verifies(lookup(&heads))?;
+        by_palmer.assert_verifies(lookup(&heads))?;
+
+        // Merging both copies collects all signatures
+        let merged = Project::merge_signatures(cheyenne.clone(), &[&by_dylan, &by_palmer])?;
+        assert_eq!(merged.current().signatures.len(), 3);
+        assert_eq!(merged.current().revision, proposal.current().revision);
+        merged.assert_verifies(lookup(&heads))?;
+
+        // Nothing new to add
+        let again = Project::merge_signatures(cheyenne, &[&merged, &by_dylan])?;
+        assert_eq!(again.current().content_id, merged.current().content_id);
+
+        // Different revisions cannot be merged
+        assert_matches!(
+            Project::merge_signatures(dylan, &[&merged, &project])
+                .unwrap_err()
+                .downcast_ref::<error::Merge>(),
+            Some(error::Merge::RevisionMismatch)
+        );
+
+        Ok(())
+    }
+}
+
 #[test]
 fn update() -> anyhow::Result<()> {
     let repo = tmp::repo()?;
@@ -336,9 +422,105 @@ fn fork() -> anyhow::Result<()> {
         // But trying to figure out which is newer should reveal they are unrelated
         let git = dylan.git::<identities::VerifiedProject>();
         anyhow::ensure!(
-            git.newer(west, east).is_err(),
+            git.newer(west.clone(), east.clone()).is_err(),
             "expected diverging histories"
         );
+        assert_matches!(
+            git.with_history(HistoryMode::Linear).newer(west, east),
+            Err(error::History::Fork { .. })
+        );
+
+        // Dylan's forks were created by merging, so they're not linear
+        assert_matches!(
+            dylan
+                .git::<identities::Project>()
+                .with_history(HistoryMode::Linear)
+                .verify(*dylan_west.current().content_id, lookup(&heads)),
+            Err(error::VerifyProject::Verification(
+                VerificationError::NonLinear(_)
+            ))
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn linear_fork() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let git = Identities::from(&*repo).with_history(HistoryMode::Linear);
+        let cheyenne = Device::new(&*CHEYENNE_DESKTOP, git.clone())?;
+        let dylan = Device::new(&*DYLAN, git)?;
+
+        let heads = current_heads_from(vec![&cheyenne, &dylan]);
+
+        let cheyenne_project = {
+            let update = IndirectDelegation::try_from_iter(vec![
+                Right(cheyenne.current().clone()),
+                Right(dylan.current().clone()),
+            ])?;
+            Project::new(cheyenne.clone())?.update(None, update)
+        }?;
+        let dylan_project = Project::create_from(dylan.clone(), &cheyenne_project)?;
+        dylan_project.assert_verifies(lookup(&heads))?;
+
+        // Cheyenne proposes an update without having seen dylan's signature
+        let cheyenne_west = cheyenne_project.clone().change_description("West")?;
+        let err = dylan_project
+            .clone()
+            .update_from(&cheyenne_west)
+            .unwrap_err();
+        match err.downcast_ref::<error::Merge>() {
+            Some(error::Merge::Fork { ours, theirs }) => {
+                assert_eq!(ours.content_id, dylan_project.current().content_id);
+                assert_eq!(theirs.content_id, cheyenne_west.current().content_id);
+                assert_eq!(
+                    theirs.signers,
+                    Some(CHEYENNE_DESKTOP.public()).into_iter().collect()
+                );
+            },
+            other => panic!("expected fork, got {:?}", other),
+        }
+
+        // Once cheyenne builds on dylan's signature, the history stays linear
+        let cheyenne_project = cheyenne_project
+            .update_from(&dylan_project)?
+            .change_description("West")?;
+        let dylan_project = dylan_project.update_from(&cheyenne_project)?;
+        dylan_project.assert_verifies(lookup(&heads))
+    }
+}
+
+#[test]
+fn merge_stays_linear() -> anyhow::Result<()> {
+    let repo = tmp::repo()?;
+    {
+        let cheyenne = Device::new(&*CHEYENNE_DESKTOP, Identities::from(&*repo))?;
+        let dylan = Device::new(&*DYLAN, Identities::from(&*repo))?;
+
+        let heads = current_heads_from(vec![&cheyenne, &dylan]);
+
+        let cheyenne_project = {
+            let update = IndirectDelegation::try_from_iter(vec![
+                Right(cheyenne.current().clone()),
+                Right(dylan.current().clone()),
+            ])?;
+            Project::new(cheyenne)?.update(None, update)
+        }?;
+        let dylan_project = Project::create_from(dylan.clone(), &cheyenne_project)?;
+
+        // Merging a revision which builds on ours doesn't create a merge commit,
+        // even if the history mode doesn't require it
+        let cheyenne_project = cheyenne_project
+            .update_from(&dylan_project)?
+            .change_description("West")?;
+        let dylan_project = dylan_project.update_from(&cheyenne_project)?;
+        dylan_project.assert_verifies(lookup(&heads))?;
+        dylan
+            .git::<identities::Project>()
+            .with_history(HistoryMode::Linear)
+            .verify(*dylan_project.current().content_id, lookup(&heads))?;
 
         Ok(())
     }
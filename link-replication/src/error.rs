@@ -86,6 +86,9 @@ pub enum Validation {
 
     #[error("no data found for {0}")]
     NoData(LocalOrRemote),
+
+    #[error("signed refs of {remote} are newer than the revocation of its key at {effective}")]
+    Revoked { remote: PeerId, effective: u64 },
 }
 
 #[derive(Clone, Copy, Debug)]
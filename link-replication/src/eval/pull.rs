@@ -3,8 +3,13 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Debug,
+    marker::PhantomData,
+};
 
+use git_ref_format::RefString;
 use itertools::Itertools;
 
 use super::rad;
@@ -17,6 +22,7 @@ use crate::{
     sigrefs::{self, Refs},
     state::FetchState,
     validation,
+    CobPolicy,
     DataPolicy,
     Error,
     FetchLimit,
@@ -62,14 +68,19 @@ where
     let scx = state.as_shim(cx);
     let local_id = *LocalPeer::id(&scx);
     let delegates = VerifiedIdentity::delegate_ids(&anchor);
+    let revoked = VerifiedIdentity::revoked_ids(&anchor);
     let delegates_sans_local = delegates
         .iter()
         .filter(|id| *id != &local_id)
         .copied()
         .collect();
 
+    // Delegates are subject to the cob filters of their tracking entry, if
+    // any, but their data is always fetched
+    let mut cobs: BTreeMap<PeerId, CobPolicy> = BTreeMap::new();
     let tracked: BTreeMap<PeerId, peek::FetchSpec> = Tracking::tracked(&scx)?
-        .filter_map_ok(|(id, policy)| {
+        .filter_map_ok(|(id, policy, cob_policy)| {
+            cobs.insert(id, cob_policy);
             if !delegates.contains(&id) {
                 Some((
                     id,
@@ -93,6 +104,18 @@ where
         }))
         .collect::<Result<_, _>>()?;
 
+    // Revoked keys may not sign anything new, so only the sigrefs we already
+    // have are accepted from them.
+    let revoked_sigrefs = revoked
+        .keys()
+        .filter_map(|id| {
+            SignedRefs::load(cx, id, 0)
+                .map(|sigrefs| sigrefs.map(|s| (*id, s.at)))
+                .transpose()
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+    let mut refused = BTreeSet::new();
+
     info!("fetching verification refs");
     let peek = peek::ForFetch {
         local_id,
@@ -104,7 +127,7 @@ where
     state.step(cx, &peek)?;
 
     info!("loading sigrefs");
-    let signed_refs = sigrefs::combined(
+    let mut signed_refs = sigrefs::combined(
         &state.as_shim(cx),
         sigrefs::Select {
             must: &delegates_sans_local,
@@ -117,9 +140,25 @@ where
             cutoff: 2,
         },
     )?;
+    let mut warnings = Vec::new();
+    signed_refs.retain(|id, sigrefs| {
+        filter_cobs(&mut sigrefs.refs, cobs.get(id));
+        refuse_revoked(
+            id,
+            sigrefs,
+            &revoked_sigrefs,
+            &revoked,
+            &mut refused,
+            &mut warnings,
+        )
+    });
+    drop_refused(state.updates_mut(), &refused);
     debug!(?signed_refs);
 
     let mut transitive: BTreeMap<PeerId, DataPolicy> = BTreeMap::new();
+    // Transitively tracked peers are subject to the cob filters of all
+    // non-delegates tracking them.
+    let mut trans_cobs: BTreeMap<PeerId, Vec<&CobPolicy>> = BTreeMap::new();
     for (id, spec) in &peek.tracked {
         if let Some(sigrefs) = signed_refs.get(id) {
             for remote_id in &sigrefs.remotes {
@@ -137,9 +176,18 @@ where
                         }
                     })
                     .or_insert(spec.policy);
+                let policies = trans_cobs.entry(*remote_id).or_default();
+                if !spec.is_delegate {
+                    policies.extend(cobs.get(id));
+                }
             }
         }
     }
+    let cob_policies = |id: &PeerId| {
+        cobs.get(id)
+            .into_iter()
+            .chain(trans_cobs.get(id).into_iter().flatten().copied())
+    };
 
     let requires_confirmation = {
         info!("setting up local rad/ hierarchy");
@@ -228,7 +276,19 @@ where
                 .collect(),
             cutoff: 0,
         };
-        let trans_sigrefs = sigrefs::combined(&state.as_shim(cx), selector)?;
+        let mut trans_sigrefs = sigrefs::combined(&state.as_shim(cx), selector)?;
+        trans_sigrefs.retain(|id, sigrefs| {
+            filter_cobs(&mut sigrefs.refs, cob_policies(id));
+            refuse_revoked(
+                id,
+                sigrefs,
+                &revoked_sigrefs,
+                &revoked,
+                &mut refused,
+                &mut warnings,
+            )
+        });
+        drop_refused(state.updates_mut(), &refused);
         let trans_ids = state.id_tips().keys().copied().collect();
         debug!(?trans_sigrefs);
         let trans_fetch = fetch::Transitive {
@@ -259,7 +319,6 @@ where
     info!("updating signed refs");
     SignedRefs::update(cx)?;
 
-    let mut warnings = Vec::new();
     debug!(?signed_refs);
     info!("validating signed trees");
     for (peer, refs) in &signed_refs.refs {
@@ -279,11 +338,11 @@ where
             continue;
         }
         debug!("remote {}", peer);
-        let refs = SignedRefs::load(cx, peer, 0)
-            .map(|s| s.map(|Sigrefs { at, refs, .. }| Refs { at, refs }))?;
-        match refs {
+        match SignedRefs::load(cx, peer, 0)? {
             None => warnings.push(error::Validation::NoData((*peer).into())),
-            Some(refs) => {
+            Some(Sigrefs { at, mut refs, .. }) => {
+                filter_cobs(&mut refs, cob_policies(peer));
+                let refs = Refs { at, refs };
                 let ws = validation::validate::<U, _, _, _>(&*cx, peer, &refs)?;
                 debug_assert!(
                     ws.is_empty(),
@@ -303,3 +362,69 @@ where
         _marker: PhantomData,
     })
 }
+
+/// Returns `false` and records a warning if the `sigrefs` of `id` are not
+/// acceptable because its key was revoked.
+fn refuse_revoked<O>(
+    id: &PeerId,
+    sigrefs: &Sigrefs<O>,
+    local: &BTreeMap<PeerId, O>,
+    revoked: &BTreeMap<PeerId, u64>,
+    refused: &mut BTreeSet<PeerId>,
+    warnings: &mut Vec<error::Validation>,
+) -> bool
+where
+    O: PartialEq,
+{
+    match validation::revoked(id, sigrefs, local.get(id), revoked) {
+        None => true,
+        Some(warning) => {
+            warn!("refusing signed refs: {}", warning);
+            warnings.push(warning);
+            refused.insert(*id);
+            false
+        },
+    }
+}
+
+/// Remove the pending updates to the remote tracking refs of the `refused`
+/// peers, so their local refs stay as they were.
+fn drop_refused(pending: &mut Vec<Update<'static>>, refused: &BTreeSet<PeerId>) {
+    let prefixes = refused
+        .iter()
+        .map(|id| format!("refs/remotes/{}/", id))
+        .collect::<Vec<_>>();
+    pending.retain(|up| {
+        let name = up.refname().as_str();
+        !prefixes
+            .iter()
+            .any(|prefix| name.starts_with(prefix.as_str()))
+    })
+}
+
+/// Remove the collaborative objects not allowed by all of `policies` from
+/// the `signed` refs, so they are neither fetched nor expected to be present.
+fn filter_cobs<'a, O, P>(signed: &mut HashMap<RefString, O>, policies: P)
+where
+    P: IntoIterator<Item = &'a CobPolicy> + Clone,
+{
+    signed.retain(|name, _| {
+        let cob = name
+            .as_str()
+            .strip_prefix(refs::Prefix::Cobs.as_str())
+            .and_then(|cob| cob.split_once('/'));
+        match cob {
+            None => true,
+            Some((typename, id)) => {
+                let allowed = policies
+                    .clone()
+                    .into_iter()
+                    .all(|policy| policy.allows(typename, id));
+                if !allowed {
+                    debug!("skipping denied cob {}", name);
+                }
+                allowed
+            },
+        }
+    })
+}
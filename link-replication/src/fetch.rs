@@ -68,7 +68,8 @@ impl<T: AsRef<oid>> Negotiation for Fetch<T> {
                     Left(
                         refs::parsed::Rad::Id
                         | refs::parsed::Rad::SignedRefs
-                        | refs::parsed::Rad::Ids { .. },
+                        | refs::parsed::Rad::Ids { .. }
+                        | refs::parsed::Rad::IdProposals { .. },
                     ),
             } if self.signed_refs.remotes.contains(&remote_id) => {
                 Some(FilteredRef::new(tip, &remote_id, parsed))
@@ -3,7 +3,10 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{collections::BTreeSet, fmt::Debug};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+};
 
 use link_crypto::PeerId;
 use link_git::protocol::{oid, ObjectId};
@@ -13,8 +16,11 @@ use crate::{error, refs, Error, LocalPeer, Refdb};
 
 pub trait AnyIdentity {
     type Oid: AsRef<oid>;
+    type Urn: Urn + Ord;
 
     fn content_id(&self) -> Self::Oid;
+    fn revision(&self) -> Self::Oid;
+    fn urn(&self) -> Self::Urn;
 }
 
 pub trait VerifiedIdentity: Sized {
@@ -33,6 +39,11 @@ pub trait VerifiedIdentity: Sized {
     /// Set of all URNs this identity delegates to (ie. indirect delegations).
     /// Possibly empty.
     fn delegate_urns(&self) -> BTreeSet<Self::Urn>;
+
+    /// The [`PeerId`]s revoked by this identity, directly and indirectly, along
+    /// with the time (in seconds since the Unix epoch) the revocation takes
+    /// effect. Possibly empty.
+    fn revoked_ids(&self) -> BTreeMap<PeerId, u64>;
 }
 
 pub trait Urn: Sized {
@@ -46,7 +57,7 @@ pub trait Identities {
     type Urn: Urn;
     type Oid: AsRef<oid>;
 
-    type UnverifiedIdentity: AnyIdentity<Oid = Self::Oid>;
+    type UnverifiedIdentity: AnyIdentity<Oid = Self::Oid, Urn = Self::Urn>;
     type VerifiedIdentity: VerifiedIdentity<Oid = Self::Oid, Urn = Self::Urn>
         + Debug
         + Send
@@ -59,6 +70,11 @@ pub trait Identities {
     /// Get a verified identity by URN.
     fn get(&self, urn: &Self::Urn) -> Result<Option<Self::UnverifiedIdentity>, Self::LookupError>;
 
+    /// Load the identity revision at commit `head`, without verifying it.
+    fn load<H>(&self, head: H) -> Result<Self::UnverifiedIdentity, Self::LookupError>
+    where
+        H: AsRef<oid>;
+
     /// Verify the identity history with tip `head`.
     fn verify<H, F, T>(
         &self,
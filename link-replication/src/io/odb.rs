@@ -29,6 +29,14 @@ impl Odb {
 
         Ok(Self(Arc::new(odb::Odb { loose, packed })))
     }
+
+    /// Re-scan the packs of the object database.
+    ///
+    /// Lookups recover from packs removed by a repack on their own, but this
+    /// releases them early.
+    pub fn reload(&self) -> Result<(), Error> {
+        Ok(self.0.packed.index.reload()?)
+    }
 }
 
 impl Thickener for Odb {
@@ -527,14 +527,21 @@ impl<D: Odb> refdb::Refdb for Refdb<D> {
             .map(|RefEdit { change, name, .. }| {
                 let name = fullname_to_refstring(name)?;
                 let updated = match change {
-                    Change::Update { new, .. } => match new {
-                        Target::Peeled(oid) => Updated::Direct { name, target: oid },
+                    Change::Update { new, expected, .. } => match new {
+                        Target::Peeled(oid) => Updated::Direct {
+                            name,
+                            target: oid,
+                            prev: previous_oid(&expected),
+                        },
                         Target::Symbolic(sym) => Updated::Symbolic {
                             name,
                             target: fullname_to_refstring(sym)?,
                         },
                     },
-                    Change::Delete { .. } => Updated::Prune { name },
+                    Change::Delete { expected, .. } => Updated::Prune {
+                        name,
+                        prev: previous_oid(&expected),
+                    },
                 };
 
                 Ok(updated)
@@ -666,6 +673,13 @@ fn qualified_to_fullname(q: Qualified) -> FullName {
         .expect("`Qualified` is a valid `FullName`")
 }
 
+fn previous_oid(expected: &PreviousValue) -> Option<ObjectId> {
+    match expected {
+        PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(*oid),
+        _ => None,
+    }
+}
+
 fn fullname_to_refstring(name: FullName) -> Result<RefString, git_ref_format::Error> {
     RefString::try_from(Vec::from(name.into_inner()).into_string_lossy())
 }
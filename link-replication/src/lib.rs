@@ -49,13 +49,13 @@ mod success;
 pub use success::Success;
 
 mod track;
-pub use track::{DataPolicy, Rel as TrackingRel, Tracking};
+pub use track::{CobFilter, CobPolicy, DataPolicy, Rel as TrackingRel, Tracking};
 
 mod transmit;
 pub use transmit::{FilteredRef, LsRefs, Negotiation, Net, RefPrefix, WantsHaves};
 
 mod validation;
-pub use validation::validate;
+pub use validation::{revoked, validate};
 
 // Re-exports
 pub use link_git::{
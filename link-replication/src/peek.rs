@@ -13,6 +13,7 @@ pub use clone::ForClone;
 mod fetch;
 pub use fetch::{ForFetch, Spec as FetchSpec};
 
+/// Note that the prefix `refs/rad/id` also matches `refs/rad/id-proposals/*`.
 pub(crate) fn ref_prefixes(id: &PeerId, remote_id: &PeerId) -> impl Iterator<Item = RefPrefix> {
     IntoIterator::into_iter([
         refs::scoped(id, remote_id, refs::Owned::refs_rad_id()).into(),
@@ -3,6 +3,7 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
+use either::Either::Left;
 use link_crypto::PeerId;
 use link_git::protocol::Ref;
 use radicle_data::NonEmptyVec;
@@ -12,6 +13,7 @@ use crate::{
     error,
     ids,
     internal::{self, Layout, UpdateTips},
+    prepare,
     refs,
     transmit::{self, BuildWantsHaves, LsRefs},
     FetchState,
@@ -99,8 +101,14 @@ impl UpdateTips for ForClone {
         .map_err(|e| error::Prepare::Verification(e.into()))?;
 
         let tips = if verified.delegate_ids().contains(&self.remote_id) {
+            let urn = verified.urn();
             refs.iter()
-                .filter_map(FilteredRef::as_verification_ref_update)
+                .filter_map(|r| match &r.parsed.inner {
+                    Left(refs::parsed::Rad::IdProposals { revision }) => {
+                        prepare::proposal(cx, &urn, r, revision)
+                    },
+                    _ => r.as_verification_ref_update(),
+                })
                 .collect()
         } else {
             vec![]
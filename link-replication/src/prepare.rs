@@ -1,9 +1,9 @@
 // Copyright © 2022 The Radicle Link Contributors
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
-use git_ref_format::Qualified;
+use git_ref_format::{name, refname, Component, Qualified};
 use link_crypto::PeerId;
 use link_git::protocol::ObjectId;
 
@@ -58,6 +58,8 @@ where
 
         let mut tips_inner = Vec::with_capacity(refs.len());
         let mut track_inner = Vec::new();
+        let mut verified_urn = None;
+        let mut proposals = Vec::new();
         for r in refs {
             match &r.parsed.inner {
                 Left(refs::parsed::Rad::Selv) => {
@@ -95,10 +97,12 @@ where
                             // all refs for this remote_id
                             tips_inner.clear();
                             track_inner.clear();
+                            proposals.clear();
                             break;
                         },
 
-                        Ok(_) => {
+                        Ok(id) => {
+                            verified_urn = Some(id.urn());
                             if let Some(u) = r.as_verification_ref_update() {
                                 tips_inner.push(u)
                             }
@@ -106,6 +110,8 @@ where
                     }
                 },
 
+                Left(refs::parsed::Rad::IdProposals { revision }) => proposals.push((r, revision)),
+
                 Left(_) => {
                     if let Some(u) = r.as_verification_ref_update() {
                         tips_inner.push(u)
@@ -116,6 +122,19 @@ where
             }
         }
 
+        // Proposals are only accepted as revisions of the identity at
+        // `rad/id`, and are pruned once the remote no longer has them.
+        if let Some(urn) = verified_urn {
+            let mut accepted = HashSet::new();
+            for (r, revision) in proposals {
+                if let Some(u) = proposal(cx, &urn, r, revision) {
+                    accepted.insert(Qualified::from(r.to_remote_tracking()));
+                    tips_inner.push(u);
+                }
+            }
+            tips_inner.extend(prune_proposals(cx, remote_id, &accepted)?);
+        }
+
         updates.tips.append(&mut tips_inner);
         updates.track.append(&mut track_inner);
     }
@@ -123,6 +142,78 @@ where
     Ok(updates)
 }
 
+/// If `fr` is a proposed `revision` of the identity `urn`, convert it to an
+/// [`Update`].
+///
+/// Proposals are not signed by a quorum of delegates yet, and so can't be
+/// verified. They are, however, required to be revisions of the same identity.
+pub(crate) fn proposal<'a, C, T>(
+    cx: &C,
+    urn: &C::Urn,
+    fr: &'a FilteredRef<T>,
+    revision: &ObjectId,
+) -> Option<Update<'a>>
+where
+    C: Identities,
+    C::Urn: Ord,
+{
+    use ids::AnyIdentity as _;
+
+    match Identities::load(cx, fr.tip) {
+        Ok(proposal)
+            if proposal.urn() == *urn && proposal.revision().as_ref().to_owned() == *revision =>
+        {
+            fr.as_verification_ref_update()
+        },
+        Ok(_) => {
+            warn!(
+                remote_id = %fr.remote_id(),
+                revision = %revision,
+                "skipping proposal which is not a revision of the identity"
+            );
+            None
+        },
+        Err(e) => {
+            warn!(
+                err = %e,
+                remote_id = %fr.remote_id(),
+                revision = %revision,
+                "skipping invalid proposal"
+            );
+            None
+        },
+    }
+}
+
+/// Prune the proposals of `remote_id` we have, except for the `accepted` ones.
+fn prune_proposals<C>(
+    cx: &C,
+    remote_id: &PeerId,
+    accepted: &HashSet<Qualified<'_>>,
+) -> Result<Vec<Update<'static>>, error::Prepare>
+where
+    for<'b> &'b C: RefScan,
+{
+    let prefix = refname!("refs/remotes")
+        .join(Component::from(remote_id))
+        .join(name::RAD)
+        .join(refname!("id-proposals"));
+    let scan_err = |e: <&C as RefScan>::Error| error::Prepare::Scan { source: e.into() };
+
+    let mut prune = Vec::new();
+    for known in RefScan::scan(cx, prefix.as_str()).map_err(scan_err)? {
+        let refdb::Ref { name, target, .. } = known.map_err(scan_err)?;
+        if !accepted.contains(&name) {
+            prune.push(Update::Prune {
+                name,
+                prev: target.map_left(|oid| oid.into()),
+            });
+        }
+    }
+
+    Ok(prune)
+}
+
 /// If a top-level namespace exists for `id`, symref to it. Otherwise, create a
 /// direct ref.
 pub(crate) fn rad_self<'a, C, A>(
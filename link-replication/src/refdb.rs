@@ -173,9 +173,22 @@ impl SymrefTarget<'_> {
 
 #[derive(Clone, Debug)]
 pub enum Updated {
-    Direct { name: RefString, target: ObjectId },
-    Symbolic { name: RefString, target: RefString },
-    Prune { name: RefString },
+    Direct {
+        name: RefString,
+        target: ObjectId,
+        /// The target of the ref before the update, if it existed and was a
+        /// direct ref.
+        prev: Option<ObjectId>,
+    },
+    Symbolic {
+        name: RefString,
+        target: RefString,
+    },
+    Prune {
+        name: RefString,
+        /// The target of the ref before it was pruned, if it was a direct ref.
+        prev: Option<ObjectId>,
+    },
 }
 
 #[derive(Debug, Default)]
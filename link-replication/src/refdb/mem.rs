@@ -50,10 +50,11 @@ impl Refdb for Mem {
                     no_ff: _,
                 } => {
                     let name = name.into_owned();
-                    self.refs.insert(name.clone(), target);
+                    let prev = self.refs.insert(name.clone(), target);
                     ap.updated.push(Updated::Direct {
                         name: name.into_refstring(),
                         target,
+                        prev,
                     });
                 },
                 Update::Symbolic {
@@ -70,9 +71,10 @@ impl Refdb for Mem {
                 },
                 Update::Prune { name, prev: _ } => {
                     let name = name.into_owned();
-                    if self.refs.remove(&name).is_some() {
+                    if let Some(prev) = self.refs.remove(&name) {
                         ap.updated.push(Updated::Prune {
                             name: name.into_refstring(),
+                            prev: Some(prev),
                         })
                     }
                 },
@@ -10,13 +10,17 @@ use std::{
 
 use bstr::{BStr, ByteSlice as _};
 use either::Either;
-use git_ref_format::{lit, Component, Qualified, RefString};
+use git_ref_format::{lit, name, refname, Component, Qualified, RefString};
 use link_crypto::PeerId;
+use link_git::protocol::ObjectId;
 use thiserror::Error;
 
 use super::{Owned, RemoteTracking};
 use crate::ids;
 
+/// The category of proposed identity revisions, `refs/rad/id-proposals/*`.
+pub const ID_PROPOSALS: &str = "id-proposals";
+
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
@@ -112,6 +116,12 @@ where
                         let urn = Urn::try_from_id(id.as_str()).ok()?;
                         iter.next().is_none().then(|| Left(Rad::Ids { urn }))
                     },
+                    (ID_PROPOSALS, Some(revision)) => {
+                        let revision = ObjectId::from_hex(revision.as_str().as_bytes()).ok()?;
+                        iter.next()
+                            .is_none()
+                            .then(|| Left(Rad::IdProposals { revision }))
+                    },
 
                     _ => None,
                 },
@@ -161,6 +171,7 @@ pub enum Rad<Urn> {
     Selv, // self
     SignedRefs,
     Ids { urn: Urn },
+    IdProposals { revision: ObjectId },
 }
 
 impl<Urn> From<Rad<Urn>> for Qualified<'_>
@@ -179,6 +190,14 @@ where
                 Component::from_refstring(super::from_urn(&urn)).expect("urn is a valid component"),
             )
                 .into(),
+            Rad::IdProposals { revision } => (
+                lit::Refs,
+                name::RAD,
+                refname!("id-proposals").and(
+                    RefString::try_from(revision.to_string()).expect("revision is a valid refname"),
+                ),
+            )
+                .into(),
         }
     }
 }
@@ -80,6 +80,14 @@ impl<T> Default for Flattened<T> {
 pub struct Combined<Oid>(BTreeMap<PeerId, Sigrefs<Oid>>);
 
 impl<Oid> Combined<Oid> {
+    /// Retain only the sigrefs for which `f` returns `true`.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&PeerId, &mut Sigrefs<Oid>) -> bool,
+    {
+        self.0.retain(f)
+    }
+
     pub fn flattened(self) -> Flattened<Oid> {
         let mut refs = BTreeMap::new();
         let mut remotes = BTreeSet::new();
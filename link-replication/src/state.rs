@@ -316,6 +316,13 @@ where
         self.inner.get(urn)
     }
 
+    fn load<H>(&self, head: H) -> Result<Self::UnverifiedIdentity, Self::LookupError>
+    where
+        H: AsRef<oid>,
+    {
+        self.inner.load(head)
+    }
+
     fn verify<H, F, V>(
         &self,
         head: H,
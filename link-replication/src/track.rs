@@ -3,6 +3,8 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use either::Either;
 
 use crate::{PeerId, Urn};
@@ -31,11 +33,75 @@ impl Default for DataPolicy {
     }
 }
 
+/// Filter for the collaborative objects of a tracked peer, ie. the refs under
+/// `refs/cobs/<typename>/<object id>`.
+///
+/// Mirrors the `cobs` section of the rfc699 configuration, with type names and
+/// object identifiers in their textual form.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CobPolicy {
+    /// Filter for type names which have no entry in `types`. If `None`, those
+    /// types are denied.
+    pub wildcard: Option<CobFilter>,
+    /// Filters per type name, taking precedence over the `wildcard`.
+    pub types: BTreeMap<String, CobFilter>,
+}
+
+impl CobPolicy {
+    /// Allow all collaborative objects.
+    pub fn allow_all() -> Self {
+        Self {
+            wildcard: Some(CobFilter {
+                policy: DataPolicy::Allow,
+                objects: None,
+            }),
+            types: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the object `id` of type `typename` is allowed to be fetched.
+    pub fn allows(&self, typename: &str, id: &str) -> bool {
+        self.types
+            .get(typename)
+            .or_else(|| self.wildcard.as_ref())
+            .map(|filter| filter.allows(id))
+            .unwrap_or(false)
+    }
+}
+
+impl Default for CobPolicy {
+    fn default() -> Self {
+        Self::allow_all()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CobFilter {
+    pub policy: DataPolicy,
+    /// The objects the `policy` applies to, `None` meaning all objects. The
+    /// inverse `policy` applies to the complement.
+    pub objects: Option<BTreeSet<String>>,
+}
+
+impl CobFilter {
+    pub fn allows(&self, id: &str) -> bool {
+        let matches = self
+            .objects
+            .as_ref()
+            .map(|objs| objs.contains(id))
+            .unwrap_or(true);
+        match self.policy {
+            DataPolicy::Allow => matches,
+            DataPolicy::Deny => !matches,
+        }
+    }
+}
+
 pub trait Tracking {
     type Urn: Urn;
 
     type Updated: Iterator<Item = Either<PeerId, Self::Urn>>;
-    type Tracked: Iterator<Item = Result<(PeerId, DataPolicy), Self::TrackedError>>;
+    type Tracked: Iterator<Item = Result<(PeerId, DataPolicy, CobPolicy), Self::TrackedError>>;
 
     type TrackError: std::error::Error + Send + Sync + 'static;
     type TrackedError: std::error::Error + Send + Sync + 'static;
@@ -46,6 +112,7 @@ pub trait Tracking {
     where
         I: IntoIterator<Item = Rel<Self::Urn>>;
 
-    /// All tracked [`PeerId`]s in the context of the current [`Urn`].
+    /// All tracked [`PeerId`]s in the context of the current [`Urn`], along
+    /// with the policies for fetching their data and collaborative objects.
     fn tracked(&self) -> Result<Self::Tracked, Self::TrackedError>;
 }
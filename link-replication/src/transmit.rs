@@ -227,6 +227,12 @@ impl<T> FilteredRef<T> {
                 type_change: Policy::Allow,
             }),
 
+            Rad::IdProposals { .. } => Some(Update::Direct {
+                name: track_as.into(),
+                target: self.tip,
+                no_ff: Policy::Allow,
+            }),
+
             Rad::Selv => None,
         })
     }
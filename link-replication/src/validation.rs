@@ -3,14 +3,25 @@
 // This file is part of radicle-link, distributed under the GPLv3 with Radicle
 // Linking Exception. For full terms see the included LICENSE file.
 
-use std::{collections::HashSet, convert::TryFrom, fmt::Debug};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+    fmt::Debug,
+};
 
 use either::Either;
 use itertools::Itertools as _;
 use link_crypto::PeerId;
 use link_git::protocol::oid;
 
-use crate::{error, ids, refdb, refs, sigrefs::Refs, RefScan};
+use crate::{
+    error,
+    ids,
+    refdb,
+    refs,
+    sigrefs::{Refs, Sigrefs},
+    RefScan,
+};
 
 pub fn validate<'a, U, S, P, O>(
     scan: S,
@@ -39,6 +50,28 @@ where
     }
 }
 
+/// Refuse the `sigrefs` of `id` if its key was `revoked`, unless they are the
+/// ones already recorded `local`ly.
+///
+/// The commit time of the sigrefs is chosen by the signer, so it can't tell
+/// whether they were signed before the revocation. Sigrefs fetched earlier
+/// are kept, but no new ones are accepted.
+pub fn revoked<O>(
+    id: &PeerId,
+    sigrefs: &Sigrefs<O>,
+    local: Option<&O>,
+    revoked: &BTreeMap<PeerId, u64>,
+) -> Option<error::Validation>
+where
+    O: PartialEq,
+{
+    let effective = *revoked.get(id)?;
+    (local != Some(&sigrefs.at)).then(|| error::Validation::Revoked {
+        remote: *id,
+        effective,
+    })
+}
+
 struct SigTree<'a, Oid> {
     id: Option<&'a PeerId>,
     refs: &'a Refs<Oid>,
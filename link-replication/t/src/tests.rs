@@ -4,3 +4,5 @@
 // Linking Exception. For full terms see the included LICENSE file.
 
 mod refs;
+mod track;
+mod validation;
@@ -13,6 +13,7 @@ use link_replication::{
         parsed::{parse, Identity, Rad},
         Owned,
     },
+    ObjectId,
     Urn,
 };
 
@@ -134,6 +135,15 @@ fn rad_ids() {
     succeed::<Usize>(Left(Rad::Ids { urn: Usize(42) }), "refs/rad/ids/42");
 }
 
+#[test]
+fn rad_id_proposals() {
+    let revision = ObjectId::from_hex(b"c2e3b2e6f28d3b3e3a1b6e3d1f0a5e8b2c4d6f7a").unwrap();
+    succeed::<Identity>(
+        Left(Rad::IdProposals { revision }),
+        "refs/rad/id-proposals/c2e3b2e6f28d3b3e3a1b6e3d1f0a5e8b2c4d6f7a",
+    );
+}
+
 #[test]
 fn unknown_rad() {
     fail::<Identity>("refs/rad/asdf");
@@ -150,6 +160,8 @@ fn rad_extra_input() {
         "refs/rad/self/asdf",
         "refs/rad/signed_refs/qwert",
         "refs/rad/ids/42/blah",
+        "refs/rad/id-proposals/not-a-revision",
+        "refs/rad/id-proposals/c2e3b2e6f28d3b3e3a1b6e3d1f0a5e8b2c4d6f7a/blah",
         "refs/remotes/hyn3aar1qghrnjrdi161oks1w3z9s173mxti88ci6qthps8brmp6yo/rad/id/ygasd",
         "refs/remotes/hyn3aar1qghrnjrdi161oks1w3z9s173mxti88ci6qthps8brmp6yo/rad/self/knfbe",
         "refs/remotes/hyn3aar1qghrnjrdi161oks1w3z9s173mxti88ci6qthps8brmp6yo/rad/signed_refs/oiyb",
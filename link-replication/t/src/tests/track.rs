@@ -0,0 +1,48 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+
+use link_replication::{CobFilter, CobPolicy, DataPolicy};
+
+fn filter(policy: DataPolicy, objects: Option<&[&str]>) -> CobFilter {
+    CobFilter {
+        policy,
+        objects: objects.map(|objs| objs.iter().map(|obj| obj.to_string()).collect()),
+    }
+}
+
+#[test]
+fn allow_all() {
+    let policy = CobPolicy::allow_all();
+    assert!(policy.allows("discussions", "1"));
+    assert!(policy.allows("xyz.radicle.issue", "hnrk"));
+}
+
+#[test]
+fn unspecified_typename_is_denied() {
+    let policy = CobPolicy {
+        wildcard: None,
+        types: BTreeMap::from([(
+            "discussions".to_owned(),
+            filter(DataPolicy::Deny, Some(&["1", "2", "3"])),
+        )]),
+    };
+    assert!(!policy.allows("discussions", "1"));
+    assert!(policy.allows("discussions", "4"));
+    assert!(!policy.allows("issues", "1"));
+}
+
+#[test]
+fn typename_takes_precedence_over_wildcard() {
+    let policy = CobPolicy {
+        wildcard: Some(filter(DataPolicy::Allow, None)),
+        types: BTreeMap::from([(
+            "discussions".to_owned(),
+            filter(DataPolicy::Allow, Some(&["1"])),
+        )]),
+    };
+    assert!(policy.allows("discussions", "1"));
+    assert!(!policy.allows("discussions", "2"));
+    assert!(policy.allows("issues", "2"));
+}
@@ -0,0 +1,72 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use link_crypto::PeerId;
+use link_replication::{error, revoked, ObjectId, Sigrefs};
+use once_cell::sync::Lazy;
+
+static PEER: Lazy<PeerId> = Lazy::new(|| {
+    "hyn3aar1qghrnjrdi161oks1w3z9s173mxti88ci6qthps8brmp6yo"
+        .parse()
+        .unwrap()
+});
+
+static KNOWN: Lazy<ObjectId> =
+    Lazy::new(|| ObjectId::from_hex(b"c2e3b2e6f28d3b3e3a1b6e3d1f0a5e8b2c4d6f7a").unwrap());
+
+/// Sigrefs committed after the revocation, but with a commit time before it.
+static BACKDATED: Lazy<ObjectId> =
+    Lazy::new(|| ObjectId::from_hex(b"5d1e0c3b7a9f2e4d6c8b0a1f3e5d7c9b2a4f6e8d").unwrap());
+
+const EFFECTIVE: u64 = 1_650_000_000;
+
+fn sigrefs(at: ObjectId) -> Sigrefs<ObjectId> {
+    Sigrefs {
+        at,
+        refs: HashMap::new(),
+        remotes: BTreeSet::new(),
+    }
+}
+
+fn is_revoked(v: Option<error::Validation>) -> bool {
+    matches!(
+        v,
+        Some(error::Validation::Revoked { remote, effective })
+            if remote == *PEER && effective == EFFECTIVE
+    )
+}
+
+#[test]
+fn fetched_before_revocation() {
+    let revocations = BTreeMap::from([(*PEER, EFFECTIVE)]);
+    assert!(revoked(&PEER, &sigrefs(*KNOWN), Some(&KNOWN), &revocations).is_none())
+}
+
+#[test]
+fn backdated_after_revocation() {
+    let revocations = BTreeMap::from([(*PEER, EFFECTIVE)]);
+    assert!(is_revoked(revoked(
+        &PEER,
+        &sigrefs(*BACKDATED),
+        Some(&KNOWN),
+        &revocations
+    )))
+}
+
+#[test]
+fn unknown_after_revocation() {
+    let revocations = BTreeMap::from([(*PEER, EFFECTIVE)]);
+    assert!(is_revoked(revoked(
+        &PEER,
+        &sigrefs(*BACKDATED),
+        None,
+        &revocations
+    )))
+}
+
+#[test]
+fn not_revoked() {
+    assert!(revoked(&PEER, &sigrefs(*BACKDATED), Some(&KNOWN), &BTreeMap::new()).is_none())
+}
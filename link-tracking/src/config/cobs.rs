@@ -124,6 +124,11 @@ impl<Ty: Ord, Id: Ord> Cobs<Ty, Id> {
         self.0.get(&TypeName::Wildcard)
     }
 
+    /// Iterate over the type names and their [`Filter`]s.
+    pub fn iter(&self) -> impl Iterator<Item = (&TypeName<Ty>, &Filter<Id>)> {
+        self.0.iter()
+    }
+
     /// Insert the given `typename` and `filter`. If the entry already existed,
     /// the old [`Filter`] is replaced and returned.
     pub fn insert(&mut self, typename: TypeName<Ty>, filter: Filter<Id>) -> Option<Filter<Id>> {
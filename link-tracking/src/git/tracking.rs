@@ -23,6 +23,8 @@ use super::{
 pub mod batch;
 pub use batch::{batch, Action, Applied};
 pub mod error;
+pub mod manifest;
+pub use manifest::Manifest;
 pub mod policy;
 pub mod reference;
 pub use reference::{RefName, Remote};
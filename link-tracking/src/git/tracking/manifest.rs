@@ -0,0 +1,232 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A portable listing of tracking entries.
+//!
+//! A [`Manifest`] is serialised as a canonical JSON array of entries of the
+//! form:
+//!
+//! ```ignore
+//! {
+//!   "urn": <urn>,
+//!   "peer": (<peer id> | null),
+//!   "config": <config>
+//! }
+//! ```
+//!
+//! where a `null` peer denotes the `default` entry of the URN, and `<config>`
+//! is the tracking [`Config`]. A manifest exported from one device can be
+//! applied on another using [`Manifest::actions`] and [`super::batch`].
+
+use std::{borrow::Cow, convert::TryFrom, iter::FromIterator, str::FromStr};
+
+use link_canonical::{
+    json::{ToCjson, Value},
+    Canonical,
+};
+use link_crypto::PeerId;
+use link_identities::urn::Urn;
+use radicle_git_ext::Oid;
+
+use super::{policy, Action, Config, Tracked};
+
+const URN: &str = "urn";
+const PEER: &str = "peer";
+const CONFIG: &str = "config";
+
+pub mod error {
+    use thiserror::Error;
+
+    use crate::config;
+
+    #[derive(Debug, Error)]
+    pub enum Entry {
+        #[error("missing '{0}' key")]
+        Missing(&'static str),
+        #[error("expected type {expected}, but found {found}")]
+        MismatchedTy { expected: String, found: String },
+        #[error("failed to parse the URN")]
+        Urn(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("failed to parse the peer identifier")]
+        Peer(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        Config(#[from] config::error::Cjson),
+    }
+
+    #[derive(Debug, Error)]
+    pub enum Manifest {
+        #[error("failed to parse manifest: {0}")]
+        Bytes(String),
+        #[error("expected type {expected}, but found {found}")]
+        MismatchedTy { expected: String, found: String },
+        #[error(transparent)]
+        Entry(#[from] Entry),
+    }
+}
+
+/// A tracking entry of a [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub urn: Urn<Oid>,
+    /// The tracked peer, or `None` for the `default` entry of the `urn`.
+    pub peer: Option<PeerId>,
+    pub config: Config,
+}
+
+impl From<Tracked> for Entry {
+    fn from(tracked: Tracked) -> Self {
+        match tracked {
+            Tracked::Default { urn, config } => Self {
+                urn,
+                peer: None,
+                config,
+            },
+            Tracked::Peer { urn, peer, config } => Self {
+                urn,
+                peer: Some(peer),
+                config,
+            },
+        }
+    }
+}
+
+/// A list of tracking [`Entry`]s, see the [module documentation][self].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub entries: Vec<Entry>,
+}
+
+impl Manifest {
+    /// The [`Action`]s to create all entries of the manifest, each subject to
+    /// the same `policy`.
+    ///
+    /// Passing the actions to [`super::batch`] applies the manifest
+    /// atomically.
+    pub fn actions(&self, policy: policy::Track) -> impl Iterator<Item = Action<'_, Oid>> + '_ {
+        self.entries.iter().map(move |entry| Action::Track {
+            urn: Cow::Borrowed(&entry.urn),
+            peer: entry.peer,
+            config: &entry.config,
+            policy,
+        })
+    }
+}
+
+impl FromIterator<Tracked> for Manifest {
+    fn from_iter<T: IntoIterator<Item = Tracked>>(iter: T) -> Self {
+        Self {
+            entries: iter.into_iter().map(Entry::from).collect(),
+        }
+    }
+}
+
+impl ToCjson for Entry {
+    fn into_cjson(self) -> Value {
+        vec![
+            (URN, self.urn.to_string().into_cjson()),
+            (PEER, self.peer.map(|peer| peer.to_string()).into_cjson()),
+            (CONFIG, self.config.into_cjson()),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+impl ToCjson for Manifest {
+    fn into_cjson(self) -> Value {
+        self.entries.into_cjson()
+    }
+}
+
+impl Canonical for Manifest {
+    type Error = <Value as Canonical>::Error;
+
+    fn canonical_form(&self) -> Result<Vec<u8>, Self::Error> {
+        self.clone().into_cjson().canonical_form()
+    }
+}
+
+impl TryFrom<Value> for Entry {
+    type Error = error::Entry;
+
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
+        use error::Entry;
+
+        match val {
+            Value::Object(mut map) => {
+                let urn = match map.remove(&URN.into()).ok_or(Entry::Missing(URN))? {
+                    Value::String(urn) => urn
+                        .as_str()
+                        .parse()
+                        .map_err(|err| Entry::Urn(Box::new(err)))?,
+                    val => {
+                        return Err(Entry::MismatchedTy {
+                            expected: "string".to_string(),
+                            found: val.ty_name().to_string(),
+                        })
+                    },
+                };
+                let peer = match map.remove(&PEER.into()).ok_or(Entry::Missing(PEER))? {
+                    Value::Null => None,
+                    Value::String(peer) => Some(
+                        peer.as_str()
+                            .parse()
+                            .map_err(|err| Entry::Peer(Box::new(err)))?,
+                    ),
+                    val => {
+                        return Err(Entry::MismatchedTy {
+                            expected: "string or null".to_string(),
+                            found: val.ty_name().to_string(),
+                        })
+                    },
+                };
+                let config = map.remove(&CONFIG.into()).ok_or(Entry::Missing(CONFIG))?;
+                Ok(Self {
+                    urn,
+                    peer,
+                    config: Config::try_from(config)?,
+                })
+            },
+            val => Err(Entry::MismatchedTy {
+                expected: "object, keys: [\"config\", \"peer\", \"urn\"]".to_string(),
+                found: val.ty_name().to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<Value> for Manifest {
+    type Error = error::Manifest;
+
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
+        match val {
+            Value::Array(entries) => Ok(Self {
+                entries: entries
+                    .into_iter()
+                    .map(Entry::try_from)
+                    .collect::<Result<_, _>>()?,
+            }),
+            val => Err(error::Manifest::MismatchedTy {
+                expected: "array".to_string(),
+                found: val.ty_name().to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Manifest {
+    type Error = error::Manifest;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let val = Value::try_from(bytes).map_err(error::Manifest::Bytes)?;
+        Self::try_from(val)
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = error::Manifest;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.as_bytes())
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright © 2022 The Radicle Link Contributors
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryFrom as _;
+
+use link_canonical::Canonical as _;
+use link_crypto::{PeerId, SecretKey};
+use link_identities::urn::Urn;
+use link_tracking::{
+    config::Cobs,
+    git::{
+        config::Config,
+        tracking::{
+            manifest::{Entry, Manifest},
+            policy,
+            Action,
+        },
+    },
+};
+use radicle_git_ext::Oid;
+
+fn manifest() -> Manifest {
+    let urn = Urn::new(Oid::from(git2::Oid::zero()));
+    Manifest {
+        entries: vec![
+            Entry {
+                urn: urn.clone(),
+                peer: None,
+                config: Config::default(),
+            },
+            Entry {
+                urn,
+                peer: Some(PeerId::from(SecretKey::new())),
+                config: Config {
+                    data: false,
+                    cobs: Cobs::deny_all(),
+                },
+            },
+        ],
+    }
+}
+
+#[test]
+fn parse_commutes() {
+    let manifest = manifest();
+    let bytes = manifest.canonical_form().unwrap();
+    assert_eq!(Manifest::try_from(bytes.as_slice()).unwrap(), manifest);
+}
+
+#[test]
+fn default_entry_has_null_peer() {
+    let manifest = Manifest {
+        entries: manifest().entries.into_iter().take(1).collect(),
+    };
+    let json = String::from_utf8(manifest.canonical_form().unwrap()).unwrap();
+    assert_eq!(
+        json,
+        format!(
+            r#"[{{"config":{{"cobs":{{"*":{{"pattern":"*","policy":"allow"}}}},"data":true}},"peer":null,"urn":"{}"}}]"#,
+            manifest.entries[0].urn
+        )
+    );
+}
+
+#[test]
+fn actions_track_every_entry() {
+    let manifest = manifest();
+    let actions = manifest
+        .actions(policy::Track::MustNotExist)
+        .collect::<Vec<_>>();
+    assert_eq!(actions.len(), manifest.entries.len());
+    for (action, entry) in actions.iter().zip(&manifest.entries) {
+        match action {
+            Action::Track {
+                urn,
+                peer,
+                config,
+                policy,
+            } => {
+                assert_eq!(urn.as_ref(), &entry.urn);
+                assert_eq!(peer, &entry.peer);
+                assert_eq!(*config, &entry.config);
+                assert_eq!(policy, &policy::Track::MustNotExist);
+            },
+            Action::Untrack { .. } => panic!("manifest yielded untrack action"),
+        }
+    }
+}